@@ -1,15 +1,12 @@
-use std::str::FromStr;
-
-use itertools::Itertools;
-use tauri::State;
+use tauri::{AppHandle, Manager, Runtime, State};
 use uuid::Uuid;
 
 use crate::{
-    graphql::queries::{
-        get_games::GetGamesGames, get_matches::GetMatchesMatches,
-        get_tournaments::GetTournamentsTournamentsAll,
-    },
+    generator::{catalog, RaceInfo},
+    graphql::queries::get_tournaments::GetTournamentsTournamentsAll,
+    logging::{LogLevel, LogSettings, Logging, LOG_SETTINGS_FILE_NAME},
     services::tournament::{
+        config::ServiceConfig,
         payloads::UpdateGamePayload,
         service::TournamentService,
         types::{BargainsColor, GameFrontendModel, GameOutcome, GameResult, HeroFrontendModel, MatchFrontendModel, ModType, TournamentFrontendModel},
@@ -32,9 +29,8 @@ pub async fn load_tournament(
     tournament_id: Uuid,
 ) -> Result<TournamentFrontendModel, crate::error::Error> {
     let result = tournament_service.get_tournament(tournament_id).await?;
-    //println!("Tournament found: {:?}", &result);
     if let Some(tournament) = result {
-        Ok(TournamentFrontendModel::from(tournament))
+        TournamentFrontendModel::try_from(tournament)
     } else {
         Err(crate::error::Error::Other(format!("No tournament found with id {}", tournament_id)))
     }
@@ -48,12 +44,19 @@ pub async fn load_heroes(
 ) -> Result<(), crate::error::Error> {
     //let mod_type = ModType::from_str(&mod_type)?;
     let heroes = tournament_service.get_heroes(mod_type).await?;
-    //println!("Heroes: {:?}", &heroes);
     let mut current_heroes_locked = app_manager.current_heroes.write().await;
     *current_heroes_locked = heroes;
     Ok(())
 }
 
+/// Races of mod, same ones generator uses for stats tables.
+#[tauri::command]
+pub async fn load_races(
+    mod_type: ModType
+) -> Result<Vec<RaceInfo>, crate::error::Error> {
+    catalog::races(&mod_type)
+}
+
 #[tauri::command]
 pub async fn load_users(
     tournament_service: State<'_, TournamentService>,
@@ -103,7 +106,7 @@ pub async fn select_game(
     let game = games_locked.iter()
         .find(|g| g.id == game_id)
         .ok_or(crate::error::Error::Other("Game not found".to_string()))?;
-    Ok(game.into_frontend_model())
+    game.into_frontend_model()
 }
 
 #[tauri::command]
@@ -132,7 +135,7 @@ pub async fn update_game_first_player_race(
     race: i64,
 ) -> Result<(), crate::error::Error> {
     let payload = UpdateGamePayload::new(game_id).with_first_player_race(race);
-    Ok(tournament_service.update_game(payload).await?)
+    tournament_service.update_game(payload).await
 }
 
 #[tauri::command]
@@ -145,22 +148,21 @@ pub async fn update_game_first_player_hero(
     let payload = UpdateGamePayload::new(game_id).with_first_player_hero(hero);
     tournament_service.update_game(payload).await?;
     let mut games_locked = app_manager.current_games.write().await;
-    let focused_game = games_locked.iter_mut()
-        .find(|g| g.id == game_id)
-        .unwrap();
-    focused_game.first_player_hero = Some(hero);
+    // game may be not loaded in app, nothing to keep in sync then
+    if let Some(focused_game) = games_locked.iter_mut().find(|g| g.id == game_id) {
+        focused_game.first_player_hero = Some(hero);
+    }
     Ok(())
 }
 
 #[tauri::command]
 pub async fn update_game_second_player_race(
     tournament_service: State<'_, TournamentService>,
-    app_manager: State<'_, AppManager>,
     game_id: Uuid,
     race: i64,
 ) -> Result<(), crate::error::Error> {
     let payload = UpdateGamePayload::new(game_id).with_second_player_race(race);
-    Ok(tournament_service.update_game(payload).await?)
+    tournament_service.update_game(payload).await
 }
 
 #[tauri::command]
@@ -173,10 +175,10 @@ pub async fn update_game_second_player_hero(
     let payload = UpdateGamePayload::new(game_id).with_second_player_hero(hero);
     tournament_service.update_game(payload).await?;
     let mut games_locked = app_manager.current_games.write().await;
-    let focused_game = games_locked.iter_mut()
-        .find(|g| g.id == game_id)
-        .unwrap();
-    focused_game.second_player_hero = Some(hero);
+    // game may be not loaded in app, nothing to keep in sync then
+    if let Some(focused_game) = games_locked.iter_mut().find(|g| g.id == game_id) {
+        focused_game.second_player_hero = Some(hero);
+    }
     Ok(())
 }
 
@@ -187,7 +189,7 @@ pub async fn update_game_bargains_color(
     color: BargainsColor,
 ) -> Result<(), crate::error::Error> {
     let payload = UpdateGamePayload::new(game_id).with_bargains_color(color);
-    Ok(tournament_service.update_game(payload).await?)
+    tournament_service.update_game(payload).await
 }
 
 #[tauri::command]
@@ -197,7 +199,7 @@ pub async fn update_game_bargains_amount(
     amount: i64,
 ) -> Result<(), crate::error::Error> {
     let payload = UpdateGamePayload::new(game_id).with_bargains_amount(amount);
-    Ok(tournament_service.update_game(payload).await?)
+    tournament_service.update_game(payload).await
 }
 
 #[tauri::command]
@@ -207,7 +209,7 @@ pub async fn update_game_result(
     result: GameResult,
 ) -> Result<(), crate::error::Error> {
     let payload = UpdateGamePayload::new(game_id).with_result(result);
-    Ok(tournament_service.update_game(payload).await?)
+    tournament_service.update_game(payload).await
 }
 
 #[tauri::command]
@@ -217,5 +219,69 @@ pub async fn update_game_outcome(
     outcome: GameOutcome,
 ) -> Result<(), crate::error::Error> {
     let payload = UpdateGamePayload::new(game_id).with_outcome(outcome);
-    Ok(tournament_service.update_game(payload).await?)
+    tournament_service.update_game(payload).await
+}
+
+#[tauri::command]
+pub async fn load_service_config(
+    tournament_service: State<'_, TournamentService>
+) -> Result<ServiceConfig, crate::error::Error> {
+    Ok(tournament_service.config())
+}
+
+/// Switches service to new config and saves it so next start uses it too.
+#[tauri::command]
+pub async fn update_service_config<R: Runtime>(
+    app: AppHandle<R>,
+    tournament_service: State<'_, TournamentService>,
+    config: ServiceConfig
+) -> Result<(), crate::error::Error> {
+    tournament_service.reconfigure(config.clone())?;
+    let config_dir = app.path().app_config_dir()
+        .map_err(|error| crate::error::Error::Other(format!("No config directory: {error}")))?;
+    if let Some(settings_path) = ServiceConfig::settings_path(Some(&config_dir)) {
+        config.save(&settings_path)?;
+    }
+    Ok(())
+}
+
+/// Stops generation or export started with `operation_id`, other requests keep running.
+#[tauri::command]
+pub async fn cancel_operation(
+    tournament_service: State<'_, TournamentService>,
+    operation_id: Uuid
+) -> Result<(), crate::error::Error> {
+    tournament_service.cancel_operation(operation_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn load_log_level(
+    logging: State<'_, Logging>
+) -> Result<LogLevel, crate::error::Error> {
+    Ok(logging.level())
+}
+
+/// Changes level of log file right away and saves it for next start.
+#[tauri::command]
+pub async fn update_log_level<R: Runtime>(
+    app: AppHandle<R>,
+    logging: State<'_, Logging>,
+    level: LogLevel
+) -> Result<(), crate::error::Error> {
+    logging.set_level(level)?;
+    let config_dir = app.path().app_config_dir()
+        .map_err(|error| crate::error::Error::Other(format!("No config directory: {error}")))?;
+    LogSettings { level }.save(&config_dir.join(LOG_SETTINGS_FILE_NAME))?;
+    tracing::info!(%level, "Log level changed");
+    Ok(())
+}
+
+/// Text user can attach to bug report.
+#[tauri::command]
+pub async fn get_diagnostics(
+    logging: State<'_, Logging>,
+    tournament_service: State<'_, TournamentService>
+) -> Result<String, crate::error::Error> {
+    logging.diagnostics(&tournament_service.config())
 }
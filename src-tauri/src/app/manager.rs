@@ -1,4 +1,4 @@
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::RwLock;
 
 use crate::graphql::queries::{get_games::GetGamesGames, get_heroes::GetHeroesHeroesNewHeroesEntities, get_users::GetUsersUsers};
 
@@ -0,0 +1,394 @@
+use std::{path::PathBuf, process::ExitCode};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use h5_stats_generator_lib::{
+    error::Error,
+    generator::{
+        comparison::ComparisonSettings, games_export::export_games, head_to_head::HeadToHeadFormat, locale::Locale, output::OutputSettings, pipeline::{build_comparison_workbook, build_head_to_head, build_html_report, build_json_report, build_ratings_workbook, build_season_workbook, build_workbook, load_stats_model, save_head_to_head, tournament_name, WorkbookSettings},
+        heatmap::{Heatmap, HeatmapSettings, HeatmapTable}, progress::ProgressReporter, rating::RatingSettings, season::{Season, SeasonFilter}, snapshot::{TournamentSnapshot, TournamentSource}, validation::validate, winrate::WinrateSettings, TournamentStatsModel
+    },
+    logging::LogLevel,
+    services::tournament::{config::ServiceConfig, service::TournamentService, types::{GameType, ModType}}
+};
+use tracing_subscriber::filter::LevelFilter;
+use uuid::Uuid;
+
+#[derive(Debug, Parser)]
+#[command(name = "h5-stats", about = "Generates tournament statistics without GUI")]
+struct Cli {
+    /// Service settings file, H5_STATS_CONFIG is used if not set
+    #[arg(long, global = true, env = "H5_STATS_CONFIG")]
+    config: Option<PathBuf>,
+    /// Language of workbook and issue descriptions, `ru` or `en`
+    #[arg(long, global = true, default_value_t = Locale::Ru)]
+    locale: Locale,
+    /// Messages of this level and more severe are written to stderr
+    #[arg(long, global = true, default_value_t = LogLevel::Warn)]
+    log_level: LogLevel,
+    #[command(subcommand)]
+    command: Command
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    Xlsx,
+    Json,
+    /// Single page with sortable tables
+    Html,
+    /// Raw games, rejected ones are written into separate file
+    Csv
+}
+
+impl Format {
+    fn extension(&self) -> &'static str {
+        match self {
+            Format::Xlsx => "xlsx",
+            Format::Json => "json",
+            Format::Html => "html",
+            Format::Csv => "csv"
+        }
+    }
+}
+
+/// Where tournament data is taken from.
+#[derive(Debug, Args)]
+#[group(required = true, multiple = false)]
+struct SourceArgs {
+    /// Id of tournament to download from API
+    #[arg(long)]
+    tournament: Option<Uuid>,
+    /// Snapshot file saved with `snapshot` command, API isn't used then
+    #[arg(long)]
+    snapshot: Option<PathBuf>
+}
+
+impl SourceArgs {
+    async fn load(&self, tournament_service: &TournamentService) -> Result<TournamentStatsModel, Error> {
+        match (&self.tournament, &self.snapshot) {
+            (_, Some(snapshot)) => Ok(TournamentSnapshot::load(snapshot)?.model),
+            (Some(tournament), None) => load_stats_model(tournament_service, *tournament, &ProgressReporter::default()).await,
+            (None, None) => unreachable!("clap requires one of source options")
+        }
+    }
+}
+
+/// Several tournaments, ones from API go before snapshots.
+#[derive(Debug, Args)]
+#[group(required = true, multiple = true)]
+struct SourcesArgs {
+    /// Id of tournament to download from API, can be repeated
+    #[arg(long)]
+    tournament: Vec<Uuid>,
+    /// Snapshot file saved with `snapshot` command, can be repeated
+    #[arg(long)]
+    snapshot: Vec<PathBuf>
+}
+
+impl SourcesArgs {
+    async fn load(&self, tournament_service: &TournamentService) -> Result<Vec<TournamentStatsModel>, Error> {
+        let mut models = vec![];
+        for tournament in &self.tournament {
+            models.push(load_stats_model(tournament_service, *tournament, &ProgressReporter::default()).await?);
+        }
+        for snapshot in &self.snapshot {
+            models.push(TournamentSnapshot::load(snapshot)?.model);
+        }
+        Ok(models)
+    }
+}
+
+#[derive(Debug, Args)]
+struct WinrateArgs {
+    /// Winrates of fewer games are greyed out in workbook
+    #[arg(long)]
+    min_games: Option<u32>,
+    /// Don't add notes with confidence intervals to winrate cells
+    #[arg(long)]
+    no_intervals: bool
+}
+
+impl WinrateArgs {
+    fn into_settings(self) -> WinrateSettings {
+        WinrateSettings { show_intervals: !self.no_intervals, min_games: self.min_games }
+    }
+}
+
+#[derive(Debug, Args)]
+struct HeatmapArgs {
+    /// Highlighting of one table as `<table>=<off|color-scale|data-bar>`, can be repeated.
+    /// Tables: race-games, race-winrates, match-up-games, match-up-winrates, hero-picks, hero-winrates, hero-match-ups
+    #[arg(long, value_name = "TABLE=KIND", value_parser = parse_heatmap)]
+    heatmap: Vec<(HeatmapTable, Heatmap)>,
+    /// Don't highlight any table except ones set with --heatmap
+    #[arg(long)]
+    no_heatmaps: bool
+}
+
+impl HeatmapArgs {
+    fn into_settings(self) -> HeatmapSettings {
+        let settings = if self.no_heatmaps { HeatmapSettings::all(Heatmap::Off) } else { HeatmapSettings::default() };
+        self.heatmap.into_iter().fold(settings, |settings, (table, heatmap)| settings.with_table(table, heatmap))
+    }
+}
+
+fn parse_heatmap(value: &str) -> Result<(HeatmapTable, Heatmap), String> {
+    let (table, heatmap) = value.split_once('=').ok_or(format!("Expected TABLE=KIND, got {value}"))?;
+    Ok((
+        table.parse().map_err(|_| format!("Unknown table {table}"))?,
+        heatmap.parse().map_err(|_| format!("Unknown heatmap {heatmap}, expected off, color-scale or data-bar"))?
+    ))
+}
+
+#[derive(Debug, Args)]
+struct OutputArgs {
+    /// Exact file to save result to
+    #[arg(long, conflicts_with = "dir")]
+    out: Option<PathBuf>,
+    /// Directory to save result to, file name is built from template
+    #[arg(long, required_unless_present = "out")]
+    dir: Option<PathBuf>,
+    /// File name template, supports {tournament} and {date}
+    #[arg(long, requires = "dir")]
+    template: Option<String>,
+    /// Replace output file if it exists already
+    #[arg(long)]
+    force: bool
+}
+
+impl OutputArgs {
+    fn into_settings(self) -> OutputSettings {
+        let output = match (self.out, self.dir) {
+            (Some(out), _) => OutputSettings::with_path(out),
+            (None, Some(dir)) => OutputSettings::with_directory(dir, self.template),
+            (None, None) => unreachable!("clap requires one of output options")
+        };
+        output.overwrite(self.force)
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Prints ids and names of all known tournaments
+    List,
+    /// Prints problems found in tournament games
+    Validate {
+        #[command(flatten)]
+        source: SourceArgs
+    },
+    /// Downloads everything needed for generation into single file
+    Snapshot {
+        #[arg(long)]
+        tournament: Uuid,
+        #[command(flatten)]
+        output: OutputArgs
+    },
+    /// Builds stats workbook(or JSON/HTML/CSV export) for tournament
+    Generate {
+        #[command(flatten)]
+        source: SourceArgs,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = Format::Xlsx)]
+        format: Format,
+        #[command(flatten)]
+        winrates: WinrateArgs,
+        #[command(flatten)]
+        heatmaps: HeatmapArgs,
+        #[command(flatten)]
+        output: OutputArgs
+    },
+    /// Builds one stats workbook over several tournaments, ones of other mod or game type are skipped
+    Season {
+        #[command(flatten)]
+        sources: SourcesArgs,
+        /// Mod of tournaments, `Universe` or `Hrta`
+        #[arg(long)]
+        mod_type: ModType,
+        /// Game type of tournaments, `Rmg` or `Arena`
+        #[arg(long)]
+        game_type: GameType,
+        #[command(flatten)]
+        winrates: WinrateArgs,
+        #[command(flatten)]
+        heatmaps: HeatmapArgs,
+        #[command(flatten)]
+        output: OutputArgs
+    },
+    /// Builds workbook with changes of meta between two tournaments of one mod
+    Compare {
+        /// Id of earlier tournament or its snapshot file
+        #[arg(long)]
+        previous: TournamentSource,
+        /// Id of later tournament or its snapshot file
+        #[arg(long)]
+        current: TournamentSource,
+        /// Heroes picked in at least this share of race games are in the meta
+        #[arg(long, default_value_t = ComparisonSettings::default().meta_pick_ratio)]
+        meta_pick_ratio: f64,
+        #[command(flatten)]
+        output: OutputArgs
+    },
+    /// Builds report of games between two players over given tournaments, ones of other type than the first are skipped
+    HeadToHead {
+        #[command(flatten)]
+        sources: SourcesArgs,
+        /// Id of user whose point of view games are shown from
+        #[arg(long)]
+        first: Uuid,
+        /// Id of opponent
+        #[arg(long)]
+        second: Uuid,
+        /// Output format, `xlsx`, `html` or `json`
+        #[arg(long, default_value_t = HeadToHeadFormat::Xlsx)]
+        format: HeadToHeadFormat,
+        #[command(flatten)]
+        winrates: WinrateArgs,
+        #[command(flatten)]
+        output: OutputArgs
+    },
+    /// Replays games of tournaments in given order and builds Elo and Glicko-2 ratings of players
+    Ratings {
+        #[command(flatten)]
+        sources: SourcesArgs,
+        /// Elo rating change for single game
+        #[arg(long, default_value_t = RatingSettings::default().elo_k)]
+        elo_k: f64,
+        #[command(flatten)]
+        output: OutputArgs
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_max_level(LevelFilter::from(cli.log_level))
+        .init();
+    let tournament_service = match ServiceConfig::load(cli.config.as_deref()).and_then(TournamentService::new) {
+        Ok(service) => service,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let result = match cli.command {
+        Command::List => list(&tournament_service).await,
+        Command::Validate { source } => check(&tournament_service, source, cli.locale).await,
+        Command::Snapshot { tournament, output } => snapshot(&tournament_service, tournament, output.into_settings()).await,
+        Command::Generate { source, format, winrates, heatmaps, output } => {
+            let settings = WorkbookSettings { locale: cli.locale, winrates: winrates.into_settings(), heatmaps: heatmaps.into_settings() };
+            generate(&tournament_service, source, format, output.into_settings(), settings).await
+        },
+        Command::Season { sources, mod_type, game_type, winrates, heatmaps, output } => {
+            let settings = WorkbookSettings { locale: cli.locale, winrates: winrates.into_settings(), heatmaps: heatmaps.into_settings() };
+            season(&tournament_service, sources, SeasonFilter { mod_type, game_type }, output.into_settings(), settings).await
+        },
+        Command::Compare { previous, current, meta_pick_ratio, output } => {
+            compare(&tournament_service, previous, current, ComparisonSettings { meta_pick_ratio }, output.into_settings(), cli.locale).await
+        },
+        Command::HeadToHead { sources, first, second, format, winrates, output } => {
+            let settings = WorkbookSettings { locale: cli.locale, winrates: winrates.into_settings(), ..Default::default() };
+            head_to_head(&tournament_service, sources, first, second, format, output.into_settings(), settings).await
+        },
+        Command::Ratings { sources, elo_k, output } => ratings(&tournament_service, sources, elo_k, output.into_settings(), cli.locale).await
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn list(tournament_service: &TournamentService) -> Result<(), Error> {
+    for tournament in tournament_service.get_all_tournaments().await? {
+        println!("{}\t{}", tournament.id, tournament.name);
+    }
+    Ok(())
+}
+
+async fn check(tournament_service: &TournamentService, source: SourceArgs, locale: Locale) -> Result<(), Error> {
+    let tournament_stats_model = source.load(tournament_service).await?;
+    for issue in validate(&tournament_stats_model) {
+        let issue = issue.into_frontend_model(&tournament_stats_model, locale.messages());
+        println!("{:?}\t{}\t{}\t{}", issue.severity, issue.players, issue.game_id, issue.description);
+    }
+    Ok(())
+}
+
+async fn snapshot(tournament_service: &TournamentService, tournament_id: Uuid, output: OutputSettings) -> Result<(), Error> {
+    let snapshot = TournamentSnapshot::fetch(tournament_service, tournament_id, &ProgressReporter::default()).await?;
+    let output_path = output.resolve(tournament_name(&snapshot.model)?, "json")?;
+    snapshot.save(&output_path)?;
+    println!("{}", output_path.display());
+    Ok(())
+}
+
+async fn generate(tournament_service: &TournamentService, source: SourceArgs, format: Format, output: OutputSettings, settings: WorkbookSettings) -> Result<(), Error> {
+    let tournament_stats_model = source.load(tournament_service).await?;
+    let output_path = output.resolve(tournament_name(&tournament_stats_model)?, format.extension())?;
+    match format {
+        Format::Xlsx => build_workbook(&tournament_stats_model, &settings, &ProgressReporter::default())?.save(&output_path)?,
+        Format::Json => build_json_report(&tournament_stats_model)?.save(&output_path)?,
+        Format::Html => std::fs::write(&output_path, build_html_report(&tournament_stats_model, &settings)?)?,
+        Format::Csv => {
+            let paths = export_games(&tournament_stats_model, &output_path, output.overwrite)?;
+            println!("{}", paths.rejected.display());
+        }
+    }
+    println!("{}", output_path.display());
+    Ok(())
+}
+
+async fn season(tournament_service: &TournamentService, sources: SourcesArgs, filter: SeasonFilter, output: OutputSettings, settings: WorkbookSettings) -> Result<(), Error> {
+    let season = Season::merge(sources.load(tournament_service).await?, &filter)?;
+    let output_path = output.resolve(tournament_name(&season.model)?, "xlsx")?;
+    build_season_workbook(&season, &settings, &ProgressReporter::default())?.save(&output_path)?;
+    println!("{}", output_path.display());
+    Ok(())
+}
+
+async fn compare(
+    tournament_service: &TournamentService,
+    previous: TournamentSource,
+    current: TournamentSource,
+    settings: ComparisonSettings,
+    output: OutputSettings,
+    locale: Locale
+) -> Result<(), Error> {
+    let previous_model = previous.load(tournament_service, &ProgressReporter::default()).await?;
+    let current_model = current.load(tournament_service, &ProgressReporter::default()).await?;
+    let name = format!("{} - {}", tournament_name(&previous_model)?, tournament_name(&current_model)?);
+    let output_path = output.resolve(&name, "xlsx")?;
+    build_comparison_workbook(&previous_model, &current_model, &settings, locale)?.save(&output_path)?;
+    println!("{}", output_path.display());
+    Ok(())
+}
+
+async fn head_to_head(
+    tournament_service: &TournamentService,
+    sources: SourcesArgs,
+    first: Uuid,
+    second: Uuid,
+    format: HeadToHeadFormat,
+    output: OutputSettings,
+    settings: WorkbookSettings
+) -> Result<(), Error> {
+    let head_to_head = build_head_to_head(sources.load(tournament_service).await?, first, second, settings.locale)?;
+    let name = format!("{} vs {}", head_to_head.first.nickname, head_to_head.second.nickname);
+    let output_path = output.resolve(&name, format.extension())?;
+    save_head_to_head(&head_to_head, format, &settings, &output_path)?;
+    println!("{}", output_path.display());
+    Ok(())
+}
+
+async fn ratings(tournament_service: &TournamentService, sources: SourcesArgs, elo_k: f64, output: OutputSettings, locale: Locale) -> Result<(), Error> {
+    let models = sources.load(tournament_service).await?;
+    let last_model = models.last().ok_or(Error::Other("No tournaments given".to_string()))?;
+    let output_path = output.resolve(tournament_name(last_model)?, "xlsx")?;
+    let settings = RatingSettings { elo_k, ..Default::default() };
+    build_ratings_workbook(&models.iter().collect::<Vec<_>>(), &settings, locale)?.save(&output_path)?;
+    println!("{}", output_path.display());
+    Ok(())
+}
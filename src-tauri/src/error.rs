@@ -1,8 +1,11 @@
+use std::path::PathBuf;
+
 use rust_xlsxwriter::XlsxError;
 use strum::ParseError;
 use uuid::Uuid;
 
-#[derive(Debug, thiserror::Error)]
+/// Sent to frontend as `{ kind, message }`, `kind` is name of variant, so frontend can tell errors apart without parsing messages.
+#[derive(Debug, thiserror::Error, strum::IntoStaticStr)]
 pub enum Error {
     #[error(transparent)]
     Reqwest(#[from]reqwest::Error),
@@ -10,10 +13,36 @@ pub enum Error {
     Parse(#[from]ParseError),
     #[error(transparent)]
     Xlsx(#[from]XlsxError),
+    #[error(transparent)]
+    Io(#[from]std::io::Error),
+    #[error(transparent)]
+    Json(#[from]serde_json::Error),
+    #[error(transparent)]
+    Csv(#[from]csv::Error),
     #[error("Incorrect data for `{0}` request")]
     IncorrectData(String),
+    #[error("`{operation}` request failed: {}", format_graphql_errors(.errors))]
+    GraphQl {
+      operation: String,
+      errors: Vec<graphql_client::Error>
+    },
+    #[error("`{operation}` request failed with status {status}: {body}")]
+    Http {
+      operation: String,
+      status: u16,
+      body: String
+    },
     #[error("Some uncategorized error: `{0}`")]
     Other(String),
+    #[error("Unknown `{value}` value of `{enum_name}`")]
+    UnknownVariant {
+      enum_name: &'static str,
+      value: String
+    },
+    #[error("Operation was cancelled")]
+    Cancelled,
+    #[error("File {0:?} already exists")]
+    OutputExists(PathBuf),
     #[error("No `{field:?}` field found for game `{game_id:?}`")]
     NoGameField {
       field: String,
@@ -21,11 +50,40 @@ pub enum Error {
     }
 }
 
+// graphql_client's own Display adds locations that are meaningless for users
+fn format_graphql_errors(errors: &[graphql_client::Error]) -> String {
+    errors.iter()
+        .map(|error| {
+            let mut text = error.message.clone();
+            if let Some(path) = &error.path {
+                let path = path.iter().map(|fragment| fragment.to_string()).collect::<Vec<String>>().join("/");
+                text = format!("{path}: {text}");
+            }
+            if let Some(extensions) = error.extensions.as_ref().filter(|e| !e.is_empty()) {
+                text = format!("{text} {}", serde_json::Value::from_iter(extensions.clone()));
+            }
+            text
+        })
+        .collect::<Vec<String>>()
+        .join("; ")
+}
+
+impl Error {
+    /// Name of variant, e.g. `OutputExists`.
+    pub fn kind(&self) -> &'static str {
+      self.into()
+    }
+}
+
 impl serde::Serialize for Error {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
       S: serde::ser::Serializer,
     {
-      serializer.serialize_str(self.to_string().as_ref())
+      use serde::ser::SerializeStruct;
+      let mut error = serializer.serialize_struct("Error", 2)?;
+      error.serialize_field("kind", self.kind())?;
+      error.serialize_field("message", &self.to_string())?;
+      error.end()
     }
 }
\ No newline at end of file
@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::{error::Error, services::tournament::types::ModType};
+
+use super::{locale::Locale, RaceInfo, TournamentStatsModel};
+
+/// API doesn't provide races, so they are bundled with app for every mod together with translations of names.
+const CATALOG: &str = include_str!("../../resources/catalog.json");
+
+#[derive(Deserialize)]
+struct ModCatalog {
+    mod_type: ModType,
+    races: Vec<CatalogRace>
+}
+
+#[derive(Deserialize)]
+struct CatalogRace {
+    id: i64,
+    name: String,
+    #[serde(default)]
+    translations: HashMap<Locale, String>
+}
+
+fn mod_catalog(mod_type: &ModType) -> Result<ModCatalog, Error> {
+    let catalog: Vec<ModCatalog> = serde_json::from_str(CATALOG)?;
+    catalog.into_iter()
+        .find(|mod_catalog| mod_catalog.mod_type == *mod_type)
+        .ok_or(Error::Other(format!("No races known for mod {mod_type}")))
+}
+
+/// Races of mod in order they are laid out in stats tables.
+pub fn races(mod_type: &ModType) -> Result<Vec<RaceInfo>, Error> {
+    Ok(mod_catalog(mod_type)?.races.into_iter()
+        .map(|race| RaceInfo { id: race.id, name: race.name })
+        .collect())
+}
+
+/// Replaces names of races of model with ones catalog has for `locale`, names without translation are kept.
+/// Heroes are kept as API names them, their ids are assigned by API for every mod, so catalog can't refer to them.
+pub fn localize(model: &mut TournamentStatsModel, locale: Locale) -> Result<(), Error> {
+    let Some(tournament) = &model.tournament else {
+        return Ok(());
+    };
+    let mod_catalog = mod_catalog(&tournament.mod_type.clone().try_into()?)?;
+    for race in &mut model.races {
+        if let Some(name) = mod_catalog.races.iter().find(|r| r.id == race.id).and_then(|r| r.translations.get(&locale)) {
+            race.name = name.clone();
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use rust_xlsxwriter::{Chart, ChartLegendPosition};
+
+use super::locale::Messages;
+
+/// Charts are placed over cells, so their size is given in pixels.
+const CHART_WIDTH: u32 = 640;
+const CHART_HEIGHT: u32 = 360;
+/// Winrate axes don't need fractional percents of cells.
+const PERCENT_AXIS_FORMAT: &str = "0%";
+
+/// Cells of one table column charts take their series from.
+#[derive(Debug, Clone, Copy)]
+pub struct ColumnRange<'a> {
+    pub sheet: &'a str,
+    pub col: u16,
+    pub first_row: u32,
+    pub last_row: u32
+}
+
+impl<'a> ColumnRange<'a> {
+    pub fn new(sheet: &'a str, col: u16, first_row: u32, rows: u32) -> Self {
+        ColumnRange { sheet, col, first_row, last_row: first_row + rows.saturating_sub(1) }
+    }
+
+    fn cells(&self) -> (&'a str, u32, u16, u32, u16) {
+        (self.sheet, self.first_row, self.col, self.last_row, self.col)
+    }
+}
+
+/// Columns of race games with line of race winrates on secondary axis.
+pub fn race_games_chart(names: ColumnRange, games: ColumnRange, winrates: ColumnRange, messages: &Messages) -> Chart {
+    let mut winrates_chart = Chart::new_line();
+    winrates_chart.add_series()
+        .set_name(messages.total_winrate)
+        .set_categories(names.cells())
+        .set_values(winrates.cells())
+        .set_secondary_axis(true);
+
+    let mut chart = Chart::new_column();
+    chart.add_series()
+        .set_name(messages.total_games)
+        .set_categories(names.cells())
+        .set_values(games.cells());
+    chart.combine(&winrates_chart);
+    chart.title().set_name(messages.race_games_chart_title);
+    chart.y2_axis().set_num_format(PERCENT_AXIS_FORMAT);
+    chart.legend().set_position(ChartLegendPosition::Bottom);
+    chart.set_width(CHART_WIDTH).set_height(CHART_HEIGHT);
+    chart
+}
+
+/// Pie of hero pick rates of race.
+pub fn hero_pickrate_chart(names: ColumnRange, pickrates: ColumnRange, messages: &Messages) -> Chart {
+    let mut chart = Chart::new_pie();
+    chart.add_series()
+        .set_name(messages.pickrate)
+        .set_categories(names.cells())
+        .set_values(pickrates.cells());
+    chart.title().set_name(messages.hero_pickrate_chart_title);
+    chart.set_width(CHART_WIDTH).set_height(CHART_HEIGHT);
+    chart
+}
+
+/// Line of player winrate after every game, games are numbered on x axis.
+pub fn cumulative_winrate_chart(winrates: ColumnRange, messages: &Messages) -> Chart {
+    let mut chart = Chart::new_line();
+    chart.add_series()
+        .set_name(messages.cumulative_winrate)
+        .set_values(winrates.cells());
+    chart.title().set_name(messages.cumulative_winrate_chart_title);
+    chart.y_axis().set_num_format(PERCENT_AXIS_FORMAT).set_min(0).set_max(1);
+    chart.legend().set_hidden();
+    chart.set_width(CHART_WIDTH).set_height(CHART_HEIGHT);
+    chart
+}
@@ -1,50 +1,232 @@
-use rust_xlsxwriter::workbook::Workbook;
-use tauri::State;
+use std::path::PathBuf;
+
+use tauri::{AppHandle, Emitter, Runtime, State};
 use uuid::Uuid;
 use crate::error::Error as Error;
 
-use crate::generator::pair::PairStatsBuilder;
-use crate::generator::race::RaceStatsBuilder;
-use crate::generator::types::GameEntry;
-use crate::services::tournament::service::TournamentService;
-
-use super::player::build_player_stats;
-use super::TournamentStatsModel;
-
-#[tauri::command]
-pub async fn invoke_generation(
-    tournament_service: State<'_, TournamentService>,
-    tournament_id: Uuid
-) -> Result<(), Error> {
-    let mut tournament_stats_model = TournamentStatsModel::default();
-    let tournament = tournament_service.get_tournament(tournament_id).await?
-        .ok_or(Error::Other(format!("No tournament with id {}", tournament_id)))?;
-    let users = tournament_service.get_users(tournament_id).await?;
-    let matches = tournament_service.get_matches(tournament_id, None).await?;
-    let games = tournament_service.get_all_games(tournament_id).await?;
-    let games_converted = games.into_iter()
-        .filter_map(|game| {
-            if let Ok(converted_game) = GameEntry::try_from(game) {
-                Some(converted_game)
-            } else {
-                None
-            }
-        })
-        .collect::<Vec<GameEntry>>();
-    let heroes = tournament_service.get_heroes(tournament.mod_type.clone().into()).await?;
-    tournament_stats_model.tournament = Some(tournament);
-    tournament_stats_model.games = games_converted;
-    tournament_stats_model.heroes = heroes;
-    tournament_stats_model.users = users;
-    tournament_stats_model.matches = matches;
-
-    let mut workbook = Workbook::new();
-    let mut pair_stats_builder = PairStatsBuilder::new();
-    let mut race_stats_builder = RaceStatsBuilder::new();
-    pair_stats_builder.build(&tournament_stats_model, &mut workbook)?;
-    race_stats_builder.build(&tournament_stats_model, &mut workbook)?;
-    build_player_stats(&tournament_stats_model, &mut workbook)?;
-    println!("Done");
-    workbook.save("D:\\test.xlsx")?;
-    Ok(()) 
-}
\ No newline at end of file
+use crate::generator::comparison::ComparisonSettings;
+use crate::generator::games_export::{export_games, GamesExportPaths};
+use crate::generator::head_to_head::{HeadToHead, HeadToHeadFormat};
+use crate::generator::locale::Locale;
+use crate::generator::output::OutputSettings;
+use crate::generator::progress::{GenerationProgress, ProgressReporter, GENERATION_PROGRESS_EVENT};
+use crate::generator::season::{Season, SeasonFilter};
+use crate::generator::snapshot::{TournamentSnapshot, TournamentSource};
+use crate::generator::validation::{validate, GameIssueFrontendModel};
+use crate::services::tournament::service::{Operation, TournamentService};
+
+use super::pipeline::{
+    build_comparison_workbook, build_head_to_head, build_html_report, build_json_report, build_season_workbook, build_workbook, load_stats_model, load_stats_models, save_head_to_head,
+    tournament_name, WorkbookSettings
+};
+
+/// Reporter emitting progress to frontend, stops generation when `cancel_operation` is invoked with its id.
+fn progress_reporter<R: Runtime>(app: AppHandle<R>, operation: &Operation) -> ProgressReporter {
+    ProgressReporter::new(move |progress| {
+        // window may be closed already, nothing to do with it then
+        let _ = app.emit(GENERATION_PROGRESS_EVENT, progress);
+    }, operation.cancellation())
+}
+
+#[tauri::command]
+pub async fn invoke_generation<R: Runtime>(
+    app: AppHandle<R>,
+    tournament_service: State<'_, TournamentService>,
+    operation_id: Uuid,
+    tournament_id: Uuid,
+    output: OutputSettings,
+    settings: WorkbookSettings
+) -> Result<PathBuf, Error> {
+    let operation = tournament_service.start_operation(operation_id);
+    let progress = progress_reporter(app, &operation);
+    let tournament_stats_model = load_stats_model(&tournament_service, tournament_id, &progress).await?;
+    let output_path = output.resolve(tournament_name(&tournament_stats_model)?, "xlsx")?;
+    let mut workbook = build_workbook(&tournament_stats_model, &settings, &progress)?;
+    progress.report(GenerationProgress::Saving);
+    workbook.save(&output_path)?;
+    tracing::info!(path = %output_path.display(), "Workbook saved");
+    Ok(output_path)
+}
+
+/// Same as `invoke_generation`, but one workbook is built over games of all tournaments matching `filter`.
+#[tauri::command]
+pub async fn invoke_season_generation<R: Runtime>(
+    app: AppHandle<R>,
+    tournament_service: State<'_, TournamentService>,
+    operation_id: Uuid,
+    tournament_ids: Vec<Uuid>,
+    filter: SeasonFilter,
+    output: OutputSettings,
+    settings: WorkbookSettings
+) -> Result<PathBuf, Error> {
+    let operation = tournament_service.start_operation(operation_id);
+    let progress = progress_reporter(app, &operation);
+    let models = load_stats_models(&tournament_service, &tournament_ids, &progress).await?;
+    let season = Season::merge(models, &filter)?;
+    let output_path = output.resolve(tournament_name(&season.model)?, "xlsx")?;
+    let mut workbook = build_season_workbook(&season, &settings, &progress)?;
+    progress.report(GenerationProgress::Saving);
+    workbook.save(&output_path)?;
+    tracing::info!(path = %output_path.display(), tournaments = season.tournaments.len(), "Workbook saved");
+    Ok(output_path)
+}
+
+#[tauri::command]
+pub async fn invoke_json_export(
+    tournament_service: State<'_, TournamentService>,
+    operation_id: Uuid,
+    tournament_id: Uuid,
+    output: OutputSettings
+) -> Result<PathBuf, Error> {
+    let operation = tournament_service.start_operation(operation_id);
+    let progress = ProgressReporter::cancelled_by(operation.cancellation());
+    let tournament_stats_model = load_stats_model(&tournament_service, tournament_id, &progress).await?;
+    let output_path = output.resolve(tournament_name(&tournament_stats_model)?, "json")?;
+    build_json_report(&tournament_stats_model)?.save(&output_path)?;
+    Ok(output_path)
+}
+
+/// Saves stats as single HTML page with sortable tables, e.g. to host it after tournament.
+#[tauri::command]
+pub async fn invoke_html_export(
+    tournament_service: State<'_, TournamentService>,
+    operation_id: Uuid,
+    tournament_id: Uuid,
+    output: OutputSettings,
+    settings: WorkbookSettings
+) -> Result<PathBuf, Error> {
+    let operation = tournament_service.start_operation(operation_id);
+    let progress = ProgressReporter::cancelled_by(operation.cancellation());
+    let tournament_stats_model = load_stats_model(&tournament_service, tournament_id, &progress).await?;
+    let output_path = output.resolve(tournament_name(&tournament_stats_model)?, "html")?;
+    std::fs::write(&output_path, build_html_report(&tournament_stats_model, &settings)?)?;
+    tracing::info!(path = %output_path.display(), "HTML report saved");
+    Ok(output_path)
+}
+
+#[tauri::command]
+pub async fn invoke_games_export(
+    tournament_service: State<'_, TournamentService>,
+    operation_id: Uuid,
+    tournament_id: Uuid,
+    output: OutputSettings
+) -> Result<GamesExportPaths, Error> {
+    let operation = tournament_service.start_operation(operation_id);
+    let progress = ProgressReporter::cancelled_by(operation.cancellation());
+    let tournament_stats_model = load_stats_model(&tournament_service, tournament_id, &progress).await?;
+    let output_path = output.resolve(tournament_name(&tournament_stats_model)?, "csv")?;
+    export_games(&tournament_stats_model, &output_path, output.overwrite)
+}
+
+#[tauri::command]
+pub async fn invoke_validation(
+    tournament_service: State<'_, TournamentService>,
+    tournament_id: Uuid,
+    locale: Locale
+) -> Result<Vec<GameIssueFrontendModel>, Error> {
+    let tournament_stats_model = load_stats_model(&tournament_service, tournament_id, &ProgressReporter::default()).await?;
+    Ok(validate(&tournament_stats_model).iter()
+        .map(|issue| issue.into_frontend_model(&tournament_stats_model, locale.messages()))
+        .collect())
+}
+
+#[tauri::command]
+pub async fn invoke_snapshot_save(
+    tournament_service: State<'_, TournamentService>,
+    operation_id: Uuid,
+    tournament_id: Uuid,
+    output: OutputSettings
+) -> Result<PathBuf, Error> {
+    let operation = tournament_service.start_operation(operation_id);
+    let progress = ProgressReporter::cancelled_by(operation.cancellation());
+    let snapshot = TournamentSnapshot::fetch(&tournament_service, tournament_id, &progress).await?;
+    let output_path = output.resolve(tournament_name(&snapshot.model)?, "json")?;
+    snapshot.save(&output_path)?;
+    Ok(output_path)
+}
+
+/// Same as `invoke_generation`, but data is taken from snapshot file instead of API.
+#[tauri::command]
+pub async fn invoke_snapshot_generation<R: Runtime>(
+    app: AppHandle<R>,
+    tournament_service: State<'_, TournamentService>,
+    operation_id: Uuid,
+    snapshot_path: PathBuf,
+    output: OutputSettings,
+    settings: WorkbookSettings
+) -> Result<PathBuf, Error> {
+    let operation = tournament_service.start_operation(operation_id);
+    let progress = progress_reporter(app, &operation);
+    let snapshot = TournamentSnapshot::load(&snapshot_path)?;
+    let output_path = output.resolve(tournament_name(&snapshot.model)?, "xlsx")?;
+    let mut workbook = build_workbook(&snapshot.model, &settings, &progress)?;
+    progress.report(GenerationProgress::Saving);
+    workbook.save(&output_path)?;
+    tracing::info!(path = %output_path.display(), snapshot = %snapshot_path.display(), "Workbook saved");
+    Ok(output_path)
+}
+
+/// Builds workbook with changes of meta between two tournaments, each is taken from API or snapshot.
+#[tauri::command]
+pub async fn invoke_comparison(
+    tournament_service: State<'_, TournamentService>,
+    operation_id: Uuid,
+    previous: TournamentSource,
+    current: TournamentSource,
+    output: OutputSettings,
+    settings: ComparisonSettings,
+    locale: Locale
+) -> Result<PathBuf, Error> {
+    let operation = tournament_service.start_operation(operation_id);
+    let progress = ProgressReporter::cancelled_by(operation.cancellation());
+    let previous_model = previous.load(&tournament_service, &progress).await?;
+    let current_model = current.load(&tournament_service, &progress).await?;
+    let name = format!("{} - {}", tournament_name(&previous_model)?, tournament_name(&current_model)?);
+    let output_path = output.resolve(&name, "xlsx")?;
+    build_comparison_workbook(&previous_model, &current_model, &settings, locale)?.save(&output_path)?;
+    tracing::info!(path = %output_path.display(), "Comparison saved");
+    Ok(output_path)
+}
+
+/// Head-to-head of two players over games of `tournament_ids`, ones of other mod or game type than the first are skipped.
+#[tauri::command]
+pub async fn invoke_head_to_head(
+    tournament_service: State<'_, TournamentService>,
+    tournament_ids: Vec<Uuid>,
+    first_user: Uuid,
+    second_user: Uuid,
+    locale: Locale
+) -> Result<HeadToHead, Error> {
+    let models = load_stats_models(&tournament_service, &tournament_ids, &ProgressReporter::default()).await?;
+    build_head_to_head(models, first_user, second_user, locale)
+}
+
+/// Same as `invoke_head_to_head`, but report is saved as workbook, HTML page or JSON.
+/// Skipped tournaments are reported as warnings.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn invoke_head_to_head_export<R: Runtime>(
+    app: AppHandle<R>,
+    tournament_service: State<'_, TournamentService>,
+    operation_id: Uuid,
+    tournament_ids: Vec<Uuid>,
+    first_user: Uuid,
+    second_user: Uuid,
+    format: HeadToHeadFormat,
+    output: OutputSettings,
+    settings: WorkbookSettings
+) -> Result<PathBuf, Error> {
+    let operation = tournament_service.start_operation(operation_id);
+    let progress = progress_reporter(app, &operation);
+    let models = load_stats_models(&tournament_service, &tournament_ids, &progress).await?;
+    let head_to_head = build_head_to_head(models, first_user, second_user, settings.locale)?;
+    for name in &head_to_head.skipped {
+        let message = settings.locale.messages().season_tournament_skipped.replace("{tournament}", name);
+        progress.report(GenerationProgress::Warning { message });
+    }
+    let name = format!("{} vs {}", head_to_head.first.nickname, head_to_head.second.nickname);
+    let output_path = output.resolve(&name, format.extension())?;
+    save_head_to_head(&head_to_head, format, &settings, &output_path)?;
+    tracing::info!(path = %output_path.display(), "Head-to-head saved");
+    Ok(output_path)
+}
@@ -0,0 +1,238 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+use super::{stats::{RaceTotals, TournamentStats, WinLoss, WINRATE_CONFIDENCE_Z}, TournamentStatsModel};
+
+/// What counts as a meta change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ComparisonSettings {
+    /// Heroes picked in at least this share of race picks are in the meta, mirror is two picks.
+    pub meta_pick_ratio: f64
+}
+
+impl Default for ComparisonSettings {
+    fn default() -> Self {
+        ComparisonSettings { meta_pick_ratio: 0.05 }
+    }
+}
+
+/// `count` of `total`, e.g. wins of games or race picks of all picks.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Proportion {
+    pub count: u32,
+    pub total: u32
+}
+
+impl Proportion {
+    /// Share from 0 to 1, `None` if total is zero.
+    pub fn ratio(&self) -> Option<f64> {
+        if self.total == 0 {
+            None
+        } else {
+            Some(self.count as f64 / self.total as f64)
+        }
+    }
+}
+
+impl From<WinLoss> for Proportion {
+    fn from(value: WinLoss) -> Self {
+        Proportion { count: value.wins, total: value.games() }
+    }
+}
+
+/// The same proportion in previous and current tournament.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub struct Shift {
+    pub previous: Proportion,
+    pub current: Proportion
+}
+
+impl Shift {
+    /// Change of ratio, `None` if any tournament has nothing to compare.
+    pub fn delta(&self) -> Option<f64> {
+        Some(self.current.ratio()? - self.previous.ratio()?)
+    }
+
+    /// Pooled two-proportion z statistic of delta, `None` if it can't be computed.
+    pub fn z_score(&self) -> Option<f64> {
+        let delta = self.delta()?;
+        let (n1, n2) = (self.previous.total as f64, self.current.total as f64);
+        let pooled = (self.previous.count + self.current.count) as f64 / (n1 + n2);
+        let error = (pooled * (1.0 - pooled) * (1.0 / n1 + 1.0 / n2)).sqrt();
+        if error == 0.0 {
+            None
+        } else {
+            Some(delta / error)
+        }
+    }
+
+    /// Delta is unlikely to be noise, two-sided test at 95%.
+    pub fn is_significant(&self) -> bool {
+        self.z_score().is_some_and(|z| z.abs() >= WINRATE_CONFIDENCE_Z)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RaceShift {
+    pub race: i64,
+    /// Picks of race of picks of all races, mirror is two picks.
+    pub pickrate: Shift,
+    pub winrate: Shift
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchUpShift {
+    pub race: i64,
+    pub opponent_race: i64,
+    pub winrate: Shift
+}
+
+/// Hero that entered or dropped out of the meta, pick rate is of race picks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeroShift {
+    pub race: i64,
+    pub hero: i64,
+    pub pickrate: Shift
+}
+
+/// Average bargain of race, `None` for tournament without bargains.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BargainsShift {
+    pub race: i64,
+    pub previous: Option<f64>,
+    pub current: Option<f64>
+}
+
+impl BargainsShift {
+    pub fn delta(&self) -> Option<f64> {
+        Some(self.current? - self.previous?)
+    }
+}
+
+/// Changes of meta between two tournaments of one mod, races are in catalog order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TournamentComparison {
+    pub previous_name: String,
+    pub current_name: String,
+    pub races: Vec<RaceShift>,
+    pub match_ups: Vec<MatchUpShift>,
+    pub entered_heroes: Vec<HeroShift>,
+    pub dropped_heroes: Vec<HeroShift>,
+    /// Empty if neither tournament has bargains.
+    pub bargains: Vec<BargainsShift>
+}
+
+impl TournamentComparison {
+    pub fn compute(previous: &TournamentStatsModel, current: &TournamentStatsModel, settings: &ComparisonSettings) -> Result<Self, Error> {
+        let (previous_tournament, current_tournament) = match (&previous.tournament, &current.tournament) {
+            (Some(previous), Some(current)) => (previous, current),
+            _ => return Err(Error::Other("No tournament provided for comparison".to_string()))
+        };
+        if previous_tournament.mod_type != current_tournament.mod_type {
+            return Err(Error::Other(format!(
+                "Tournaments {} and {} are of different mods and can't be compared",
+                previous_tournament.name, current_tournament.name
+            )));
+        }
+        let previous_stats = TournamentStats::compute(previous)?;
+        let current_stats = TournamentStats::compute(current)?;
+
+        let races = current.races.iter()
+            .map(|race| RaceShift {
+                race: race.id,
+                pickrate: Shift { previous: race_picks(&previous_stats, race.id), current: race_picks(&current_stats, race.id) },
+                winrate: Shift {
+                    previous: previous_stats.race(race.id).map(|r| r.results.into()).unwrap_or_default(),
+                    current: current_stats.race(race.id).map(|r| r.results.into()).unwrap_or_default()
+                }
+            })
+            .collect();
+
+        let mut match_ups = vec![];
+        for race in &current.races {
+            for opponent_race in current.races.iter().filter(|r| r.id != race.id) {
+                let results = |stats: &TournamentStats| stats.race_pair(race.id, opponent_race.id)
+                    .map(|p| p.results.into())
+                    .unwrap_or_default();
+                match_ups.push(MatchUpShift {
+                    race: race.id,
+                    opponent_race: opponent_race.id,
+                    winrate: Shift { previous: results(&previous_stats), current: results(&current_stats) }
+                });
+            }
+        }
+
+        let in_meta = |pickrate: Proportion| pickrate.ratio().is_some_and(|ratio| ratio >= settings.meta_pick_ratio);
+        let mut entered_heroes = vec![];
+        let mut dropped_heroes = vec![];
+        for race in &current.races {
+            // heroes of other races can be picked too, so heroes are taken from games instead of catalog
+            let mut heroes = vec![];
+            for stats in [&current_stats, &previous_stats] {
+                for hero in stats.race_heroes(race.id).map(|r| r.heroes.as_slice()).unwrap_or_default() {
+                    if !heroes.contains(&hero.hero) {
+                        heroes.push(hero.hero);
+                    }
+                }
+            }
+            for hero in heroes {
+                let pickrate = Shift { previous: hero_picks(&previous_stats, race.id, hero), current: hero_picks(&current_stats, race.id, hero) };
+                match (in_meta(pickrate.previous), in_meta(pickrate.current)) {
+                    (false, true) => entered_heroes.push(HeroShift { race: race.id, hero, pickrate }),
+                    (true, false) => dropped_heroes.push(HeroShift { race: race.id, hero, pickrate }),
+                    _ => {}
+                }
+            }
+        }
+
+        let bargains = if previous_tournament.with_bargains || current_tournament.with_bargains {
+            current.races.iter()
+                .map(|race| BargainsShift {
+                    race: race.id,
+                    previous: previous_stats.race_bargains(race.id).map(|b| b.average_amount),
+                    current: current_stats.race_bargains(race.id).map(|b| b.average_amount)
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+
+        Ok(TournamentComparison {
+            previous_name: previous_tournament.name.clone(),
+            current_name: current_tournament.name.clone(),
+            races,
+            match_ups,
+            entered_heroes,
+            dropped_heroes,
+            bargains
+        })
+    }
+
+    pub fn race(&self, race: i64) -> Option<&RaceShift> {
+        self.races.iter().find(|r| r.race == race)
+    }
+
+    pub fn match_up(&self, race: i64, opponent_race: i64) -> Option<&MatchUpShift> {
+        self.match_ups.iter().find(|m| m.race == race && m.opponent_race == opponent_race)
+    }
+}
+
+fn race_picks(stats: &TournamentStats, race: i64) -> Proportion {
+    let picks = |totals: &RaceTotals| totals.results.games() + 2 * totals.mirrors;
+    Proportion {
+        count: stats.race(race).map(picks).unwrap_or(0),
+        total: stats.races.iter().map(picks).sum()
+    }
+}
+
+/// Picks of `hero` of all picks of `race`, mirror is two picks.
+fn hero_picks(stats: &TournamentStats, race: i64, hero: i64) -> Proportion {
+    stats.race_heroes(race)
+        .map(|race_heroes| Proportion {
+            count: race_heroes.heroes.iter().find(|h| h.hero == hero).map(|h| h.results.games()).unwrap_or(0),
+            total: race_heroes.total_picks
+        })
+        .unwrap_or_default()
+}
@@ -0,0 +1,172 @@
+use rust_xlsxwriter::{workbook::Workbook, worksheet::Worksheet, Format, Note};
+
+use crate::error::Error;
+
+use super::{
+    comparison::{HeroShift, Proportion, Shift, TournamentComparison}, heatmap::{HEATMAP_GREEN, HEATMAP_RED}, locale::Messages,
+    styles::{Style, STYLES}, TournamentStatsModel
+};
+
+/// Bargains are shown rounded to this many digits after point.
+const BARGAIN_DIGITS: i32 = 1;
+
+fn rounded(value: f64, digits: i32) -> f64 {
+    let factor = 10f64.powi(digits);
+    (value * factor).round() / factor
+}
+
+/// Adds tournament names and significance legend above table of `columns` columns, returns first free row.
+fn write_title(worksheet: &mut Worksheet, comparison: &TournamentComparison, messages: &Messages, columns: u16) -> Result<u32, Error> {
+    let title = messages.comparison_title
+        .replace("{previous}", &comparison.previous_name)
+        .replace("{current}", &comparison.current_name);
+    worksheet.merge_range(0, 0, 0, columns - 1, &title, STYLES.get(&Style::TextBoldCentered)?)?;
+    worksheet.merge_range(1, 0, 1, columns - 1, messages.comparison_significance_note, &Format::new())?;
+    Ok(2)
+}
+
+fn write_ratio(worksheet: &mut Worksheet, row: u32, col: u16, proportion: &Proportion) -> Result<(), Error> {
+    match proportion.ratio() {
+        Some(ratio) => worksheet.write_with_format(row, col, ratio, STYLES.get(&Style::ThinBorderPercent)?)?,
+        None => worksheet.write_blank(row, col, STYLES.get(&Style::ThinBorderPercent)?)?
+    };
+    Ok(())
+}
+
+/// Writes change of `shift`, significant growth is green and significant fall is red.
+fn write_delta(worksheet: &mut Worksheet, row: u32, col: u16, shift: &Shift) -> Result<(), Error> {
+    let format = STYLES.get(&Style::ThinBorderPercentDelta)?.clone();
+    match shift.delta() {
+        Some(delta) if shift.is_significant() => {
+            let color = if delta > 0.0 { HEATMAP_GREEN } else { HEATMAP_RED };
+            worksheet.write_with_format(row, col, delta, &format.set_background_color(color))?
+        },
+        Some(delta) => worksheet.write_with_format(row, col, delta, &format)?,
+        None => worksheet.write_blank(row, col, &format)?
+    };
+    Ok(())
+}
+
+/// Adds sheet with pick rates and winrates of races in both tournaments.
+pub fn build_comparison_races_sheet(model: &TournamentStatsModel, comparison: &TournamentComparison, messages: &Messages, workbook: &mut Workbook) -> Result<(), Error> {
+    let worksheet = workbook.add_worksheet().set_name(messages.comparison_races_sheet_name)?;
+    let header_row = write_title(worksheet, comparison, messages, messages.comparison_race_headers.len() as u16)?;
+    for (col, header) in messages.comparison_race_headers.iter().enumerate() {
+        worksheet.write_with_format(header_row, col as u16, *header, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+    }
+    worksheet.set_column_width(0, 25)?;
+    worksheet.set_column_range_width(1, messages.comparison_race_headers.len() as u16 - 1, 14)?;
+
+    for (index, shift) in comparison.races.iter().enumerate() {
+        let row = header_row + 1 + index as u32;
+        worksheet.write_with_format(row, 0, model.race_name(shift.race)?, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+        write_ratio(worksheet, row, 1, &shift.pickrate.previous)?;
+        write_ratio(worksheet, row, 2, &shift.pickrate.current)?;
+        write_delta(worksheet, row, 3, &shift.pickrate)?;
+        write_ratio(worksheet, row, 4, &shift.winrate.previous)?;
+        write_ratio(worksheet, row, 5, &shift.winrate.current)?;
+        write_delta(worksheet, row, 6, &shift.winrate)?;
+    }
+    Ok(())
+}
+
+/// Adds sheet with changes of match-up winrates laid out like match-ups table of race stats sheet.
+/// Every cell has note with both winrates and games they are computed from.
+pub fn build_comparison_match_ups_sheet(model: &TournamentStatsModel, comparison: &TournamentComparison, messages: &Messages, workbook: &mut Workbook) -> Result<(), Error> {
+    let worksheet = workbook.add_worksheet().set_name(messages.comparison_match_ups_sheet_name)?;
+    let header_row = write_title(worksheet, comparison, messages, model.races.len() as u16 + 1)?;
+    worksheet.write_with_format(header_row, 0, "VS", STYLES.get(&Style::BackgroundRed)?)?;
+    worksheet.set_column_width(0, 25)?;
+
+    for (index, race) in model.races.iter().enumerate() {
+        let row = header_row + 1 + index as u32;
+        worksheet.write_with_format(header_row, 1 + index as u16, &race.name, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+        worksheet.set_column_width(1 + index as u16, 14)?;
+        worksheet.write_with_format(row, 0, &race.name, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+        for (opponent_index, opponent_race) in model.races.iter().enumerate() {
+            let col = 1 + opponent_index as u16;
+            let Some(match_up) = comparison.match_up(race.id, opponent_race.id) else {
+                worksheet.set_cell_format(row, col, STYLES.get(&Style::BackgroundBlack)?)?;
+                continue;
+            };
+            let winrate = &match_up.winrate;
+            write_delta(worksheet, row, col, winrate)?;
+            if let (Some(previous), Some(current)) = (winrate.previous.ratio(), winrate.current.ratio()) {
+                let note = messages.comparison_shift_note
+                    .replace("{previous}", &format!("{:.1}", previous * 100.0))
+                    .replace("{previous_games}", &winrate.previous.total.to_string())
+                    .replace("{current}", &format!("{:.1}", current * 100.0))
+                    .replace("{current_games}", &winrate.current.total.to_string());
+                worksheet.insert_note(row, col, &Note::new(note).add_author_prefix(false))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Adds sheet with heroes that entered the meta and ones that dropped out of it, tables go one under another.
+pub fn build_comparison_heroes_sheet(model: &TournamentStatsModel, comparison: &TournamentComparison, messages: &Messages, workbook: &mut Workbook) -> Result<(), Error> {
+    let worksheet = workbook.add_worksheet().set_name(messages.comparison_heroes_sheet_name)?;
+    let columns = messages.comparison_hero_headers.len() as u16;
+    let mut row = write_title(worksheet, comparison, messages, columns)?;
+    worksheet.set_column_range_width(0, 1, 25)?;
+    worksheet.set_column_range_width(2, 3, 14)?;
+    for (title, heroes) in [
+        (messages.comparison_entered_heroes_title, &comparison.entered_heroes),
+        (messages.comparison_dropped_heroes_title, &comparison.dropped_heroes)
+    ] {
+        row = write_heroes_table(worksheet, model, title, heroes, messages, row)? + 1;
+    }
+    Ok(())
+}
+
+/// Returns row after the last one of table.
+fn write_heroes_table(
+    worksheet: &mut Worksheet,
+    model: &TournamentStatsModel,
+    title: &str,
+    heroes: &[HeroShift],
+    messages: &Messages,
+    mut row: u32
+) -> Result<u32, Error> {
+    worksheet.merge_range(row, 0, row, messages.comparison_hero_headers.len() as u16 - 1, title, STYLES.get(&Style::TextBoldCentered)?)?;
+    for (col, header) in messages.comparison_hero_headers.iter().enumerate() {
+        worksheet.write_with_format(row + 1, col as u16, *header, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+    }
+    row += 2;
+    for hero in heroes {
+        worksheet.write_with_format(row, 0, model.race_name(hero.race)?, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+        worksheet.write_with_format(row, 1, model.hero_name(hero.hero)?, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+        write_ratio(worksheet, row, 2, &hero.pickrate.previous)?;
+        write_ratio(worksheet, row, 3, &hero.pickrate.current)?;
+        row += 1;
+    }
+    Ok(row)
+}
+
+/// Adds sheet with average bargains of races, cells of tournament without bargains are blank.
+pub fn build_comparison_bargains_sheet(model: &TournamentStatsModel, comparison: &TournamentComparison, messages: &Messages, workbook: &mut Workbook) -> Result<(), Error> {
+    let worksheet = workbook.add_worksheet().set_name(messages.comparison_bargains_sheet_name)?;
+    let header_row = 1;
+    let title = messages.comparison_title
+        .replace("{previous}", &comparison.previous_name)
+        .replace("{current}", &comparison.current_name);
+    worksheet.merge_range(0, 0, 0, messages.comparison_bargains_headers.len() as u16 - 1, &title, STYLES.get(&Style::TextBoldCentered)?)?;
+    for (col, header) in messages.comparison_bargains_headers.iter().enumerate() {
+        worksheet.write_with_format(header_row, col as u16, *header, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+    }
+    worksheet.set_column_width(0, 25)?;
+    worksheet.set_column_range_width(1, 3, 16)?;
+
+    for (index, shift) in comparison.bargains.iter().enumerate() {
+        let row = header_row + 1 + index as u32;
+        worksheet.write_with_format(row, 0, model.race_name(shift.race)?, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+        for (col, value) in [(1, shift.previous), (2, shift.current), (3, shift.delta())] {
+            match value {
+                Some(value) => worksheet.write_with_format(row, col, rounded(value, BARGAIN_DIGITS), STYLES.get(&Style::ThinBorderTextWrap)?)?,
+                None => worksheet.write_blank(row, col, STYLES.get(&Style::ThinBorderTextWrap)?)?
+            };
+        }
+    }
+    Ok(())
+}
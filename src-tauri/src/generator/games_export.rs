@@ -0,0 +1,104 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{error::Error, services::tournament::types::{BargainsColor, GameOutcome, GameResult}};
+
+use super::{types::GameEntry, TournamentStatsModel};
+
+/// Single game with every id resolved into name, written as one CSV row.
+#[derive(Debug, Serialize)]
+struct GameRow<'a> {
+    match_id: Uuid,
+    first_player: String,
+    second_player: String,
+    first_player_race: String,
+    first_player_hero: String,
+    second_player_race: String,
+    second_player_hero: String,
+    bargains_amount: Option<i64>,
+    bargains_color: Option<&'a BargainsColor>,
+    result: &'a GameResult,
+    outcome: &'a GameOutcome
+}
+
+/// Header of games file, must follow fields of `GameRow`.
+const GAME_COLUMNS: [&str; 11] = [
+    "match_id", "first_player", "second_player", "first_player_race", "first_player_hero", "second_player_race", "second_player_hero",
+    "bargains_amount", "bargains_color", "result", "outcome"
+];
+
+impl<'a> GameRow<'a> {
+    fn new(model: &'a TournamentStatsModel, game: &'a GameEntry) -> Self {
+        // players of game from unknown match can't be found, such game is written with empty players
+        let (first_player, second_player) = match model.matches.iter().find(|m| m.id == game.match_id) {
+            Some(game_match) => (
+                name_or_id(model.user_name(game_match.first_player), game_match.first_player),
+                name_or_id(model.user_name(game_match.second_player), game_match.second_player)
+            ),
+            None => (String::new(), String::new())
+        };
+        GameRow {
+            match_id: game.match_id,
+            first_player,
+            second_player,
+            first_player_race: name_or_id(model.race_name(game.first_player_race), game.first_player_race),
+            first_player_hero: name_or_id(model.hero_name(game.first_player_hero), game.first_player_hero),
+            second_player_race: name_or_id(model.race_name(game.second_player_race), game.second_player_race),
+            second_player_hero: name_or_id(model.hero_name(game.second_player_hero), game.second_player_hero),
+            // -1 is what GameEntry uses for games without bargains
+            bargains_amount: if game.bargains_amount == -1 { None } else { Some(game.bargains_amount) },
+            bargains_color: game.bargains_color.as_ref(),
+            result: &game.result,
+            outcome: &game.outcome
+        }
+    }
+}
+
+// raw export keeps invalid games too, so unknown ids are written as is
+fn name_or_id(name: Result<&String, Error>, id: impl ToString) -> String {
+    name.cloned().unwrap_or_else(|_| id.to_string())
+}
+
+/// Files written by games export.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GamesExportPaths {
+    pub games: PathBuf,
+    pub rejected: PathBuf
+}
+
+/// Writes every converted game of model into `path` and every rejected one into file next to it with `_rejected` suffix.
+/// `path` itself is expected to be checked already by `OutputSettings::resolve`.
+pub fn export_games(model: &TournamentStatsModel, path: &Path, overwrite: bool) -> Result<GamesExportPaths, Error> {
+    let rejected_path = rejected_games_path(path);
+    if rejected_path.exists() && !overwrite {
+        return Err(Error::OutputExists(rejected_path));
+    }
+
+    // serialize doesn't write headers when there are no rows, so they are written manually
+    let mut writer = csv::WriterBuilder::new().has_headers(false).from_path(path)?;
+    writer.write_record(GAME_COLUMNS)?;
+    for game in &model.games {
+        writer.serialize(GameRow::new(model, game))?;
+    }
+    writer.flush()?;
+
+    let mut writer = csv::WriterBuilder::new().has_headers(false).from_path(&rejected_path)?;
+    writer.write_record(["game_id", "match_id", "reason"])?;
+    for game in &model.rejected_games {
+        writer.serialize(game)?;
+    }
+    writer.flush()?;
+
+    Ok(GamesExportPaths { games: path.to_path_buf(), rejected: rejected_path })
+}
+
+fn rejected_games_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let file_name = match path.extension() {
+        Some(extension) => format!("{stem}_rejected.{}", extension.to_string_lossy()),
+        None => format!("{stem}_rejected")
+    };
+    path.with_file_name(file_name)
+}
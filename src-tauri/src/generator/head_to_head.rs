@@ -0,0 +1,195 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+use uuid::Uuid;
+
+use crate::{error::Error, services::tournament::types::BargainsColor};
+
+use super::{stats::{PlayerGameRecord, PlayerStats, TournamentStats, WinLoss}, types::ResultOutput, TournamentStatsModel};
+
+/// Races opponent played most are compared, the rest are rarely picked in a final.
+const OPPONENT_MAIN_RACES: usize = 3;
+
+/// What head-to-head report is saved as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumString, Display)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum HeadToHeadFormat {
+    Xlsx,
+    Html,
+    Json
+}
+
+impl HeadToHeadFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            HeadToHeadFormat::Xlsx => "xlsx",
+            HeadToHeadFormat::Html => "html",
+            HeadToHeadFormat::Json => "json"
+        }
+    }
+}
+
+/// Results of player with some race or hero.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamedResults {
+    pub name: String,
+    pub results: WinLoss
+}
+
+/// How player bargains, amounts are gold player got(negative if player paid it).
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct BargainsTendency {
+    pub games: u32,
+    pub average_amount: Option<f64>,
+    pub plus_gold: u32,
+    pub minus_gold: u32
+}
+
+impl BargainsTendency {
+    fn compute<'a>(games: impl Iterator<Item = &'a PlayerGameRecord>) -> Self {
+        let mut tendency = BargainsTendency::default();
+        let mut amount_sum = 0;
+        for amount in games.filter_map(|g| g.bargains_amount) {
+            tendency.games += 1;
+            amount_sum += amount;
+            if amount > 0 {
+                tendency.plus_gold += 1;
+            } else if amount < 0 {
+                tendency.minus_gold += 1;
+            }
+        }
+        if tendency.games > 0 {
+            tendency.average_amount = Some(amount_sum as f64 / tendency.games as f64);
+        }
+        tendency
+    }
+}
+
+/// One side of head-to-head, picks and results are of games against the other player unless said otherwise.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeadToHeadPlayer {
+    pub user: Uuid,
+    pub nickname: String,
+    pub results: WinLoss,
+    pub races: Vec<NamedResults>,
+    pub heroes: Vec<NamedResults>,
+    /// Bargains in all games of player.
+    pub bargains: BargainsTendency,
+    pub bargains_vs_opponent: BargainsTendency,
+    /// Results of player in all games with races opponent played most, in order of opponent's preference.
+    pub in_opponent_main_races: Vec<NamedResults>
+}
+
+/// Game between players from the first player's point of view.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeadToHeadGame {
+    pub match_id: Uuid,
+    pub first_race: String,
+    pub first_hero: String,
+    pub second_race: String,
+    pub second_hero: String,
+    pub bargains_amount: Option<i64>,
+    pub bargains_color: Option<BargainsColor>,
+    pub first_won: bool
+}
+
+/// Everything casters need about two players before their match, names are already resolved.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeadToHead {
+    pub tournament: String,
+    pub with_bargains: bool,
+    pub with_bargains_color: bool,
+    pub first: HeadToHeadPlayer,
+    pub second: HeadToHeadPlayer,
+    /// Games in order of matches.
+    pub games: Vec<HeadToHeadGame>,
+    /// Names of tournaments left out of history, their mod or game type differs from the first one.
+    pub skipped: Vec<String>
+}
+
+impl HeadToHead {
+    pub fn compute(model: &TournamentStatsModel, stats: &TournamentStats, first: Uuid, second: Uuid) -> Result<Self, Error> {
+        let tournament = model.tournament.as_ref().ok_or(Error::Other("No tournament provided for generation".to_string()))?;
+        if first == second {
+            return Err(Error::Other("Head-to-head needs two different players".to_string()));
+        }
+        let player_stats = |user: Uuid| stats.players.iter()
+            .find(|p| p.user == user)
+            .ok_or(Error::Other(format!("No user found with id {}", user)));
+        let (first_stats, second_stats) = (player_stats(first)?, player_stats(second)?);
+
+        let games = first_stats.games.iter()
+            .filter(|g| g.opponent == second)
+            .map(|game| Ok(HeadToHeadGame {
+                match_id: game.match_id,
+                first_race: model.race_name(game.player_race)?.clone(),
+                first_hero: model.hero_name(game.player_hero)?.clone(),
+                second_race: model.race_name(game.opponent_race)?.clone(),
+                second_hero: model.hero_name(game.opponent_hero)?.clone(),
+                bargains_amount: game.bargains_amount,
+                bargains_color: game.bargains_color.clone(),
+                first_won: matches!(game.result, ResultOutput::Win)
+            }))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(HeadToHead {
+            tournament: tournament.name.clone(),
+            with_bargains: tournament.with_bargains,
+            with_bargains_color: tournament.with_bargains_color,
+            first: compute_player(model, first_stats, second_stats)?,
+            second: compute_player(model, second_stats, first_stats)?,
+            games,
+            skipped: vec![]
+        })
+    }
+}
+
+fn compute_player(model: &TournamentStatsModel, player: &PlayerStats, opponent: &PlayerStats) -> Result<HeadToHeadPlayer, Error> {
+    let games_vs_opponent = || player.games.iter().filter(|g| g.opponent == opponent.user);
+    let mut results = WinLoss::default();
+    let mut races: Vec<(i64, WinLoss)> = vec![];
+    let mut heroes: Vec<(i64, WinLoss)> = vec![];
+    for game in games_vs_opponent() {
+        let won = matches!(game.result, ResultOutput::Win);
+        for (picks, id) in [(&mut races, game.player_race), (&mut heroes, game.player_hero)] {
+            let index = match picks.iter().position(|(pick, _)| *pick == id) {
+                Some(index) => index,
+                None => {
+                    picks.push((id, WinLoss::default()));
+                    picks.len() - 1
+                }
+            };
+            picks[index].1.add_result(won);
+        }
+        results.add_result(won);
+    }
+
+    let mut opponent_races = opponent.races.iter().collect::<Vec<_>>();
+    // stable sort keeps order of first pick for races with equal games
+    opponent_races.sort_by_key(|race| std::cmp::Reverse(race.results.games()));
+    let in_opponent_main_races = opponent_races.iter()
+        .take(OPPONENT_MAIN_RACES)
+        .map(|race| Ok(NamedResults {
+            name: model.race_name(race.id)?.clone(),
+            results: player.races.iter()
+                .find(|r| r.id == race.id)
+                .map(|r| r.results)
+                .unwrap_or_default()
+        }))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(HeadToHeadPlayer {
+        user: player.user,
+        nickname: player.nickname.clone(),
+        results,
+        races: races.into_iter()
+            .map(|(race, results)| Ok(NamedResults { name: model.race_name(race)?.clone(), results }))
+            .collect::<Result<Vec<_>, Error>>()?,
+        heroes: heroes.into_iter()
+            .map(|(hero, results)| Ok(NamedResults { name: model.hero_name(hero)?.clone(), results }))
+            .collect::<Result<Vec<_>, Error>>()?,
+        bargains: BargainsTendency::compute(player.games.iter()),
+        bargains_vs_opponent: BargainsTendency::compute(games_vs_opponent()),
+        in_opponent_main_races
+    })
+}
@@ -0,0 +1,194 @@
+use rust_xlsxwriter::{workbook::Workbook, worksheet::Worksheet};
+
+use crate::error::Error;
+
+use super::{
+    head_to_head::{BargainsTendency, HeadToHead, HeadToHeadPlayer, NamedResults}, html::{percent, HtmlCell, HtmlPage}, locale::Messages,
+    player::bargains_color_name, styles::{Style, STYLES}, types::{GameHistoryEntry, PlayerMatchHistoryHeaders, ResultOutput}, winrate::WinrateWriter
+};
+
+/// Column of second player's tables, first player's ones take three columns from the left.
+const SECOND_PLAYER_COLUMN: u16 = 4;
+/// Bargains are shown rounded to this many digits after point.
+const BARGAIN_DIGITS: i32 = 1;
+
+fn rounded(value: f64, digits: i32) -> f64 {
+    let factor = 10f64.powi(digits);
+    (value * factor).round() / factor
+}
+
+fn title(head_to_head: &HeadToHead) -> String {
+    format!("{} vs {}", head_to_head.first.nickname, head_to_head.second.nickname)
+}
+
+fn score(head_to_head: &HeadToHead) -> String {
+    let results = &head_to_head.first.results;
+    format!("{} {}:{} {}", head_to_head.first.nickname, results.wins, results.losses, head_to_head.second.nickname)
+}
+
+/// Tables of player in order they are shown.
+fn results_tables<'a>(player: &'a HeadToHeadPlayer, messages: &Messages) -> [(&'static str, &'a [NamedResults]); 3] {
+    [
+        (messages.head_to_head_races_title, &player.races),
+        (messages.head_to_head_heroes_title, &player.heroes),
+        (messages.head_to_head_opponent_races_title, &player.in_opponent_main_races)
+    ]
+}
+
+/// Values of bargains table rows, average is `None` without bargain games.
+fn bargains_values(tendency: &BargainsTendency) -> [Option<f64>; 4] {
+    [
+        Some(tendency.games as f64),
+        tendency.average_amount.map(|amount| rounded(amount, BARGAIN_DIGITS)),
+        Some(tendency.plus_gold as f64),
+        Some(tendency.minus_gold as f64)
+    ]
+}
+
+/// Adds sheet with games between players from the first player's point of view and tables of both players under it.
+pub fn build_head_to_head_sheet(head_to_head: &HeadToHead, messages: &Messages, winrates: &WinrateWriter, workbook: &mut Workbook) -> Result<(), Error> {
+    let worksheet = workbook.add_worksheet().set_name(messages.head_to_head_sheet_name)?;
+    let headers = PlayerMatchHistoryHeaders::with_bargains(head_to_head.with_bargains, head_to_head.with_bargains_color, messages);
+    headers.to_xlsx(messages, worksheet)?;
+
+    let mut row = 2;
+    for game in &head_to_head.games {
+        let entry = GameHistoryEntry {
+            opponent: &head_to_head.second.nickname,
+            player_race: &game.first_race,
+            player_hero: &game.first_hero,
+            opponent_race: &game.second_race,
+            opponent_hero: &game.second_hero,
+            bargains_amount: game.bargains_amount,
+            bargains_color: bargains_color_name(&game.bargains_color, messages),
+            result: if game.first_won { ResultOutput::Win } else { ResultOutput::Loss },
+            outcome: None
+        };
+        entry.to_xlsx(messages, worksheet, row)?;
+        row += 1;
+    }
+    if head_to_head.games.is_empty() {
+        worksheet.merge_range(row, 0, row, headers.headers.len() as u16, messages.head_to_head_no_games, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+        row += 1;
+    }
+
+    row += 1;
+    worksheet.write_with_format(row, 0, messages.head_to_head_score, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+    worksheet.merge_range(row, 1, row, headers.headers.len() as u16, &score(head_to_head), STYLES.get(&Style::TextBoldCentered)?)?;
+
+    let tables_row = row + 2;
+    build_player_tables(head_to_head, &head_to_head.first, messages, winrates, worksheet, tables_row, 0)?;
+    build_player_tables(head_to_head, &head_to_head.second, messages, winrates, worksheet, tables_row, SECOND_PLAYER_COLUMN)?;
+    Ok(())
+}
+
+fn build_player_tables(
+    head_to_head: &HeadToHead,
+    player: &HeadToHeadPlayer,
+    messages: &Messages,
+    winrates: &WinrateWriter,
+    worksheet: &mut Worksheet,
+    row: u32,
+    col: u16
+) -> Result<(), Error> {
+    worksheet.merge_range(row, col, row, col + 2, &player.nickname, STYLES.get(&Style::TextBoldCentered)?)?;
+    let mut row = row + 2;
+    for (title, results) in results_tables(player, messages) {
+        worksheet.merge_range(row, col, row, col + 2, title, STYLES.get(&Style::TextBoldCentered)?)?;
+        worksheet.write_with_format(row + 1, col + 1, messages.total_games, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+        worksheet.write_with_format(row + 1, col + 2, messages.winrate, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+        row += 2;
+        for named in results {
+            worksheet.write_with_format(row, col, &named.name, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+            worksheet.write_with_format(row, col + 1, named.results.games(), STYLES.get(&Style::ThinBorderTextWrap)?)?;
+            winrates.write(worksheet, row, col + 2, &named.results, None, STYLES.get(&Style::ThinBorderPercent)?)?;
+            row += 1;
+        }
+        row += 1;
+    }
+
+    if !head_to_head.with_bargains {
+        return Ok(());
+    }
+    worksheet.merge_range(row, col, row, col + 2, messages.head_to_head_bargains_title, STYLES.get(&Style::TextBoldCentered)?)?;
+    for (index, column) in messages.head_to_head_bargains_columns.iter().enumerate() {
+        worksheet.write_with_format(row + 1, col + 1 + index as u16, *column, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+    }
+    let all = bargains_values(&player.bargains);
+    let vs_opponent = bargains_values(&player.bargains_vs_opponent);
+    for (index, name) in messages.head_to_head_bargains_names.iter().enumerate() {
+        let value_row = row + 2 + index as u32;
+        worksheet.write_with_format(value_row, col, *name, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+        for (value_col, value) in [(col + 1, all[index]), (col + 2, vs_opponent[index])] {
+            match value {
+                Some(value) => worksheet.write_with_format(value_row, value_col, value, STYLES.get(&Style::ThinBorderTextWrap)?)?,
+                None => worksheet.write_blank(value_row, value_col, STYLES.get(&Style::ThinBorderTextWrap)?)?
+            };
+        }
+    }
+    Ok(())
+}
+
+/// Renders the same report as standalone HTML page.
+pub fn render_head_to_head_page(head_to_head: &HeadToHead, messages: &Messages) -> String {
+    let mut page = HtmlPage::new(&title(head_to_head));
+    page.paragraph(&head_to_head.tournament)
+        .paragraph(&format!("{}: {}", messages.head_to_head_score, score(head_to_head)))
+        .heading(messages.games_history_title);
+
+    if head_to_head.games.is_empty() {
+        page.paragraph(messages.head_to_head_no_games);
+    } else {
+        let headers = PlayerMatchHistoryHeaders::with_bargains(head_to_head.with_bargains, head_to_head.with_bargains_color, messages);
+        let rows = head_to_head.games.iter()
+            .map(|game| {
+                let mut row = vec![
+                    HtmlCell::new(&game.first_race),
+                    HtmlCell::new(&game.first_hero),
+                    HtmlCell::new(&game.second_race),
+                    HtmlCell::new(&game.second_hero)
+                ];
+                if head_to_head.with_bargains {
+                    row.push(HtmlCell::new(game.bargains_amount.map(|amount| amount.to_string()).unwrap_or_default()));
+                }
+                if head_to_head.with_bargains_color {
+                    row.push(HtmlCell::new(bargains_color_name(&game.bargains_color, messages).unwrap_or_default()));
+                }
+                row.push(if game.first_won {
+                    HtmlCell::new(messages.victory).with_class("win")
+                } else {
+                    HtmlCell::new(messages.defeat).with_class("loss")
+                });
+                row
+            })
+            .collect::<Vec<_>>();
+        page.table(&headers.headers, &rows);
+    }
+
+    for player in [&head_to_head.first, &head_to_head.second] {
+        page.heading(&player.nickname);
+        for (title, results) in results_tables(player, messages) {
+            let rows = results.iter()
+                .map(|named| vec![
+                    HtmlCell::new(&named.name),
+                    HtmlCell::new(named.results.games()),
+                    HtmlCell::new(percent(named.results.win_ratio()))
+                ])
+                .collect::<Vec<_>>();
+            page.subheading(title)
+                .table(&["", messages.total_games, messages.winrate], &rows);
+        }
+        if head_to_head.with_bargains {
+            let (all, vs_opponent) = (bargains_values(&player.bargains), bargains_values(&player.bargains_vs_opponent));
+            let value = |value: Option<f64>| HtmlCell::new(value.map(|value| value.to_string()).unwrap_or_default());
+            let rows = messages.head_to_head_bargains_names.iter()
+                .enumerate()
+                .map(|(index, name)| vec![HtmlCell::new(name), value(all[index]), value(vs_opponent[index])])
+                .collect::<Vec<_>>();
+            let [all_games, against_opponent] = messages.head_to_head_bargains_columns;
+            page.subheading(messages.head_to_head_bargains_title)
+                .table(&["", all_games, against_opponent], &rows);
+        }
+    }
+    page.render()
+}
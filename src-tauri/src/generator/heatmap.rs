@@ -0,0 +1,177 @@
+use rust_xlsxwriter::{
+    utility::cell_range, worksheet::Worksheet, Color, ConditionalFormat2ColorScale, ConditionalFormat3ColorScale,
+    ConditionalFormatDataBar, ConditionalFormatType
+};
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+use crate::error::Error;
+
+/// Colors Excel uses for its own red-yellow-green scales.
+pub const HEATMAP_RED: Color = Color::RGB(0xF8696B);
+const HEATMAP_YELLOW: Color = Color::RGB(0xFFEB84);
+pub const HEATMAP_GREEN: Color = Color::RGB(0x63BE7B);
+const HEATMAP_WHITE: Color = Color::RGB(0xFFFFFF);
+
+/// How values of table are highlighted with conditional formatting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumString, Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum Heatmap {
+    Off,
+    ColorScale,
+    DataBar
+}
+
+/// Tables of workbook that can be highlighted, names are used in CLI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+#[strum(serialize_all = "kebab-case")]
+pub enum HeatmapTable {
+    RaceGames,
+    RaceWinrates,
+    MatchUpGames,
+    MatchUpWinrates,
+    HeroPicks,
+    HeroWinrates,
+    HeroMatchUps
+}
+
+/// Highlighting of every table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HeatmapSettings {
+    /// Total games of races on race stats sheet.
+    pub race_games: Heatmap,
+    /// Total winrates of races on race stats sheet.
+    pub race_winrates: Heatmap,
+    pub match_up_games: Heatmap,
+    pub match_up_winrates: Heatmap,
+    /// Games and pick rates in heroes table of race sheets.
+    pub hero_picks: Heatmap,
+    /// Winrates against races in heroes table of race sheets.
+    pub hero_winrates: Heatmap,
+    /// Wins(green) and losses(red) of race heroes against heroes of one opponent race.
+    pub hero_match_ups: Heatmap
+}
+
+impl Default for HeatmapSettings {
+    fn default() -> Self {
+        HeatmapSettings {
+            race_games: Heatmap::DataBar,
+            race_winrates: Heatmap::ColorScale,
+            match_up_games: Heatmap::ColorScale,
+            match_up_winrates: Heatmap::ColorScale,
+            hero_picks: Heatmap::DataBar,
+            hero_winrates: Heatmap::ColorScale,
+            hero_match_ups: Heatmap::ColorScale
+        }
+    }
+}
+
+impl HeatmapSettings {
+    /// The same highlighting for every table.
+    pub fn all(heatmap: Heatmap) -> Self {
+        HeatmapSettings {
+            race_games: heatmap,
+            race_winrates: heatmap,
+            match_up_games: heatmap,
+            match_up_winrates: heatmap,
+            hero_picks: heatmap,
+            hero_winrates: heatmap,
+            hero_match_ups: heatmap
+        }
+    }
+
+    pub fn with_table(mut self, table: HeatmapTable, heatmap: Heatmap) -> Self {
+        *match table {
+            HeatmapTable::RaceGames => &mut self.race_games,
+            HeatmapTable::RaceWinrates => &mut self.race_winrates,
+            HeatmapTable::MatchUpGames => &mut self.match_up_games,
+            HeatmapTable::MatchUpWinrates => &mut self.match_up_winrates,
+            HeatmapTable::HeroPicks => &mut self.hero_picks,
+            HeatmapTable::HeroWinrates => &mut self.hero_winrates,
+            HeatmapTable::HeroMatchUps => &mut self.hero_match_ups
+        } = heatmap;
+        self
+    }
+}
+
+/// Cells of table one format is applied to, last row and column are included.
+#[derive(Debug, Clone, Copy)]
+pub struct CellRange {
+    pub first_row: u32,
+    pub first_col: u16,
+    pub last_row: u32,
+    pub last_col: u16
+}
+
+impl CellRange {
+    pub fn column(col: u16, first_row: u32, rows: u32) -> Self {
+        CellRange { first_row, first_col: col, last_row: first_row + rows.saturating_sub(1), last_col: col }
+    }
+}
+
+impl Heatmap {
+    /// Highlights winrates written as fractions. Scale goes from red at 0% through yellow at 50% to green at 100%
+    /// no matter what values table has, so the same color means the same winrate everywhere.
+    pub fn add_winrates(self, worksheet: &mut Worksheet, ranges: &[CellRange]) -> Result<(), Error> {
+        let Some(first) = ranges.first() else {
+            return Ok(());
+        };
+        let (first_row, first_col, last_row, last_col) = (first.first_row, first.first_col, first.last_row, first.last_col);
+        match self {
+            Heatmap::Off => {},
+            Heatmap::ColorScale => {
+                worksheet.add_conditional_format(first_row, first_col, last_row, last_col, &ConditionalFormat3ColorScale::new()
+                    .set_minimum(ConditionalFormatType::Number, 0)
+                    .set_midpoint(ConditionalFormatType::Number, 0.5)
+                    .set_maximum(ConditionalFormatType::Number, 1)
+                    .set_minimum_color(HEATMAP_RED)
+                    .set_midpoint_color(HEATMAP_YELLOW)
+                    .set_maximum_color(HEATMAP_GREEN)
+                    .set_multi_range(multi_range(ranges)))?;
+            },
+            Heatmap::DataBar => {
+                worksheet.add_conditional_format(first_row, first_col, last_row, last_col, &ConditionalFormatDataBar::new()
+                    .set_minimum(ConditionalFormatType::Number, 0)
+                    .set_maximum(ConditionalFormatType::Number, 1)
+                    .set_fill_color(HEATMAP_GREEN)
+                    .set_multi_range(multi_range(ranges)))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Highlights counts from zero(white or empty bar) up to highest value of ranges in `color`.
+    pub fn add_counts(self, worksheet: &mut Worksheet, ranges: &[CellRange], color: Color) -> Result<(), Error> {
+        let Some(first) = ranges.first() else {
+            return Ok(());
+        };
+        let (first_row, first_col, last_row, last_col) = (first.first_row, first.first_col, first.last_row, first.last_col);
+        match self {
+            Heatmap::Off => {},
+            Heatmap::ColorScale => {
+                worksheet.add_conditional_format(first_row, first_col, last_row, last_col, &ConditionalFormat2ColorScale::new()
+                    .set_minimum(ConditionalFormatType::Number, 0)
+                    .set_minimum_color(HEATMAP_WHITE)
+                    .set_maximum_color(color)
+                    .set_multi_range(multi_range(ranges)))?;
+            },
+            Heatmap::DataBar => {
+                worksheet.add_conditional_format(first_row, first_col, last_row, last_col, &ConditionalFormatDataBar::new()
+                    .set_minimum(ConditionalFormatType::Number, 0)
+                    .set_fill_color(color)
+                    .set_multi_range(multi_range(ranges)))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// All ranges share one format, so its scale is computed over all of them.
+fn multi_range(ranges: &[CellRange]) -> String {
+    ranges.iter()
+        .map(|r| cell_range(r.first_row, r.first_col, r.last_row, r.last_col))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,240 @@
+use std::fmt::Write;
+
+/// Styles of generated pages, kept inline so a page is a single file that can be sent or hosted as is.
+const PAGE_STYLE: &str = "\
+body { font-family: sans-serif; margin: 24px; color: #222; }
+table { border-collapse: collapse; margin: 8px 0 24px; }
+th, td { border: 1px solid #999; padding: 4px 8px; text-align: center; }
+th { background: #eee; }
+table.data th { cursor: pointer; user-select: none; }
+th[data-order=\"asc\"]::after { content: \" \\25B2\"; }
+th[data-order=\"desc\"]::after { content: \" \\25BC\"; }
+input.filter { margin-top: 8px; padding: 4px; width: 240px; }
+nav ul { columns: 3; }
+.win { background: #63be7b; }
+.loss { background: #f8696b; }
+.muted { color: #888; }
+";
+
+/// Sorting by click on header and filtering of data tables, switching of sections by link anchor.
+/// Cells are compared as numbers when their text starts with one, empty cells go last.
+const PAGE_SCRIPT: &str = r#"
+function sortValue(cell) {
+    const text = cell ? cell.textContent.trim() : "";
+    const number = parseFloat(text);
+    return isNaN(number) ? text : number;
+}
+function compareValues(a, b) {
+    if (a === "" || b === "") return (a === "") - (b === "");
+    if (typeof a !== typeof b) return typeof a === "number" ? -1 : 1;
+    return a < b ? -1 : a > b ? 1 : 0;
+}
+document.querySelectorAll("table.data").forEach((table) => {
+    const body = table.tBodies[0];
+    const headers = table.querySelectorAll("th");
+    headers.forEach((header, column) => header.addEventListener("click", () => {
+        const descending = header.dataset.order !== "desc";
+        headers.forEach((other) => delete other.dataset.order);
+        header.dataset.order = descending ? "desc" : "asc";
+        Array.from(body.rows)
+            .sort((a, b) => {
+                const [x, y] = [sortValue(a.cells[column]), sortValue(b.cells[column])];
+                return x === "" || y === "" ? compareValues(x, y) : compareValues(x, y) * (descending ? -1 : 1);
+            })
+            .forEach((row) => body.appendChild(row));
+    }));
+    const filter = table.previousElementSibling;
+    filter.addEventListener("input", () => {
+        const text = filter.value.toLowerCase();
+        Array.from(body.rows).forEach((row) => row.hidden = !row.textContent.toLowerCase().includes(text));
+    });
+});
+function showSection() {
+    const sections = document.querySelectorAll("section");
+    if (sections.length === 0) return;
+    const target = location.hash ? document.getElementById(decodeURIComponent(location.hash.slice(1))) : null;
+    const shown = (target && target.closest("section")) || sections[0];
+    sections.forEach((section) => section.hidden = section !== shown);
+    window.scrollTo(0, 0);
+}
+window.addEventListener("hashchange", showSection);
+showSection();
+"#;
+
+/// Red-yellow-green scale of workbook heatmaps.
+const HEAT_RED: (u8, u8, u8) = (0xF8, 0x69, 0x6B);
+const HEAT_YELLOW: (u8, u8, u8) = (0xFF, 0xEB, 0x84);
+const HEAT_GREEN: (u8, u8, u8) = (0x63, 0xBE, 0x7B);
+
+/// Escapes text for HTML content and attribute values.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c)
+        }
+    }
+    escaped
+}
+
+/// Fraction as percents with one digit after point, empty if there is nothing to show.
+pub fn percent(ratio: Option<f64>) -> String {
+    ratio.map(|ratio| format!("{:.1}%", ratio * 100.0)).unwrap_or_default()
+}
+
+/// Color of `ratio` from 0(red) through 0.5(yellow) to 1(green).
+fn heat_color(ratio: f64) -> String {
+    let ratio = ratio.clamp(0.0, 1.0);
+    let (from, to, part) = if ratio < 0.5 { (HEAT_RED, HEAT_YELLOW, ratio * 2.0) } else { (HEAT_YELLOW, HEAT_GREEN, ratio * 2.0 - 1.0) };
+    let mix = |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * part).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+}
+
+/// Table cell, text is escaped when page is rendered.
+#[derive(Debug, Clone)]
+pub struct HtmlCell {
+    text: String,
+    class: Option<&'static str>,
+    heat: Option<f64>,
+    link: Option<String>
+}
+
+impl HtmlCell {
+    pub fn new(text: impl ToString) -> Self {
+        HtmlCell { text: text.to_string(), class: None, heat: None, link: None }
+    }
+
+    /// Class from page style, e.g. `win` or `loss`.
+    pub fn with_class(mut self, class: &'static str) -> Self {
+        self.class = Some(class);
+        self
+    }
+
+    /// Background from heatmap scale, `ratio` is from 0 to 1.
+    pub fn with_heat(mut self, ratio: f64) -> Self {
+        self.heat = Some(ratio);
+        self
+    }
+
+    /// Makes text a link to section of page.
+    pub fn with_link(mut self, section: &str) -> Self {
+        self.link = Some(section.to_string());
+        self
+    }
+}
+
+/// Standalone HTML page built section by section.
+pub struct HtmlPage {
+    title: String,
+    filter_placeholder: String,
+    body: String,
+    in_section: bool
+}
+
+impl HtmlPage {
+    pub fn new(title: &str) -> Self {
+        HtmlPage { title: title.to_string(), filter_placeholder: String::new(), body: String::new(), in_section: false }
+    }
+
+    /// Placeholder of filter fields above data tables.
+    pub fn with_filter_placeholder(mut self, placeholder: &str) -> Self {
+        self.filter_placeholder = placeholder.to_string();
+        self
+    }
+
+    /// Starts part of page shown only when its link is followed, the first section is shown by default.
+    /// Everything added later belongs to this section until the next one is started.
+    pub fn section(&mut self, id: &str) -> &mut Self {
+        self.close_section();
+        let _ = writeln!(self.body, "<section id=\"{}\">", escape(id));
+        self.in_section = true;
+        self
+    }
+
+    fn close_section(&mut self) {
+        if self.in_section {
+            self.body.push_str("</section>\n");
+            self.in_section = false;
+        }
+    }
+
+    pub fn heading(&mut self, text: &str) -> &mut Self {
+        let _ = writeln!(self.body, "<h2>{}</h2>", escape(text));
+        self
+    }
+
+    pub fn subheading(&mut self, text: &str) -> &mut Self {
+        let _ = writeln!(self.body, "<h3>{}</h3>", escape(text));
+        self
+    }
+
+    pub fn paragraph(&mut self, text: &str) -> &mut Self {
+        let _ = writeln!(self.body, "<p>{}</p>", escape(text));
+        self
+    }
+
+    /// List of links to sections as `(section, text)`.
+    pub fn links<'a>(&mut self, links: impl IntoIterator<Item = (&'a str, &'a str)>) -> &mut Self {
+        self.body.push_str("<nav><ul>\n");
+        for (section, text) in links {
+            let _ = writeln!(self.body, "<li><a href=\"#{}\">{}</a></li>", escape(section), escape(text));
+        }
+        self.body.push_str("</ul></nav>\n");
+        self
+    }
+
+    /// Adds table, rows can be shorter than headers.
+    pub fn table(&mut self, headers: &[&str], rows: &[Vec<HtmlCell>]) -> &mut Self {
+        self.body.push_str("<table>\n");
+        self.write_table(headers, rows);
+        self
+    }
+
+    /// Adds table that can be sorted by click on header and filtered by text of rows.
+    pub fn data_table(&mut self, headers: &[&str], rows: &[Vec<HtmlCell>]) -> &mut Self {
+        let _ = writeln!(self.body, "<input class=\"filter\" type=\"search\" placeholder=\"{}\">", escape(&self.filter_placeholder));
+        self.body.push_str("<table class=\"data\">\n");
+        self.write_table(headers, rows);
+        self
+    }
+
+    fn write_table(&mut self, headers: &[&str], rows: &[Vec<HtmlCell>]) {
+        self.body.push_str("<thead><tr>");
+        for header in headers {
+            let _ = write!(self.body, "<th>{}</th>", escape(header));
+        }
+        self.body.push_str("</tr></thead>\n<tbody>\n");
+        for row in rows {
+            self.body.push_str("<tr>");
+            for cell in row {
+                self.body.push_str("<td");
+                if let Some(class) = cell.class {
+                    let _ = write!(self.body, " class=\"{class}\"");
+                }
+                if let Some(heat) = cell.heat {
+                    let _ = write!(self.body, " style=\"background: {}\"", heat_color(heat));
+                }
+                match &cell.link {
+                    Some(section) => { let _ = write!(self.body, "><a href=\"#{}\">{}</a></td>", escape(section), escape(&cell.text)); },
+                    None => { let _ = write!(self.body, ">{}</td>", escape(&cell.text)); }
+                }
+            }
+            self.body.push_str("</tr>\n");
+        }
+        self.body.push_str("</tbody>\n</table>\n");
+    }
+
+    pub fn render(&self) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n{PAGE_STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n{}{}<script>{PAGE_SCRIPT}</script>\n</body>\n</html>\n",
+            self.body,
+            if self.in_section { "</section>\n" } else { "" },
+            title = escape(&self.title)
+        )
+    }
+}
@@ -0,0 +1,216 @@
+use uuid::Uuid;
+
+use crate::error::Error;
+
+use super::{
+    html::{percent, HtmlCell, HtmlPage}, locale::Messages, player::bargains_color_name, stats::{PickStats, PlayerStats, TournamentStats, WinLoss},
+    types::{PlayerMatchHistoryHeaders, ResultOutput}, winrate::WinrateSettings, RaceInfo, TournamentStatsModel
+};
+
+const INDEX_SECTION: &str = "index";
+const RACES_SECTION: &str = "races";
+
+fn race_section(race: i64) -> String {
+    format!("race-{race}")
+}
+
+fn player_section(user: Uuid) -> String {
+    format!("player-{user}")
+}
+
+/// Winrate colored by heatmap scale, winrates of fewer than `min_games` games are greyed out instead.
+fn winrate_cell(results: &WinLoss, settings: &WinrateSettings) -> HtmlCell {
+    let cell = HtmlCell::new(percent(results.win_ratio()));
+    match results.win_ratio() {
+        Some(_) if settings.min_games.is_some_and(|min_games| results.games() < min_games) => cell.with_class("muted"),
+        Some(ratio) => cell.with_heat(ratio),
+        None => cell
+    }
+}
+
+/// Count colored by its part of the biggest count of table.
+fn count_cell(count: u32, max: u32) -> HtmlCell {
+    let cell = HtmlCell::new(count);
+    if max == 0 { cell } else { cell.with_heat(count as f64 / max as f64) }
+}
+
+/// Renders stats as single page with index linking sections of races stats, every race and every player.
+/// Names are expected to be localized already.
+pub fn render_stats_page(model: &TournamentStatsModel, stats: &TournamentStats, messages: &Messages, settings: &WinrateSettings) -> Result<String, Error> {
+    let tournament = model.tournament.as_ref().ok_or(Error::Other("No tournament provided for generation".to_string()))?;
+    let mut page = HtmlPage::new(&tournament.name).with_filter_placeholder(messages.html_filter_placeholder);
+
+    page.section(INDEX_SECTION).heading(messages.html_index_title);
+    let race_sections = model.races.iter().map(|race| race_section(race.id)).collect::<Vec<_>>();
+    page.links(std::iter::once((RACES_SECTION, messages.pair_sheet_name))
+        .chain(race_sections.iter().map(String::as_str).zip(model.races.iter().map(|race| race.name.as_str()))));
+    let player_rows = stats.players.iter()
+        .map(|player| {
+            let total = player.total();
+            vec![
+                HtmlCell::new(&player.nickname).with_link(&player_section(player.user)),
+                HtmlCell::new(total.games()),
+                HtmlCell::new(total.wins),
+                HtmlCell::new(total.losses),
+                winrate_cell(&total, settings)
+            ]
+        })
+        .collect::<Vec<_>>();
+    page.subheading(messages.html_players_title)
+        .data_table(&[messages.html_player_column, messages.total_games, messages.wins, messages.losses, messages.winrate], &player_rows);
+
+    build_races_section(model, stats, messages, settings, &mut page)?;
+    for race in &model.races {
+        build_race_section(race, model, stats, messages, settings, &mut page)?;
+    }
+    for player in &stats.players {
+        build_player_section(model, player, messages, settings, &mut page)?;
+    }
+    Ok(page.render())
+}
+
+fn build_races_section(model: &TournamentStatsModel, stats: &TournamentStats, messages: &Messages, settings: &WinrateSettings, page: &mut HtmlPage) -> Result<(), Error> {
+    page.section(RACES_SECTION)
+        .links([(INDEX_SECTION, messages.html_back_to_index)])
+        .heading(messages.pair_sheet_name);
+
+    let max_games = stats.races.iter().map(|race| race.games()).max().unwrap_or(0);
+    let rows = model.races.iter()
+        .map(|race| {
+            let totals = stats.race(race.id).ok_or(Error::Other(format!("No stats for race {}", race.id)))?;
+            Ok(vec![
+                HtmlCell::new(&race.name).with_link(&race_section(race.id)),
+                count_cell(totals.games(), max_games),
+                HtmlCell::new(totals.mirrors),
+                winrate_cell(&totals.results, settings)
+            ])
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    page.data_table(&[messages.html_race_column, messages.total_games, messages.html_mirrors_column, messages.total_winrate], &rows);
+
+    // row race against column race, mirrors have only games
+    let pair_results = |race: i64, opponent_race: i64| stats.race_pair(race, opponent_race).map(|pair| pair.results).unwrap_or_default();
+    let pair_games = |race: i64, opponent_race: i64| if race == opponent_race {
+        stats.race(race).map(|totals| totals.mirrors).unwrap_or(0)
+    } else {
+        pair_results(race, opponent_race).games()
+    };
+    let headers = std::iter::once("VS").chain(model.races.iter().map(|race| race.name.as_str())).collect::<Vec<_>>();
+    let match_up_rows = |cell: &dyn Fn(&RaceInfo, &RaceInfo) -> HtmlCell| model.races.iter()
+        .map(|race| std::iter::once(HtmlCell::new(&race.name))
+            .chain(model.races.iter().map(|opponent_race| cell(race, opponent_race)))
+            .collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let winrates = match_up_rows(&|race, opponent_race| if race.id == opponent_race.id {
+        HtmlCell::new("")
+    } else {
+        winrate_cell(&pair_results(race.id, opponent_race.id), settings)
+    });
+    page.subheading(messages.match_ups_winrates_title).data_table(&headers, &winrates);
+
+    let max_pair_games = model.races.iter()
+        .flat_map(|race| model.races.iter().map(|opponent_race| pair_games(race.id, opponent_race.id)))
+        .max()
+        .unwrap_or(0);
+    let games = match_up_rows(&|race, opponent_race| count_cell(pair_games(race.id, opponent_race.id), max_pair_games));
+    page.subheading(messages.match_ups_games_title).data_table(&headers, &games);
+    Ok(())
+}
+
+fn build_race_section(
+    race: &RaceInfo,
+    model: &TournamentStatsModel,
+    stats: &TournamentStats,
+    messages: &Messages,
+    settings: &WinrateSettings,
+    page: &mut HtmlPage
+) -> Result<(), Error> {
+    page.section(&race_section(race.id))
+        .links([(INDEX_SECTION, messages.html_back_to_index), (RACES_SECTION, messages.pair_sheet_name)])
+        .heading(&race.name);
+    let Some(race_heroes) = stats.race_heroes(race.id) else {
+        page.paragraph(messages.no_games);
+        return Ok(());
+    };
+
+    let opponent_races = model.races.iter().filter(|r| r.id != race.id).collect::<Vec<_>>();
+    let opponent_headers = opponent_races.iter()
+        .map(|opponent_race| messages.winrate_vs_race.replace("{race}", &opponent_race.name))
+        .collect::<Vec<_>>();
+    let headers = [messages.html_hero_column, messages.total_games, messages.pickrate, messages.winrate].into_iter()
+        .chain(opponent_headers.iter().map(String::as_str))
+        .collect::<Vec<_>>();
+    let max_games = race_heroes.heroes.iter().map(|hero| hero.results.games()).max().unwrap_or(0);
+    let rows = race_heroes.heroes.iter()
+        .map(|hero| {
+            let mut row = vec![
+                HtmlCell::new(model.hero_name(hero.hero)?),
+                count_cell(hero.results.games(), max_games),
+                HtmlCell::new(percent(Some(race_heroes.pick_ratio(hero)))),
+                winrate_cell(&hero.results, settings)
+            ];
+            row.extend(opponent_races.iter().map(|opponent_race| winrate_cell(&hero.vs_race(opponent_race.id), settings)));
+            Ok(row)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    page.subheading(messages.heroes_title).data_table(&headers, &rows);
+    Ok(())
+}
+
+fn build_player_section(
+    model: &TournamentStatsModel,
+    player: &PlayerStats,
+    messages: &Messages,
+    settings: &WinrateSettings,
+    page: &mut HtmlPage
+) -> Result<(), Error> {
+    let tournament = model.tournament.as_ref().ok_or(Error::Other("No tournament provided for generation".to_string()))?;
+    let total = player.total();
+    page.section(&player_section(player.user))
+        .links([(INDEX_SECTION, messages.html_back_to_index)])
+        .heading(&player.nickname)
+        .paragraph(&format!("{}: {}, {}: {}", messages.total_games, total.games(), messages.total_winrate, percent(total.win_ratio())));
+
+    let headers = std::iter::once(messages.html_opponent_column)
+        .chain(PlayerMatchHistoryHeaders::new(tournament, messages).headers)
+        .collect::<Vec<_>>();
+    let rows = player.games.iter()
+        .map(|game| {
+            let opponent = model.users.iter()
+                .find(|user| user.id == game.opponent)
+                .ok_or(Error::Other(format!("No user found with id {}", game.opponent)))?;
+            let mut row = vec![
+                HtmlCell::new(&opponent.nickname).with_link(&player_section(opponent.id)),
+                HtmlCell::new(model.race_name(game.player_race)?),
+                HtmlCell::new(model.hero_name(game.player_hero)?),
+                HtmlCell::new(model.race_name(game.opponent_race)?),
+                HtmlCell::new(model.hero_name(game.opponent_hero)?)
+            ];
+            if tournament.with_bargains {
+                row.push(HtmlCell::new(game.bargains_amount.map(|amount| amount.to_string()).unwrap_or_default()));
+            }
+            if tournament.with_bargains_color {
+                row.push(HtmlCell::new(bargains_color_name(&game.bargains_color, messages).unwrap_or_default()));
+            }
+            row.push(match game.result {
+                ResultOutput::Win => HtmlCell::new(messages.victory).with_class("win"),
+                ResultOutput::Loss => HtmlCell::new(messages.defeat).with_class("loss")
+            });
+            Ok(row)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    page.subheading(messages.games_history_title).data_table(&headers, &rows);
+
+    let race_rows = pick_rows(&player.races, |race| model.race_name(race), settings)?;
+    page.subheading(messages.race_picks_title).data_table(&[messages.html_race_column, messages.total_games, messages.winrate], &race_rows);
+    let hero_rows = pick_rows(&player.heroes, |hero| model.hero_name(hero), settings)?;
+    page.subheading(messages.hero_picks_title).data_table(&[messages.html_hero_column, messages.total_games, messages.winrate], &hero_rows);
+    Ok(())
+}
+
+fn pick_rows<'a>(picks: &[PickStats], name: impl Fn(i64) -> Result<&'a String, Error>, settings: &WinrateSettings) -> Result<Vec<Vec<HtmlCell>>, Error> {
+    picks.iter()
+        .map(|pick| Ok(vec![HtmlCell::new(name(pick.id)?), HtmlCell::new(pick.results.games()), winrate_cell(&pick.results, settings)]))
+        .collect()
+}
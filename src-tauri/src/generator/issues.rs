@@ -0,0 +1,32 @@
+use rust_xlsxwriter::workbook::Workbook;
+
+use crate::error::Error;
+
+use super::{locale::Messages, stats::TournamentStats, styles::{Style, STYLES}, validation::IssueSeverity, TournamentStatsModel};
+
+/// Adds sheet listing every validation issue, nothing is added if games are fine.
+pub fn build_issues_sheet(model: &TournamentStatsModel, stats: &TournamentStats, messages: &Messages, workbook: &mut Workbook) -> Result<(), Error> {
+    if stats.issues.is_empty() {
+        return Ok(());
+    }
+    let worksheet = workbook.add_worksheet().set_name(messages.issues_sheet_name)?;
+    worksheet.merge_range(0, 0, 0, 3, messages.issues_title, STYLES.get(&Style::TextBoldCentered)?)?;
+    for (col, header) in messages.issues_headers.iter().enumerate() {
+        worksheet.write_with_format(1, col as u16, *header, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+    }
+    worksheet.set_column_width(0, 30)?;
+    worksheet.set_column_width(1, 38)?;
+    worksheet.set_column_width(2, 14)?;
+    worksheet.set_column_width(3, 60)?;
+
+    for (row, issue) in (2..).zip(stats.issues.iter()) {
+        worksheet.write_with_format(row, 0, issue.players(model), STYLES.get(&Style::ThinBorderTextWrap)?)?;
+        worksheet.write_with_format(row, 1, issue.game_id.to_string(), STYLES.get(&Style::ThinBorderTextWrap)?)?;
+        match issue.severity() {
+            IssueSeverity::Error => worksheet.write_with_format(row, 2, messages.issue_error, STYLES.get(&Style::BackgroundRed)?)?,
+            IssueSeverity::Warning => worksheet.write_with_format(row, 2, messages.issue_warning, STYLES.get(&Style::BackgroundSilver)?)?
+        };
+        worksheet.write_with_format(row, 3, issue.kind.describe(model, messages), STYLES.get(&Style::ThinBorderTextWrap)?)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use std::{fs::File, io::BufWriter, path::Path};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{error::Error, services::tournament::types::TournamentFrontendModel};
+
+use super::{stats::TournamentStats, RaceInfo, TournamentStatsModel};
+
+/// Must be increased on every incompatible change of `StatsReport` or `TournamentStats` layout.
+pub const STATS_REPORT_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReportHero {
+    pub id: i64,
+    pub race: i64,
+    pub name: String
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReportUser {
+    pub id: Uuid,
+    pub nickname: String
+}
+
+/// Machine-readable version of generated stats.
+/// Stats reference races, heroes and users by ids, names are resolved by dictionaries included in report.
+/// Hero pick rate is hero games divided by `total_picks` of its race.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatsReport {
+    pub schema_version: u32,
+    pub generated_at: String,
+    pub tournament: TournamentFrontendModel,
+    pub races: Vec<RaceInfo>,
+    pub heroes: Vec<ReportHero>,
+    pub users: Vec<ReportUser>,
+    pub stats: TournamentStats
+}
+
+impl StatsReport {
+    pub fn new(model: &TournamentStatsModel, stats: TournamentStats) -> Result<Self, Error> {
+        let tournament = model.tournament.as_ref().ok_or(Error::Other("No tournament provided for generation".to_string()))?;
+        Ok(StatsReport {
+            schema_version: STATS_REPORT_SCHEMA_VERSION,
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            tournament: TournamentFrontendModel::try_from(tournament.clone())?,
+            races: model.races.clone(),
+            heroes: model.heroes.iter()
+                .map(|h| ReportHero { id: h.id, race: h.race, name: h.name.clone() })
+                .collect(),
+            users: model.users.iter()
+                .map(|u| ReportUser { id: u.id, nickname: u.nickname.clone() })
+                .collect(),
+            stats
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+}
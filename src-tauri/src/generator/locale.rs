@@ -0,0 +1,384 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+/// Language of generated workbook.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumString, Display)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    Ru,
+    En
+}
+
+impl Locale {
+    pub fn messages(&self) -> &'static Messages {
+        match self {
+            Locale::Ru => &RU,
+            Locale::En => &EN
+        }
+    }
+}
+
+/// Every text of workbook that isn't a name of race, hero or player.
+/// Texts with `{placeholder}` are filled with `str::replace`.
+pub struct Messages {
+    pub wins: &'static str,
+    pub losses: &'static str,
+    pub total_games: &'static str,
+    pub winrate: &'static str,
+    pub total_winrate: &'static str,
+    pub no_games: &'static str,
+
+    pub pair_sheet_name: &'static str,
+    pub match_ups_games_title: &'static str,
+    pub match_ups_winrates_title: &'static str,
+
+    pub bargains_title: &'static str,
+    pub bargains_cells_names: [&'static str; 16],
+    pub bargains_total_stats_names: [&'static str; 8],
+    pub heroes_title: &'static str,
+    pub total_wins: &'static str,
+    pub total_losses: &'static str,
+    pub pickrate: &'static str,
+    /// Has `{race}` placeholder.
+    pub games_vs_race: &'static str,
+    /// Has `{race}` placeholder.
+    pub winrate_vs_race: &'static str,
+
+    pub games_history_title: &'static str,
+    pub player_race: &'static str,
+    pub player_hero: &'static str,
+    pub opponent_race: &'static str,
+    pub opponent_hero: &'static str,
+    pub player_bargains: &'static str,
+    pub bargains_color: &'static str,
+    pub result: &'static str,
+    pub victory: &'static str,
+    pub defeat: &'static str,
+    pub bargains_color_blue: &'static str,
+    pub bargains_color_red: &'static str,
+    pub race_picks_title: &'static str,
+    pub hero_picks_title: &'static str,
+
+    pub issues_sheet_name: &'static str,
+    pub issues_title: &'static str,
+    pub issues_headers: [&'static str; 4],
+    pub issue_error: &'static str,
+    pub issue_warning: &'static str,
+    /// Has `{reason}` placeholder.
+    pub issue_missing_field: &'static str,
+    pub issue_result_not_selected: &'static str,
+    /// Has `{race}` placeholder.
+    pub issue_unknown_race: &'static str,
+    /// Has `{hero}` placeholder.
+    pub issue_unknown_hero: &'static str,
+    /// Has `{hero}` and `{race}` placeholders.
+    pub issue_hero_not_of_race: &'static str,
+    /// Has `{amount}` placeholder.
+    pub issue_unexpected_bargains: &'static str,
+
+    pub rating_sheet_name: &'static str,
+    pub rating_title: &'static str,
+    pub rating_headers: [&'static str; 9],
+    pub rating_history_sheet_name: &'static str,
+    pub rating_history_headers: [&'static str; 7],
+
+    /// Has `{low}`, `{high}` and `{games}` placeholders.
+    pub winrate_interval: &'static str,
+    /// Has `{min}` placeholder.
+    pub winrate_small_sample: &'static str,
+
+    pub race_games_chart_title: &'static str,
+    pub hero_pickrate_chart_title: &'static str,
+    pub cumulative_winrate: &'static str,
+    pub cumulative_winrate_chart_title: &'static str,
+
+    /// Has `{tournament}` placeholder.
+    pub season_tournament_skipped: &'static str,
+
+    pub comparison_races_sheet_name: &'static str,
+    pub comparison_match_ups_sheet_name: &'static str,
+    pub comparison_heroes_sheet_name: &'static str,
+    pub comparison_bargains_sheet_name: &'static str,
+    /// Has `{previous}` and `{current}` placeholders.
+    pub comparison_title: &'static str,
+    pub comparison_significance_note: &'static str,
+    /// Has `{previous}`, `{previous_games}`, `{current}` and `{current_games}` placeholders.
+    pub comparison_shift_note: &'static str,
+    pub comparison_race_headers: [&'static str; 7],
+    pub comparison_entered_heroes_title: &'static str,
+    pub comparison_dropped_heroes_title: &'static str,
+    pub comparison_hero_headers: [&'static str; 4],
+    pub comparison_bargains_headers: [&'static str; 4],
+
+    pub head_to_head_sheet_name: &'static str,
+    pub head_to_head_score: &'static str,
+    pub head_to_head_no_games: &'static str,
+    pub head_to_head_races_title: &'static str,
+    pub head_to_head_heroes_title: &'static str,
+    pub head_to_head_opponent_races_title: &'static str,
+    pub head_to_head_bargains_title: &'static str,
+    pub head_to_head_bargains_columns: [&'static str; 2],
+    pub head_to_head_bargains_names: [&'static str; 4],
+
+    pub html_index_title: &'static str,
+    pub html_back_to_index: &'static str,
+    pub html_players_title: &'static str,
+    pub html_filter_placeholder: &'static str,
+    pub html_player_column: &'static str,
+    pub html_opponent_column: &'static str,
+    pub html_race_column: &'static str,
+    pub html_hero_column: &'static str,
+    pub html_mirrors_column: &'static str
+}
+
+static RU: Messages = Messages {
+    wins: "Побед",
+    losses: "Поражений",
+    total_games: "Всего игр",
+    winrate: "Винрейт",
+    total_winrate: "Общий винрейт",
+    no_games: "Нет игр",
+
+    pair_sheet_name: "Общая статистика по расам",
+    match_ups_games_title: "Число игр по матчапам",
+    match_ups_winrates_title: "Винрейты матчапов",
+
+    bargains_title: "Данные о торгах за фракцию",
+    bargains_cells_names: [
+        "Игр с плюсом по золоту",
+        "Игр с минусом по золоту",
+        "Игр без торга",
+        "Побед с плюсом",
+        "Поражений с плюсом",
+        "Винрейт с плюсом",
+        "Побед с минусом",
+        "Поражений с минусом",
+        "Винрейт с минусом",
+        "Побед без торга",
+        "Поражений без торга",
+        "Винрейт без торга",
+        "Максимальный плюс по золоту",
+        "Максимальный минус по золоту",
+        "Средний плюсовый торг",
+        "Средний минусовый торг"
+    ],
+    bargains_total_stats_names: [
+        "Общий средний торг",
+        "Суммарно игр с плюсовым торгом",
+        "Суммарно игр с минусовым торгом",
+        "Суммарно игр без торга",
+        "Общий винрейт с плюсовым торгом",
+        "Общий винрейт с минусовым торгом",
+        "Общий винрейт без торга",
+        "Общий винрейт фракции"
+    ],
+    heroes_title: "Общая статистика использования героев",
+    total_wins: "Всего побед",
+    total_losses: "Всего поражений",
+    pickrate: "Процент выбора",
+    games_vs_race: "Игр vs {race}",
+    winrate_vs_race: "Винрейт vs {race}",
+
+    games_history_title: "История игр",
+    player_race: "Фракция игрока",
+    player_hero: "Герой игрока",
+    opponent_race: "Фракция оппонента",
+    opponent_hero: "Герой оппонента",
+    player_bargains: "Торг игрока",
+    bargains_color: "Цвет торга",
+    result: "Результат",
+    victory: "Победа",
+    defeat: "Поражение",
+    bargains_color_blue: "Синий",
+    bargains_color_red: "Красный",
+    race_picks_title: "Выбор рас",
+    hero_picks_title: "Выбор героев",
+
+    issues_sheet_name: "Ошибки в играх",
+    issues_title: "Игры с ошибками не учитываются в статистике",
+    issues_headers: ["Матч", "Игра", "Уровень", "Проблема"],
+    issue_error: "Ошибка",
+    issue_warning: "Предупреждение",
+    issue_missing_field: "Игра не заполнена: {reason}",
+    issue_result_not_selected: "Не выбран результат игры",
+    issue_unknown_race: "Неизвестная фракция {race}",
+    issue_unknown_hero: "Неизвестный герой {hero}",
+    issue_hero_not_of_race: "Герой {hero} не принадлежит фракции {race}",
+    issue_unexpected_bargains: "Торг {amount} в турнире без торгов",
+
+    rating_sheet_name: "Рейтинг",
+    rating_title: "Рейтинг игроков по Эло и Glicko-2",
+    rating_headers: ["Посев", "Игрок", "Игр", "Побед", "Поражений", "Эло", "Glicko-2", "Отклонение (RD)", "Волатильность"],
+    rating_history_sheet_name: "История рейтинга",
+    rating_history_headers: ["№", "Оппонент", "Побед", "Поражений", "Эло", "Glicko-2", "Отклонение (RD)"],
+
+    winrate_interval: "95% доверительный интервал: {low}% – {high}%, игр: {games}",
+    winrate_small_sample: "Меньше {min} игр, винрейт ненадёжен",
+
+    race_games_chart_title: "Игры и винрейт рас",
+    hero_pickrate_chart_title: "Процент выбора героев",
+    cumulative_winrate: "Винрейт после игры",
+    cumulative_winrate_chart_title: "Винрейт по ходу турнира",
+
+    season_tournament_skipped: "Турнир {tournament} пропущен: другой мод или тип игр",
+
+    comparison_races_sheet_name: "Изменения рас",
+    comparison_match_ups_sheet_name: "Изменения матчапов",
+    comparison_heroes_sheet_name: "Мета героев",
+    comparison_bargains_sheet_name: "Изменения торгов",
+    comparison_title: "{previous} → {current}",
+    comparison_significance_note: "Цветом выделены статистически значимые изменения (95%)",
+    comparison_shift_note: "Было {previous}% за {previous_games} игр, стало {current}% за {current_games}",
+    comparison_race_headers: ["Раса", "Пикрейт до", "Пикрейт после", "Изменение пикрейта", "Винрейт до", "Винрейт после", "Изменение винрейта"],
+    comparison_entered_heroes_title: "Вошли в мету",
+    comparison_dropped_heroes_title: "Выпали из меты",
+    comparison_hero_headers: ["Раса", "Герой", "Пикрейт до", "Пикрейт после"],
+    comparison_bargains_headers: ["Раса", "Средний торг до", "Средний торг после", "Изменение"],
+
+    head_to_head_sheet_name: "Личные встречи",
+    head_to_head_score: "Счёт по играм",
+    head_to_head_no_games: "Игроки ещё не встречались",
+    head_to_head_races_title: "Расы против соперника",
+    head_to_head_heroes_title: "Герои против соперника",
+    head_to_head_opponent_races_title: "Винрейт в основных расах соперника",
+    head_to_head_bargains_title: "Торги",
+    head_to_head_bargains_columns: ["Все игры", "Против соперника"],
+    head_to_head_bargains_names: ["Игр с торгом", "Средний торг", "Игр с плюсом", "Игр с минусом"],
+
+    html_index_title: "Содержание",
+    html_back_to_index: "К содержанию",
+    html_players_title: "Игроки",
+    html_filter_placeholder: "Поиск",
+    html_player_column: "Игрок",
+    html_opponent_column: "Оппонент",
+    html_race_column: "Раса",
+    html_hero_column: "Герой",
+    html_mirrors_column: "Зеркал"
+};
+
+static EN: Messages = Messages {
+    wins: "Wins",
+    losses: "Losses",
+    total_games: "Total games",
+    winrate: "Winrate",
+    total_winrate: "Total winrate",
+    no_games: "No games",
+
+    pair_sheet_name: "Race stats",
+    match_ups_games_title: "Games by match-up",
+    match_ups_winrates_title: "Match-up winrates",
+
+    bargains_title: "Bargains of race",
+    bargains_cells_names: [
+        "Games with plus gold",
+        "Games with minus gold",
+        "Games without bargains",
+        "Wins with plus",
+        "Losses with plus",
+        "Winrate with plus",
+        "Wins with minus",
+        "Losses with minus",
+        "Winrate with minus",
+        "Wins without bargains",
+        "Losses without bargains",
+        "Winrate without bargains",
+        "Max plus gold",
+        "Max minus gold",
+        "Average plus bargain",
+        "Average minus bargain"
+    ],
+    bargains_total_stats_names: [
+        "Total average bargain",
+        "Total games with plus bargain",
+        "Total games with minus bargain",
+        "Total games without bargains",
+        "Total winrate with plus bargain",
+        "Total winrate with minus bargain",
+        "Total winrate without bargains",
+        "Total race winrate"
+    ],
+    heroes_title: "Heroes usage",
+    total_wins: "Total wins",
+    total_losses: "Total losses",
+    pickrate: "Pick rate",
+    games_vs_race: "Games vs {race}",
+    winrate_vs_race: "Winrate vs {race}",
+
+    games_history_title: "Games history",
+    player_race: "Player race",
+    player_hero: "Player hero",
+    opponent_race: "Opponent race",
+    opponent_hero: "Opponent hero",
+    player_bargains: "Player bargain",
+    bargains_color: "Bargain color",
+    result: "Result",
+    victory: "Victory",
+    defeat: "Defeat",
+    bargains_color_blue: "Blue",
+    bargains_color_red: "Red",
+    race_picks_title: "Race picks",
+    hero_picks_title: "Hero picks",
+
+    issues_sheet_name: "Game issues",
+    issues_title: "Games with errors aren't counted in stats",
+    issues_headers: ["Match", "Game", "Severity", "Problem"],
+    issue_error: "Error",
+    issue_warning: "Warning",
+    issue_missing_field: "Game isn't filled: {reason}",
+    issue_result_not_selected: "Game result isn't selected",
+    issue_unknown_race: "Unknown race {race}",
+    issue_unknown_hero: "Unknown hero {hero}",
+    issue_hero_not_of_race: "Hero {hero} doesn't belong to race {race}",
+    issue_unexpected_bargains: "Bargain {amount} in tournament without bargains",
+
+    rating_sheet_name: "Ratings",
+    rating_title: "Elo and Glicko-2 ratings of players",
+    rating_headers: ["Seed", "Player", "Games", "Wins", "Losses", "Elo", "Glicko-2", "Deviation (RD)", "Volatility"],
+    rating_history_sheet_name: "Rating history",
+    rating_history_headers: ["#", "Opponent", "Wins", "Losses", "Elo", "Glicko-2", "Deviation (RD)"],
+
+    winrate_interval: "95% confidence interval: {low}% – {high}%, games: {games}",
+    winrate_small_sample: "Fewer than {min} games, winrate is unreliable",
+
+    race_games_chart_title: "Race games and winrates",
+    hero_pickrate_chart_title: "Hero pick rates",
+    cumulative_winrate: "Winrate after game",
+    cumulative_winrate_chart_title: "Winrate over tournament",
+
+    season_tournament_skipped: "Tournament {tournament} is skipped: different mod or game type",
+
+    comparison_races_sheet_name: "Race changes",
+    comparison_match_ups_sheet_name: "Match-up changes",
+    comparison_heroes_sheet_name: "Hero meta",
+    comparison_bargains_sheet_name: "Bargain changes",
+    comparison_title: "{previous} → {current}",
+    comparison_significance_note: "Statistically significant changes (95%) are colored",
+    comparison_shift_note: "Was {previous}% in {previous_games} games, now {current}% in {current_games}",
+    comparison_race_headers: ["Race", "Pick rate before", "Pick rate after", "Pick rate change", "Winrate before", "Winrate after", "Winrate change"],
+    comparison_entered_heroes_title: "Entered the meta",
+    comparison_dropped_heroes_title: "Dropped out of the meta",
+    comparison_hero_headers: ["Race", "Hero", "Pick rate before", "Pick rate after"],
+    comparison_bargains_headers: ["Race", "Average bargain before", "Average bargain after", "Change"],
+
+    head_to_head_sheet_name: "Head to head",
+    head_to_head_score: "Games score",
+    head_to_head_no_games: "Players haven't met yet",
+    head_to_head_races_title: "Races against opponent",
+    head_to_head_heroes_title: "Heroes against opponent",
+    head_to_head_opponent_races_title: "Winrate in opponent's main races",
+    head_to_head_bargains_title: "Bargains",
+    head_to_head_bargains_columns: ["All games", "Against opponent"],
+    head_to_head_bargains_names: ["Games with bargain", "Average bargain", "Plus gold games", "Minus gold games"],
+
+    html_index_title: "Contents",
+    html_back_to_index: "Back to contents",
+    html_players_title: "Players",
+    html_filter_placeholder: "Search",
+    html_player_column: "Player",
+    html_opponent_column: "Opponent",
+    html_race_column: "Race",
+    html_hero_column: "Hero",
+    html_mirrors_column: "Mirrors"
+};
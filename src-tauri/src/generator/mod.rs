@@ -1,71 +1,83 @@
 use serde::{Deserialize, Serialize};
-use types::GameEntry;
+use types::{GameEntry, RejectedGame};
+use uuid::Uuid;
 
-use crate::graphql::queries::{get_heroes::GetHeroesHeroesNewHeroesEntities, get_matches::GetMatchesMatches, get_tournament::GetTournamentTournament, get_users::GetUsersUsers};
+use crate::{error::Error, graphql::queries::{get_heroes::GetHeroesHeroesNewHeroesEntities, get_matches::GetMatchesMatches, get_tournament::GetTournamentTournament, get_users::GetUsersUsers}};
 
+pub mod catalog;
+pub mod charts;
 pub mod commands;
+pub mod comparison;
+pub mod comparison_sheets;
+pub mod games_export;
+pub mod head_to_head;
+pub mod head_to_head_report;
+pub mod heatmap;
+pub mod html;
+pub mod html_report;
+pub mod issues;
+pub mod json;
+pub mod locale;
+pub mod output;
 pub mod pair;
+pub mod pipeline;
 pub mod race;
+pub mod rating;
+pub mod rating_sheets;
+pub mod season;
+pub mod snapshot;
 pub mod player;
+pub mod progress;
+pub mod stats;
 pub mod styles;
 pub mod types;
+pub mod validation;
+pub mod winrate;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RaceInfo {
     pub id: i64,
     pub name: String
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TournamentStatsModel {
     pub tournament: Option<GetTournamentTournament>,
     pub users: Vec<GetUsersUsers>,
     pub matches: Vec<GetMatchesMatches>,
     pub games: Vec<GameEntry>,
+    /// Games that couldn't be converted into `GameEntry`, they aren't counted anywhere.
+    #[serde(default)]
+    pub rejected_games: Vec<RejectedGame>,
+    /// Races of tournament mod from `catalog`, tables are laid out in this order.
     pub races: Vec<RaceInfo>,
     pub heroes: Vec<GetHeroesHeroesNewHeroesEntities>
 }
 
-impl Default for TournamentStatsModel {
-    fn default() -> Self {
-        TournamentStatsModel { 
-            tournament: None, 
-            users: vec![], 
-            matches: vec![], 
-            games: vec![], 
-            races: vec![
-                RaceInfo {
-                    id: 1,
-                    name: "Орден порядка".to_string()
-                },
-                RaceInfo {
-                    id: 2,
-                    name: "Инферно".to_string()
-                },
-                RaceInfo {
-                    id: 3,
-                    name: "Некрополис".to_string()
-                },
-                RaceInfo {
-                    id: 4,
-                    name: "Лесной союз".to_string()
-                },
-                RaceInfo {
-                    id: 5,
-                    name: "Лига теней".to_string()
-                },
-                RaceInfo {
-                    id: 6,
-                    name: "Академия волшебства".to_string()
-                },
-                RaceInfo {
-                    id: 7,
-                    name: "Северные кланы".to_string()
-                },
-                RaceInfo {
-                    id: 8,
-                    name: "Великая орда".to_string()
-                }
-            ], heroes: vec![] }
+impl TournamentStatsModel {
+    /// Games stats are computed from, games with `Error` validation issues are skipped.
+    pub fn counted_games(&self) -> impl Iterator<Item = &GameEntry> {
+        self.games.iter().filter(|game| validation::is_counted(self, game))
     }
-}
\ No newline at end of file
+
+    pub fn race_name(&self, race: i64) -> Result<&String, Error> {
+        Ok(&self.races.iter()
+            .find(|r| r.id == race)
+            .ok_or(Error::Other("No matching race found".to_string()))?
+            .name)
+    }
+
+    pub fn hero_name(&self, hero: i64) -> Result<&String, Error> {
+        Ok(&self.heroes.iter()
+            .find(|h| h.id == hero)
+            .ok_or(Error::Other("No matching hero found".to_string()))?
+            .name)
+    }
+
+    pub fn user_name(&self, user: Uuid) -> Result<&String, Error> {
+        Ok(&self.users.iter()
+            .find(|u| u.id == user)
+            .ok_or(Error::Other(format!("No user found with id {}", user)))?
+            .nickname)
+    }
+}
@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+pub const DEFAULT_FILE_NAME_TEMPLATE: &str = "{tournament}_{date}";
+
+/// Where generated report must be saved.
+/// Either `path` is used as is or file name is built from `file_name_template` inside of `directory`.
+/// Template supports `{tournament}` and `{date}` placeholders.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct OutputSettings {
+    pub path: Option<PathBuf>,
+    pub directory: Option<PathBuf>,
+    pub file_name_template: Option<String>,
+    #[serde(default)]
+    pub overwrite: bool
+}
+
+impl OutputSettings {
+    pub fn with_path(path: impl Into<PathBuf>) -> Self {
+        OutputSettings {
+            path: Some(path.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_directory(directory: impl Into<PathBuf>, file_name_template: Option<String>) -> Self {
+        OutputSettings {
+            directory: Some(directory.into()),
+            file_name_template,
+            ..Default::default()
+        }
+    }
+
+    pub fn overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// Builds final file path for tournament and checks it can be written.
+    /// `extension` is appended to templated names that don't end with it already.
+    pub fn resolve(&self, tournament_name: &str, extension: &str) -> Result<PathBuf, Error> {
+        let path = match (&self.path, &self.directory) {
+            (Some(path), _) => path.clone(),
+            (None, Some(directory)) => {
+                let template = self.file_name_template.as_deref().unwrap_or(DEFAULT_FILE_NAME_TEMPLATE);
+                let mut file_name = render_file_name(template, tournament_name);
+                if !file_name.ends_with(&format!(".{extension}")) {
+                    file_name = format!("{file_name}.{extension}");
+                }
+                directory.join(file_name)
+            },
+            (None, None) => return Err(Error::Other("No output path or directory provided for generation".to_string()))
+        };
+
+        if path.exists() && !self.overwrite {
+            return Err(Error::OutputExists(path));
+        }
+        Ok(path)
+    }
+}
+
+fn render_file_name(template: &str, tournament_name: &str) -> String {
+    let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+    sanitize_file_name(&template
+        .replace("{tournament}", tournament_name)
+        .replace("{date}", &date))
+}
+
+// tournament names come from discord and may contain anything
+fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
@@ -1,301 +1,286 @@
-use std::collections::HashMap;
-
-use ordered_float::OrderedFloat;
 use rust_xlsxwriter::{workbook::Workbook, worksheet::Worksheet, Format};
 
-use crate::services::tournament::types::GameResult;
-
-use super::{styles::{Style, STYLES}, types::GameEntry, RaceInfo, TournamentStatsModel};
+use super::{
+    charts::{race_games_chart, ColumnRange}, heatmap::{CellRange, HeatmapSettings, HEATMAP_GREEN}, locale::Messages, stats::{TournamentStats, WinLoss},
+    styles::{Style, STYLES}, winrate::WinrateWriter, RaceInfo, TournamentStatsModel
+};
 
-pub struct PairStatsBuilder {
-    wins_by_race: HashMap<i64, HashMap<i64, usize>>,
-    losses_by_race: HashMap<i64, HashMap<i64, usize>>,
-    mirrors_by_race: HashMap<i64, usize>
+/// Positions of sheet tables, races are placed by their index in catalog.
+/// Tournaments of season breakdown are placed in order they were merged.
+struct PairSheetLayout {
+    races_count: u32,
+    tournaments_count: u32
 }
 
-impl PairStatsBuilder {
-    pub fn new() -> Self {
-
-        let races_range = std::ops::Range {start: 0, end: 9};
-
-        PairStatsBuilder { 
-            wins_by_race: HashMap::from_iter(
-                races_range.clone().map(|r| {
-                    (r, HashMap::from_iter(races_range.clone().map(|r2| {
-                            (r2, 0)
-                    })))
-                })), 
-
-            losses_by_race: HashMap::from_iter(
-                races_range.clone().map(|r| {
-                    (r, HashMap::from_iter(races_range.clone().map(|r2| {
-                            (r2, 0)
-                    })))
-                })),
-
-            mirrors_by_race: HashMap::from_iter(
-                races_range.clone().map(|r| {
-                    (r, 0)
-                })
-            )
-        }
+impl PairSheetLayout {
+    fn new(races_data: &[RaceInfo], stats: &TournamentStats) -> Self {
+        PairSheetLayout { races_count: races_data.len() as u32, tournaments_count: stats.breakdown.len() as u32 }
+    }
+
+    /// Row of race in wins/losses table, two header rows are above.
+    fn win_loss_row(&self, index: usize) -> u32 {
+        2 + index as u32
+    }
+
+    /// First of two columns of race in wins/losses table.
+    fn win_loss_column(&self, index: usize) -> u16 {
+        1 + 2 * index as u16
+    }
+
+    fn total_games_column(&self) -> u16 {
+        1 + 2 * self.races_count as u16
+    }
+
+    /// Games of tournament go right after total games.
+    fn breakdown_games_column(&self, index: usize) -> u16 {
+        self.total_games_column() + 1 + index as u16
+    }
+
+    /// Winrates of tournament go right after total winrates.
+    fn breakdown_winrate_column(&self, index: usize) -> u16 {
+        2 + index as u16
     }
 
-    pub fn build(&mut self, model: &TournamentStatsModel, workbook: &mut Workbook) -> Result<(), crate::error::Error> {
-        let worksheet = workbook.add_worksheet().set_name("Общая статистика по расам")?;
-        self.build_pairs_win_loss_stats(&model.races, &model.games, worksheet)?;
-        self.build_total_games_and_winrates(&model.races, worksheet)?;
-        self.build_match_ups_games_and_winrates(&model.races, worksheet)?;
-        Ok(())
+    fn winrates_header_row(&self) -> u32 {
+        self.win_loss_row(self.races_count as usize) + 1
     }
 
-    fn build_pairs_win_loss_stats(&mut self, races_data: &Vec<RaceInfo>, games_data: &Vec<GameEntry>, worksheet: &mut Worksheet) -> Result<(), crate::error::Error> {
-        let width = races_data.iter()
-            .map(|r| r.name.clone())
-            .collect::<Vec<String>>().iter()
-            .max_by_key(|x| x.len()).ok_or(crate::error::Error::Other("Max by key error".to_string()))?
-            .chars().count();
-
-        worksheet.merge_range(0, 0, 1, 0, "VS", STYLES.get(&Style::BackgroundRed)?)?;
-        worksheet.set_column_width(0, (width + 1) as f64)?;
-
-        for race in races_data {
-            match race.id {
-                0 => {},
-                _=> {
-                    worksheet.write_with_format(1 + (race.id as u32), 0, &race.name, STYLES.get(&Style::ThinBorderTextWrap)?)?;
-                    let col_offset = (race.id as u16) * 2 - 1;
-                    worksheet.merge_range(
-                        0, 
-                        col_offset, 
-                        0, 
-                        col_offset + 1,
-                        &race.name, 
-                        STYLES.get(&Style::ThinBorderTextCenter)?
-                    )?;
-                    worksheet.set_column_width(col_offset, (width as f64) / 1.5)?;
-                    worksheet.set_column_width(col_offset + 1, (width as f64) / 1.5)?;
-                    worksheet.write_with_format(1, col_offset, "Побед", STYLES.get(&Style::ThinBorderTextCenter)?)?;
-                    worksheet.write_with_format(1, col_offset + 1, "Поражений", STYLES.get(&Style::ThinBorderTextCenter)?)?;
-
-                    for opponent_race in races_data {
-                        match opponent_race.id {
-                            0 => {},
-                            _=> {
-                                let row_offset = (opponent_race.id as u32) + 1;
-                                if race.id != opponent_race.id {
-                                    // check for games where either 1-2 pair is current race-opponent(and 1st won) or 1-2 is opponent-race(and 2nd won)
-                                    let wins = games_data.iter().filter(|game| {
-                                            (game.first_player_race == opponent_race.id && game.second_player_race == race.id && game.result == GameResult::FirstPlayerWon) ||
-                                            (game.first_player_race == race.id && game.second_player_race == opponent_race.id && game.result == GameResult::SecondPlayerWon)
-                                        })
-                                        .collect::<Vec<&GameEntry>>()
-                                        .len();
-                                    
-                                    if let Some(opponent_race_data) = self.wins_by_race.get_mut(&opponent_race.id) {
-                                        if let Some(current_wins) = opponent_race_data.get_mut(&race.id) {
-                                            *current_wins = wins;
-                                        }
-                                    }
-
-                                    let losses = games_data.iter().filter(|game| {
-                                            (game.first_player_race == opponent_race.id && game.second_player_race == race.id && game.result == GameResult::SecondPlayerWon) ||
-                                            (game.first_player_race == race.id && game.second_player_race == opponent_race.id && game.result == GameResult::FirstPlayerWon)
-                                        })
-                                        .collect::<Vec<&GameEntry>>()
-                                        .len();
-
-                                    if let Some(opponent_race_data) = self.losses_by_race.get_mut(&opponent_race.id) {
-                                        if let Some(current_losses) = opponent_race_data.get_mut(&race.id) {
-                                            *current_losses = losses;
-                                        }
-                                    }
-
-                                    worksheet.write_with_format(row_offset, col_offset, wins as u32, STYLES.get(&Style::ThinBorderTextWrap)?)?;
-                                    worksheet.write_with_format(row_offset, col_offset + 1, losses as u32, STYLES.get(&Style::ThinBorderTextWrap)?)?;
-                                }
-                                else {
-                                    // mirrors
-                                    let games_count = games_data.iter()
-                                        .filter(|game| {
-                                            game.first_player_race == race.id && game.first_player_race == game.second_player_race
-                                        })
-                                        .collect::<Vec<&GameEntry>>()
-                                        .len();
-                                    
-                                    if let Some(mirrors_count) = self.mirrors_by_race.get_mut(&race.id) {
-                                        *mirrors_count = games_count;
-                                    }
-                                    
-                                    worksheet.merge_range(
-                                        row_offset, 
-                                        col_offset, 
-                                        row_offset, 
-                                        col_offset + 1, 
-                                        &format!("{}", games_count), STYLES.get(&Style::ThinBorderTextWrap)?)?;
-                                }
-                            }
-                        }
-                    }
-                }
+    fn match_up_games_title_row(&self) -> u32 {
+        self.winrates_header_row() + self.races_count + 2
+    }
+
+    fn match_up_winrates_title_row(&self) -> u32 {
+        self.match_up_games_title_row() + self.races_count + 4
+    }
+
+    /// Chart is placed right of wins/losses table and breakdown leaving one empty column.
+    fn chart_column(&self) -> u16 {
+        self.total_games_column() + 2 + self.tournaments_count as u16
+    }
+
+    /// Column of race in match-ups tables, first column has race names.
+    fn match_up_column(&self, index: usize) -> u16 {
+        1 + index as u16
+    }
+}
+
+pub fn build_pair_stats(
+    model: &TournamentStatsModel,
+    stats: &TournamentStats,
+    messages: &Messages,
+    winrates: &WinrateWriter,
+    heatmaps: &HeatmapSettings,
+    workbook: &mut Workbook
+) -> Result<(), crate::error::Error> {
+    // tables are sized by races, old snapshots or mods missing in catalog can have none
+    if model.races.is_empty() {
+        return Err(crate::error::Error::Other("No races provided for generation".to_string()));
+    }
+    let worksheet = workbook.add_worksheet().set_name(messages.pair_sheet_name)?;
+    let layout = PairSheetLayout::new(&model.races, stats);
+    build_pairs_win_loss_stats(&model.races, &layout, stats, messages, worksheet)?;
+    build_total_games_and_winrates(&model.races, &layout, stats, messages, winrates, heatmaps, worksheet)?;
+    build_match_ups_games_and_winrates(&model.races, &layout, stats, messages, winrates, heatmaps, worksheet)?;
+
+    let sheet = messages.pair_sheet_name;
+    let races_count = layout.races_count;
+    let chart = race_games_chart(
+        ColumnRange::new(sheet, 0, layout.win_loss_row(0), races_count),
+        ColumnRange::new(sheet, layout.total_games_column(), layout.win_loss_row(0), races_count),
+        ColumnRange::new(sheet, 1, layout.winrates_header_row() + 1, races_count),
+        messages
+    );
+    worksheet.insert_chart(0, layout.chart_column(), &chart)?;
+    Ok(())
+}
+
+fn build_pairs_win_loss_stats(races_data: &[RaceInfo], layout: &PairSheetLayout, stats: &TournamentStats, messages: &Messages, worksheet: &mut Worksheet) -> Result<(), crate::error::Error> {
+    let width = races_data.iter()
+        .map(|r| r.name.clone())
+        .collect::<Vec<String>>().iter()
+        .max_by_key(|x| x.len()).ok_or(crate::error::Error::Other("Max by key error".to_string()))?
+        .chars().count();
+
+    worksheet.merge_range(0, 0, 1, 0, "VS", STYLES.get(&Style::BackgroundRed)?)?;
+    worksheet.set_column_width(0, (width + 1) as f64)?;
+
+    for (index, race) in races_data.iter().enumerate() {
+        worksheet.write_with_format(layout.win_loss_row(index), 0, &race.name, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+        let col_offset = layout.win_loss_column(index);
+        worksheet.merge_range(
+            0,
+            col_offset,
+            0,
+            col_offset + 1,
+            &race.name,
+            STYLES.get(&Style::ThinBorderTextCenter)?
+        )?;
+        worksheet.set_column_width(col_offset, (width as f64) / 1.5)?;
+        worksheet.set_column_width(col_offset + 1, (width as f64) / 1.5)?;
+        worksheet.write_with_format(1, col_offset, messages.wins, STYLES.get(&Style::ThinBorderTextCenter)?)?;
+        worksheet.write_with_format(1, col_offset + 1, messages.losses, STYLES.get(&Style::ThinBorderTextCenter)?)?;
+
+        for (opponent_index, opponent_race) in races_data.iter().enumerate() {
+            let row_offset = layout.win_loss_row(opponent_index);
+            if race.id != opponent_race.id {
+                // row race results against column race
+                let results = pair_results(stats, opponent_race.id, race.id);
+                worksheet.write_with_format(row_offset, col_offset, results.wins, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+                worksheet.write_with_format(row_offset, col_offset + 1, results.losses, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+            }
+            else {
+                // mirrors
+                let games_count = stats.race(race.id).map(|r| r.mirrors).unwrap_or(0);
+                worksheet.merge_range(
+                    row_offset,
+                    col_offset,
+                    row_offset,
+                    col_offset + 1,
+                    &format!("{}", games_count), STYLES.get(&Style::ThinBorderTextWrap)?)?;
             }
         }
-        Ok(())
+    }
+    Ok(())
+}
+
+fn build_total_games_and_winrates(
+    races_data: &[RaceInfo],
+    layout: &PairSheetLayout,
+    stats: &TournamentStats,
+    messages: &Messages,
+    winrates: &WinrateWriter,
+    heatmaps: &HeatmapSettings,
+    worksheet: &mut Worksheet
+) -> Result<(), crate::error::Error> {
+    let total_games_column = layout.total_games_column();
+    let winrates_header_row = layout.winrates_header_row();
+    worksheet
+        .write_with_format(0, total_games_column, messages.total_games, STYLES.get(&Style::ThinBorderTextCenter)?)?
+        .set_cell_format(1, total_games_column, STYLES.get(&Style::BackgroundSilver)?)?;
+
+    worksheet.merge_range(winrates_header_row, 0, winrates_header_row, 1, messages.total_winrate, STYLES.get(&Style::ThinBorderTextCenter)?)?;
+
+    for (index, race) in races_data.iter().enumerate() {
+        let race_totals = stats.race(race.id)
+            .ok_or(crate::error::Error::Other(format!("No stats for race {}", race.id)))?;
+        worksheet.write_with_format(layout.win_loss_row(index), total_games_column, race_totals.games(), STYLES.get(&Style::ThinBorderTextWrap)?)?;
+        let row_offset = winrates_header_row + 1 + index as u32;
+        worksheet.write_with_format(row_offset, 0, &race.name, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+        winrates.write(worksheet, row_offset, 1, &race_totals.results, None, STYLES.get(&Style::ThinBorderPercent)?)?;
     }
 
-    fn build_total_games_and_winrates(&mut self, races_data: &Vec<RaceInfo>, worksheet: &mut Worksheet) -> Result<(), crate::error::Error> {
+    for (tournament_index, breakdown) in stats.breakdown.iter().enumerate() {
+        let games_column = layout.breakdown_games_column(tournament_index);
+        let winrate_column = layout.breakdown_winrate_column(tournament_index);
         worksheet
-            .write_with_format(0, 17, "Всего игр", STYLES.get(&Style::ThinBorderTextCenter)?)?
-            .set_cell_format(1, 17, STYLES.get(&Style::BackgroundSilver)?)?;
-
-        worksheet.merge_range(11, 0, 11, 1, "Общий винрейт", STYLES.get(&Style::ThinBorderTextCenter)?)?;
-
-        let races_total_games = races_data.iter()
-            .map(|r| {
-                (r.id, 
-                    calc_games(self.losses_by_race.get(&r.id).unwrap()) + 
-                    calc_games(self.wins_by_race.get(&r.id).unwrap()) + 
-                    self.mirrors_by_race.get(&r.id).unwrap()
-                )
-            })
-            .collect::<HashMap<i64, usize>>();
-
-        let races_total_games_no_mirrors = races_data.iter()
-            .map(|r| {
-                (r.id, 
-                    calc_games(self.losses_by_race.get(&r.id).unwrap()) + 
-                    calc_games(self.wins_by_race.get(&r.id).unwrap())
-                )
-            })
-            .collect::<HashMap<i64, usize>>();
-
-        let least_played_race = races_total_games.iter()
-            .min_by_key(|r| r.1)
-            .unwrap()
-            .0;
-
-        let most_played_race = races_total_games.iter()
-            .max_by_key(|r| r.1)
-            .unwrap()
-            .0;
-
-        let races_winrates = races_data.iter()
-            .map(|r| {
-                (r.id, (calc_games(self.wins_by_race.get(&r.id).unwrap()) as f32) / (*races_total_games_no_mirrors.get(&r.id).unwrap() as f32) * 100.0)
-            })
-            .collect::<HashMap<i64, f32>>();
-
-        let race_with_least_winrate = races_winrates.iter()
-            .min_by_key(|r| OrderedFloat(*r.1))
-            .unwrap()
-            .0;
-
-        let race_with_most_winrate = races_winrates.iter()
-            .max_by_key(|r| OrderedFloat(*r.1))
-            .unwrap()
-            .0;
-
-        for race in races_data {
-            match race.id {
-                0 => {},
-                _=> {
-                    let row_offset = 1 + (race.id as u32);
-                    worksheet.write_with_format(row_offset, 17, *races_total_games.get(&race.id).unwrap() as u32, STYLES.get(&Style::ThinBorderTextWrap)?)?;
-                    let row_offset = 11 + (race.id as u32);
-                    worksheet.write_with_format(row_offset, 0, &race.name, STYLES.get(&Style::ThinBorderTextWrap)?)?;
-                    worksheet.write_with_format(row_offset, 1, &format!("{:.3}%", *races_winrates.get(&race.id).unwrap()), STYLES.get(&Style::ThinBorderTextWrap)?)?;
-                }
-            }
+            .write_with_format(0, games_column, &breakdown.name, STYLES.get(&Style::ThinBorderTextCenter)?)?
+            .set_cell_format(1, games_column, STYLES.get(&Style::BackgroundSilver)?)?
+            .write_with_format(winrates_header_row, winrate_column, &breakdown.name, STYLES.get(&Style::ThinBorderTextCenter)?)?;
+        for (index, race) in races_data.iter().enumerate() {
+            let race_totals = breakdown.race(race.id);
+            worksheet.write_with_format(layout.win_loss_row(index), games_column, race_totals.map(|r| r.games()).unwrap_or(0), STYLES.get(&Style::ThinBorderTextWrap)?)?;
+            winrates.write(
+                worksheet,
+                winrates_header_row + 1 + index as u32,
+                winrate_column,
+                &race_totals.map(|r| r.results).unwrap_or_default(),
+                None,
+                STYLES.get(&Style::ThinBorderPercent)?
+            )?;
         }
+    }
 
-        worksheet
-            .set_cell_format(1 + (*most_played_race as u32), 17, STYLES.get(&Style::BackgroundGreen)?)?
-            .set_cell_format(1 + (*least_played_race as u32), 17, STYLES.get(&Style::BackgroundRed)?)?
-            .set_cell_format(11 + (*race_with_most_winrate as u32), 1, STYLES.get(&Style::BackgroundGreen)?)?
-            .set_cell_format(11 + (*race_with_least_winrate as u32), 1, STYLES.get(&Style::BackgroundRed)?)?;
-        Ok(())
+    let games_range = |first_col: u16, last_col: u16| CellRange {
+        first_row: layout.win_loss_row(0),
+        first_col,
+        last_row: layout.win_loss_row(0) + layout.races_count - 1,
+        last_col
+    };
+    heatmaps.race_games.add_counts(worksheet, &[games_range(total_games_column, total_games_column)], HEATMAP_GREEN)?;
+    let mut winrate_ranges = vec![CellRange::column(1, winrates_header_row + 1, layout.races_count)];
+    if let Some(last_index) = stats.breakdown.len().checked_sub(1) {
+        // tournaments have fewer games than season, so they are scaled separately
+        heatmaps.race_games.add_counts(worksheet, &[games_range(layout.breakdown_games_column(0), layout.breakdown_games_column(last_index))], HEATMAP_GREEN)?;
+        winrate_ranges.push(CellRange {
+            first_row: winrates_header_row + 1,
+            first_col: layout.breakdown_winrate_column(0),
+            last_row: winrates_header_row + layout.races_count,
+            last_col: layout.breakdown_winrate_column(last_index)
+        });
     }
+    heatmaps.race_winrates.add_winrates(worksheet, &winrate_ranges)?;
+    Ok(())
+}
+
+fn build_match_ups_games_and_winrates(
+    races_data: &[RaceInfo],
+    layout: &PairSheetLayout,
+    stats: &TournamentStats,
+    messages: &Messages,
+    winrates: &WinrateWriter,
+    heatmaps: &HeatmapSettings,
+    worksheet: &mut Worksheet
+) -> Result<(), crate::error::Error> {
+    let games_title_row = layout.match_up_games_title_row();
+    let winrates_title_row = layout.match_up_winrates_title_row();
+    // tables start with header row of race names below title
+    let games_header_row = games_title_row + 2;
+    let winrates_header_row = winrates_title_row + 2;
 
-    fn build_match_ups_games_and_winrates(&mut self, races_data: &Vec<RaceInfo>, worksheet: &mut Worksheet) -> Result<(), crate::error::Error> {
-        let mut most_played_pair_first = 0;
-        let mut most_played_pair_second = 0;
-        
-        let mut least_played_pair_first = 0;
-        let mut least_played_pair_second = 0;
-    
-        let mut most_played_pair_games = u32::MIN;
-        let mut least_played_pair_games = u32::MAX;
-    
-        worksheet.merge_range(21, 3, 21, 6, "Число игр по матчапам", 
-            &Format::new().set_align(rust_xlsxwriter::FormatAlign::Center).set_align(rust_xlsxwriter::FormatAlign::CenterAcross).set_bold())?;
-    
-        worksheet.merge_range(33, 3, 33, 6, "Винрейты матчапов", 
+    worksheet.merge_range(games_title_row, 3, games_title_row, 6, messages.match_ups_games_title,
         &Format::new().set_align(rust_xlsxwriter::FormatAlign::Center).set_align(rust_xlsxwriter::FormatAlign::CenterAcross).set_bold())?;
-    
-        for race in races_data {
-            match race.id {
-                0 => {},
-                _=> {
-                    let col_offset = race.id as u16;
-                    let games_row_offset = 23 + (race.id as u32);
-                    let winrate_row_offset = 35 + (race.id as u32);
-    
-                    worksheet.write_with_format(games_row_offset, 0, &race.name, STYLES.get(&Style::ThinBorderTextWrap)?)?;
-                    worksheet.write_with_format(23, col_offset, &race.name, STYLES.get(&Style::ThinBorderTextWrap)?)?;
-    
-                    worksheet.write_with_format(winrate_row_offset, 0, &race.name, STYLES.get(&Style::ThinBorderTextWrap)?)?;
-                    worksheet.write_with_format(35, col_offset, &race.name, STYLES.get(&Style::ThinBorderTextWrap)?)?;
-    
-                    for opponent_race in races_data {
-                        match opponent_race.id {
-                            0 => {},
-                            _=> {
-                                let col_offset = opponent_race.id as u16;
-                                if race.id == opponent_race.id {
-                                    worksheet.set_cell_format(games_row_offset, col_offset, STYLES.get(&Style::BackgroundBlack)?)?;
-                                    worksheet.set_cell_format(winrate_row_offset, col_offset, STYLES.get(&Style::BackgroundBlack)?)?;
-                                }
-                                else {
-                                    let pair_wins = *self.wins_by_race.get(&race.id).unwrap().get(&opponent_race.id).unwrap();
-                                    let pair_losses = *self.losses_by_race.get(&race.id).unwrap().get(&opponent_race.id).unwrap();
-                                    let total_pair_games = (pair_wins + pair_losses) as u32;
-    
-                                    let pair_winrate = (pair_wins as f32) / (total_pair_games as f32) * 100.0;
-                                    worksheet.write_with_format(games_row_offset, col_offset, total_pair_games as u32, STYLES.get(&Style::ThinBorderTextWrap)?)?;
-                                    worksheet.write_with_format(
-                                        winrate_row_offset, 
-                                        col_offset, 
-                                        format!("{:.3}%", pair_winrate as f32), 
-                                        STYLES.get(&Style::ThinBorderTextWrap)?
-                                    )?;
-    
-                                    if total_pair_games > most_played_pair_games {
-                                        most_played_pair_games = total_pair_games;
-                                        most_played_pair_first = opponent_race.id;
-                                        most_played_pair_second = race.id;
-                                    }
-    
-                                    if total_pair_games < least_played_pair_games {
-                                        least_played_pair_games = total_pair_games;
-                                        least_played_pair_first = opponent_race.id;
-                                        least_played_pair_second = race.id;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+
+    worksheet.merge_range(winrates_title_row, 3, winrates_title_row, 6, messages.match_ups_winrates_title,
+    &Format::new().set_align(rust_xlsxwriter::FormatAlign::Center).set_align(rust_xlsxwriter::FormatAlign::CenterAcross).set_bold())?;
+
+    for (index, race) in races_data.iter().enumerate() {
+        let col_offset = layout.match_up_column(index);
+        let games_row_offset = games_header_row + 1 + index as u32;
+        let winrate_row_offset = winrates_header_row + 1 + index as u32;
+
+        worksheet.write_with_format(games_row_offset, 0, &race.name, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+        worksheet.write_with_format(games_header_row, col_offset, &race.name, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+
+        worksheet.write_with_format(winrate_row_offset, 0, &race.name, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+        worksheet.write_with_format(winrates_header_row, col_offset, &race.name, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+
+        for (opponent_index, opponent_race) in races_data.iter().enumerate() {
+            let col_offset = layout.match_up_column(opponent_index);
+            if race.id == opponent_race.id {
+                worksheet.set_cell_format(games_row_offset, col_offset, STYLES.get(&Style::BackgroundBlack)?)?;
+                worksheet.set_cell_format(winrate_row_offset, col_offset, STYLES.get(&Style::BackgroundBlack)?)?;
+            }
+            else {
+                let results = pair_results(stats, race.id, opponent_race.id);
+                let total_pair_games = results.games();
+
+                worksheet.write_with_format(games_row_offset, col_offset, total_pair_games, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+                winrates.write(
+                    worksheet,
+                    winrate_row_offset,
+                    col_offset,
+                    &results,
+                    None,
+                    STYLES.get(&Style::ThinBorderPercent)?
+                )?;
             }
         }
-        // MOST - LEAST PLAYED PAIRS
-        worksheet.set_cell_format(23 + (most_played_pair_first as u32), most_played_pair_second as u16, STYLES.get(&Style::BackgroundGreen)?)?;
-        worksheet.set_cell_format(23 + (most_played_pair_second as u32), most_played_pair_first as u16, STYLES.get(&Style::BackgroundGreen)?)?;
-        worksheet.set_cell_format(23 + (least_played_pair_first as u32), least_played_pair_second as u16, STYLES.get(&Style::BackgroundRed)?)?;
-        worksheet.set_cell_format(23 + (least_played_pair_second as u32), least_played_pair_first as u16, STYLES.get(&Style::BackgroundRed)?)?;
-        Ok(())
     }
+
+    // mirrors are black cells without values, so they don't affect scales
+    let table = |header_row: u32| CellRange {
+        first_row: header_row + 1,
+        first_col: layout.match_up_column(0),
+        last_row: header_row + layout.races_count,
+        last_col: layout.match_up_column(races_data.len() - 1)
+    };
+    heatmaps.match_up_games.add_counts(worksheet, &[table(games_header_row)], HEATMAP_GREEN)?;
+    heatmaps.match_up_winrates.add_winrates(worksheet, &[table(winrates_header_row)])?;
+    Ok(())
 }
 
-fn calc_games(data: &HashMap<i64, usize>) -> usize {
-    data.into_iter().map(|d| *d.1).sum()
-}
\ No newline at end of file
+fn pair_results(stats: &TournamentStats, race: i64, opponent_race: i64) -> WinLoss {
+    stats.race_pair(race, opponent_race)
+        .map(|p| p.results)
+        .unwrap_or_default()
+}
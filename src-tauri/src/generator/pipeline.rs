@@ -0,0 +1,224 @@
+use std::{fs::File, io::BufWriter, path::Path};
+
+use rust_xlsxwriter::workbook::Workbook;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{error::Error, services::tournament::{service::TournamentService, types::ModType}};
+
+use super::{catalog::{localize, races}, comparison::{ComparisonSettings, TournamentComparison}, comparison_sheets::{build_comparison_bargains_sheet, build_comparison_heroes_sheet, build_comparison_match_ups_sheet, build_comparison_races_sheet}, head_to_head::{HeadToHead, HeadToHeadFormat}, head_to_head_report::{build_head_to_head_sheet, render_head_to_head_page}, heatmap::HeatmapSettings, html_report::render_stats_page, locale::Locale, issues::build_issues_sheet, json::StatsReport, pair::build_pair_stats, player::build_player_sheet, progress::{FetchStage, GenerationProgress, ProgressReporter}, race::build_race_sheet, rating::{RatingSettings, Ratings}, rating_sheets::{build_rating_history_sheet, build_rating_sheet}, season::{Season, SeasonFilter}, winrate::{WinrateSettings, WinrateWriter}, stats::TournamentStats, types::{GameEntry, RejectedGame}, TournamentStatsModel};
+
+/// Fetches everything needed for generation of tournament stats.
+#[tracing::instrument(skip_all, fields(%tournament_id))]
+pub async fn load_stats_model(tournament_service: &TournamentService, tournament_id: Uuid, progress: &ProgressReporter) -> Result<TournamentStatsModel, Error> {
+    let mut tournament_stats_model = TournamentStatsModel::default();
+    progress.report(GenerationProgress::Fetching { stage: FetchStage::Tournament });
+    let tournament = progress.cancellable(tournament_service.get_tournament(tournament_id)).await?
+        .ok_or(Error::Other(format!("No tournament with id {}", tournament_id)))?;
+    progress.report(GenerationProgress::Fetching { stage: FetchStage::Users });
+    let users = progress.cancellable(tournament_service.get_users(tournament_id)).await?;
+    progress.report(GenerationProgress::Fetching { stage: FetchStage::Matches });
+    let matches = progress.cancellable(tournament_service.get_matches(tournament_id, None)).await?;
+    progress.report(GenerationProgress::Fetching { stage: FetchStage::Games });
+    let games = progress.cancellable(tournament_service.get_all_games(tournament_id)).await?;
+    let mut games_converted = vec![];
+    let mut games_rejected = vec![];
+    for game in games {
+        let (game_id, match_id) = (game.id, game.match_id);
+        match GameEntry::try_from(game) {
+            Ok(converted_game) => games_converted.push(converted_game),
+            Err(error) => games_rejected.push(RejectedGame { game_id, match_id, reason: error.to_string() })
+        }
+    }
+    let mod_type: ModType = tournament.mod_type.clone().try_into()?;
+    progress.report(GenerationProgress::Fetching { stage: FetchStage::Heroes });
+    let heroes = progress.cancellable(tournament_service.get_heroes(mod_type.clone())).await?;
+    tournament_stats_model.races = races(&mod_type)?;
+    tournament_stats_model.tournament = Some(tournament);
+    tournament_stats_model.games = games_converted;
+    tournament_stats_model.rejected_games = games_rejected;
+    tournament_stats_model.heroes = heroes;
+    tournament_stats_model.users = users;
+    tournament_stats_model.matches = matches;
+    tracing::info!(
+        games = tournament_stats_model.games.len(),
+        rejected_games = tournament_stats_model.rejected_games.len(),
+        "Stats model loaded"
+    );
+    Ok(tournament_stats_model)
+}
+
+/// Fetches models of several tournaments in given order.
+pub async fn load_stats_models(tournament_service: &TournamentService, tournament_ids: &[Uuid], progress: &ProgressReporter) -> Result<Vec<TournamentStatsModel>, Error> {
+    let mut models = vec![];
+    for tournament_id in tournament_ids {
+        models.push(load_stats_model(tournament_service, *tournament_id, progress).await?);
+    }
+    Ok(models)
+}
+
+/// How workbook is rendered, stats themselves don't depend on it.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkbookSettings {
+    pub locale: Locale,
+    pub winrates: WinrateSettings,
+    pub heatmaps: HeatmapSettings
+}
+
+impl WorkbookSettings {
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+}
+
+/// Builds complete stats workbook for loaded model.
+pub fn build_workbook(tournament_stats_model: &TournamentStatsModel, settings: &WorkbookSettings, progress: &ProgressReporter) -> Result<Workbook, Error> {
+    let stats = TournamentStats::compute(tournament_stats_model)?;
+    render_workbook(tournament_stats_model, &stats, settings, progress)
+}
+
+/// Builds one workbook over all tournaments of season, tournaments left out by filter are reported as warnings.
+pub fn build_season_workbook(season: &Season, settings: &WorkbookSettings, progress: &ProgressReporter) -> Result<Workbook, Error> {
+    for name in &season.skipped {
+        let message = settings.locale.messages().season_tournament_skipped.replace("{tournament}", name);
+        tracing::warn!("{message}");
+        progress.report(GenerationProgress::Warning { message });
+    }
+    let stats = season.stats()?;
+    render_workbook(&season.model, &stats, settings, progress)
+}
+
+/// Renders already computed stats into xlsx workbook, texts and names are taken for locale of `settings`.
+/// Progress is reported after every sheet, generation stops with `Error::Cancelled` between sheets.
+#[tracing::instrument(skip_all, fields(locale = %settings.locale))]
+pub fn render_workbook(tournament_stats_model: &TournamentStatsModel, stats: &TournamentStats, settings: &WorkbookSettings, progress: &ProgressReporter) -> Result<Workbook, Error> {
+    // stats only reference races and heroes by ids, so names can be replaced after they are computed
+    let mut localized_model = tournament_stats_model.clone();
+    localize(&mut localized_model, settings.locale)?;
+    let model = &localized_model;
+    let messages = settings.locale.messages();
+    let winrates = &WinrateWriter::new(&settings.winrates, messages);
+    for issue in &stats.issues {
+        let message = format!("{}: {}", issue.players(model), issue.kind.describe(model, messages));
+        tracing::warn!(game_id = %issue.game_id, "{message}");
+        progress.report(GenerationProgress::Warning { message });
+    }
+
+    let total = 3 + model.races.len() as u32 + stats.players.len() as u32 + u32::from(!stats.issues.is_empty());
+    let mut built = 0;
+    let mut sheet_built = |name: &str| {
+        built += 1;
+        progress.report(GenerationProgress::SheetBuilt { name: name.to_string(), built, total });
+        progress.check_cancelled()
+    };
+
+    let mut workbook = Workbook::new();
+    progress.check_cancelled()?;
+    sheet_span(messages.pair_sheet_name).in_scope(|| build_pair_stats(model, stats, messages, winrates, &settings.heatmaps, &mut workbook))?;
+    sheet_built(messages.pair_sheet_name)?;
+    for race in &model.races {
+        sheet_span(&race.name).in_scope(|| build_race_sheet(race, model, stats, messages, winrates, &settings.heatmaps, &mut workbook))?;
+        sheet_built(&race.name)?;
+    }
+    for (index, player_stats) in stats.players.iter().enumerate() {
+        sheet_span(&player_stats.nickname).in_scope(|| build_player_sheet(model, player_stats, messages, winrates, &mut workbook))?;
+        progress.report(GenerationProgress::PlayerProcessed {
+            nickname: player_stats.nickname.clone(),
+            processed: index as u32 + 1,
+            total: stats.players.len() as u32
+        });
+        sheet_built(&player_stats.nickname)?;
+    }
+    sheet_span(messages.rating_sheet_name).in_scope(|| build_rating_sheet(&stats.ratings, messages, &mut workbook))?;
+    sheet_built(messages.rating_sheet_name)?;
+    sheet_span(messages.rating_history_sheet_name).in_scope(|| build_rating_history_sheet(&stats.ratings, messages, &mut workbook))?;
+    sheet_built(messages.rating_history_sheet_name)?;
+    if !stats.issues.is_empty() {
+        sheet_span(messages.issues_sheet_name).in_scope(|| build_issues_sheet(model, stats, messages, &mut workbook))?;
+        sheet_built(messages.issues_sheet_name)?;
+    }
+    Ok(workbook)
+}
+
+fn sheet_span(name: &str) -> tracing::Span {
+    tracing::debug_span!("sheet", name)
+}
+
+/// Builds workbook with ratings of players replayed over games of `models` in given order, e.g. for seeding of next tournament.
+pub fn build_ratings_workbook(models: &[&TournamentStatsModel], settings: &RatingSettings, locale: Locale) -> Result<Workbook, Error> {
+    let ratings = Ratings::compute(models, settings)?;
+    let messages = locale.messages();
+    let mut workbook = Workbook::new();
+    build_rating_sheet(&ratings, messages, &mut workbook)?;
+    build_rating_history_sheet(&ratings, messages, &mut workbook)?;
+    Ok(workbook)
+}
+
+/// Builds workbook with changes of meta from `previous` tournament to `current` one.
+pub fn build_comparison_workbook(previous: &TournamentStatsModel, current: &TournamentStatsModel, settings: &ComparisonSettings, locale: Locale) -> Result<Workbook, Error> {
+    let comparison = TournamentComparison::compute(previous, current, settings)?;
+    // both tournaments are of one mod, so names are taken from the current one
+    let mut localized_model = current.clone();
+    localize(&mut localized_model, locale)?;
+    let model = &localized_model;
+    let messages = locale.messages();
+    let mut workbook = Workbook::new();
+    build_comparison_races_sheet(model, &comparison, messages, &mut workbook)?;
+    build_comparison_match_ups_sheet(model, &comparison, messages, &mut workbook)?;
+    build_comparison_heroes_sheet(model, &comparison, messages, &mut workbook)?;
+    if !comparison.bargains.is_empty() {
+        build_comparison_bargains_sheet(model, &comparison, messages, &mut workbook)?;
+    }
+    Ok(workbook)
+}
+
+/// Computes head-to-head of two players over games of all `models` of the same mod and game type as the first one,
+/// names are taken for `locale`. Other tournaments are listed in `skipped` of result.
+pub fn build_head_to_head(models: Vec<TournamentStatsModel>, first: Uuid, second: Uuid, locale: Locale) -> Result<HeadToHead, Error> {
+    let filter = SeasonFilter::of(models.first().ok_or(Error::Other("No tournaments given".to_string()))?)?;
+    let Season { mut model, skipped, .. } = Season::merge(models, &filter)?;
+    for name in &skipped {
+        tracing::warn!(tournament = %name, "Tournament skipped, other mod or game type");
+    }
+    localize(&mut model, locale)?;
+    let stats = TournamentStats::compute(&model)?;
+    Ok(HeadToHead { skipped, ..HeadToHead::compute(&model, &stats, first, second)? })
+}
+
+/// Saves head-to-head as workbook, HTML page or JSON.
+pub fn save_head_to_head(head_to_head: &HeadToHead, format: HeadToHeadFormat, settings: &WorkbookSettings, path: &Path) -> Result<(), Error> {
+    let messages = settings.locale.messages();
+    match format {
+        HeadToHeadFormat::Xlsx => {
+            let mut workbook = Workbook::new();
+            build_head_to_head_sheet(head_to_head, messages, &WinrateWriter::new(&settings.winrates, messages), &mut workbook)?;
+            workbook.save(path)?;
+        },
+        HeadToHeadFormat::Html => std::fs::write(path, render_head_to_head_page(head_to_head, messages))?,
+        HeadToHeadFormat::Json => serde_json::to_writer_pretty(BufWriter::new(File::create(path)?), head_to_head)?
+    }
+    Ok(())
+}
+
+/// Builds JSON version of stats for loaded model.
+pub fn build_json_report(tournament_stats_model: &TournamentStatsModel) -> Result<StatsReport, Error> {
+    let stats = TournamentStats::compute(tournament_stats_model)?;
+    StatsReport::new(tournament_stats_model, stats)
+}
+
+/// Builds standalone HTML page with stats for loaded model, texts and names are taken for locale of `settings`.
+pub fn build_html_report(tournament_stats_model: &TournamentStatsModel, settings: &WorkbookSettings) -> Result<String, Error> {
+    let stats = TournamentStats::compute(tournament_stats_model)?;
+    let mut localized_model = tournament_stats_model.clone();
+    localize(&mut localized_model, settings.locale)?;
+    render_stats_page(&localized_model, &stats, settings.locale.messages(), &settings.winrates)
+}
+
+/// Returns name of tournament model was loaded for.
+pub fn tournament_name(tournament_stats_model: &TournamentStatsModel) -> Result<&str, Error> {
+    Ok(&tournament_stats_model.tournament.as_ref()
+        .ok_or(Error::Other("No tournament provided for generation".to_string()))?
+        .name)
+}
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+use crate::error::Error;
+
+/// Name of Tauri event generation progress is emitted with.
+pub const GENERATION_PROGRESS_EVENT: &str = "generation-progress";
+
+/// Data of tournament downloaded before stats are computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FetchStage {
+    Tournament,
+    Users,
+    Matches,
+    Games,
+    Heroes
+}
+
+/// Step of generation, frontend gets every one as `GENERATION_PROGRESS_EVENT` payload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum GenerationProgress {
+    Fetching { stage: FetchStage },
+    /// `built` of `total` sheets of workbook are ready, players sheets are counted too.
+    SheetBuilt { name: String, built: u32, total: u32 },
+    PlayerProcessed { nickname: String, processed: u32, total: u32 },
+    /// Problem that doesn't stop generation, e.g. game that won't be counted.
+    Warning { message: String },
+    Saving
+}
+
+/// Receives progress of generation and tells it to stop once `cancellation` is cancelled.
+pub struct ProgressReporter {
+    sink: Box<dyn Fn(GenerationProgress) + Send + Sync>,
+    cancellation: CancellationToken
+}
+
+impl Default for ProgressReporter {
+    /// Reporter that drops progress and is never cancelled.
+    fn default() -> Self {
+        ProgressReporter { sink: Box::new(|_| {}), cancellation: CancellationToken::new() }
+    }
+}
+
+impl ProgressReporter {
+    pub fn new(sink: impl Fn(GenerationProgress) + Send + Sync + 'static, cancellation: CancellationToken) -> Self {
+        ProgressReporter { sink: Box::new(sink), cancellation }
+    }
+
+    /// Reporter that drops progress, but stops generation once `cancellation` is cancelled.
+    pub fn cancelled_by(cancellation: CancellationToken) -> Self {
+        ProgressReporter { sink: Box::new(|_| {}), cancellation }
+    }
+
+    pub fn report(&self, progress: GenerationProgress) {
+        (self.sink)(progress);
+    }
+
+    /// Fails with `Error::Cancelled` if generation must stop, called between steps of generation.
+    pub fn check_cancelled(&self) -> Result<(), Error> {
+        if self.cancellation.is_cancelled() {
+            Err(Error::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Runs `request`, it's dropped with `Error::Cancelled` as soon as generation must stop.
+    pub async fn cancellable<T>(&self, request: impl Future<Output = Result<T, Error>>) -> Result<T, Error> {
+        tokio::select! {
+            result = request => result,
+            _ = self.cancellation.cancelled() => Err(Error::Cancelled)
+        }
+    }
+}
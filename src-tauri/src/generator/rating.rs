@@ -0,0 +1,248 @@
+use std::{collections::HashMap, f64::consts::PI};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{error::Error, services::tournament::types::GameResult};
+
+use super::TournamentStatsModel;
+
+/// Glicko-2 works in its own scale, ratings are converted from and into usual Glicko one with these.
+const GLICKO2_SCALE: f64 = 173.7178;
+const GLICKO2_BASE: f64 = 1500.0;
+/// Precision of volatility iteration.
+const VOLATILITY_EPSILON: f64 = 0.000001;
+
+/// Parameters ratings are computed with, defaults are ones usually recommended for both systems.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RatingSettings {
+    pub elo_initial: f64,
+    /// Maximum change of Elo rating after single game.
+    pub elo_k: f64,
+    pub glicko_initial: Glicko2Rating,
+    /// Constrains change of volatility, lower values make ratings more stable.
+    pub glicko_tau: f64
+}
+
+impl Default for RatingSettings {
+    fn default() -> Self {
+        RatingSettings {
+            elo_initial: 1500.0,
+            elo_k: 32.0,
+            glicko_initial: Glicko2Rating { rating: 1500.0, deviation: 350.0, volatility: 0.06 },
+            glicko_tau: 0.5
+        }
+    }
+}
+
+/// Glicko-2 rating in Glicko scale.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Glicko2Rating {
+    pub rating: f64,
+    pub deviation: f64,
+    pub volatility: f64
+}
+
+impl Glicko2Rating {
+    fn mu(&self) -> f64 {
+        (self.rating - GLICKO2_BASE) / GLICKO2_SCALE
+    }
+
+    fn phi(&self) -> f64 {
+        self.deviation / GLICKO2_SCALE
+    }
+
+    /// Rating after rating period with `results`, every result is opponent's rating before period and score of player (1 is win, 0 is loss).
+    pub fn updated(&self, results: &[(Glicko2Rating, f64)], tau: f64) -> Glicko2Rating {
+        let (mu, phi, sigma) = (self.mu(), self.phi(), self.volatility);
+        if results.is_empty() {
+            return Glicko2Rating { deviation: (phi * phi + sigma * sigma).sqrt() * GLICKO2_SCALE, ..*self };
+        }
+
+        let mut variance_inverse = 0.0;
+        let mut improvement_sum = 0.0;
+        for (opponent, score) in results {
+            let g = g(opponent.phi());
+            let expected = 1.0 / (1.0 + (-g * (mu - opponent.mu())).exp());
+            variance_inverse += g * g * expected * (1.0 - expected);
+            improvement_sum += g * (score - expected);
+        }
+        let variance = 1.0 / variance_inverse;
+        let delta = variance * improvement_sum;
+
+        let volatility = new_volatility(phi, sigma, variance, delta, tau);
+        let phi_star = (phi * phi + volatility * volatility).sqrt();
+        let new_phi = 1.0 / (1.0 / (phi_star * phi_star) + 1.0 / variance).sqrt();
+        let new_mu = mu + new_phi * new_phi * improvement_sum;
+        Glicko2Rating {
+            rating: new_mu * GLICKO2_SCALE + GLICKO2_BASE,
+            deviation: new_phi * GLICKO2_SCALE,
+            volatility
+        }
+    }
+}
+
+fn g(phi: f64) -> f64 {
+    1.0 / (1.0 + 3.0 * phi * phi / (PI * PI)).sqrt()
+}
+
+/// Solves volatility equation of Glicko-2 with Illinois algorithm.
+fn new_volatility(phi: f64, sigma: f64, variance: f64, delta: f64, tau: f64) -> f64 {
+    let a = (sigma * sigma).ln();
+    let f = |x: f64| {
+        let ex = x.exp();
+        ex * (delta * delta - phi * phi - variance - ex) / (2.0 * (phi * phi + variance + ex).powi(2)) - (x - a) / (tau * tau)
+    };
+
+    let mut lower = a;
+    let mut upper = if delta * delta > phi * phi + variance {
+        (delta * delta - phi * phi - variance).ln()
+    } else {
+        let mut k = 1.0;
+        while f(a - k * tau) < 0.0 {
+            k += 1.0;
+        }
+        a - k * tau
+    };
+    let (mut f_lower, mut f_upper) = (f(lower), f(upper));
+    while (upper - lower).abs() > VOLATILITY_EPSILON {
+        let middle = lower + (lower - upper) * f_lower / (f_upper - f_lower);
+        let f_middle = f(middle);
+        if f_middle * f_upper <= 0.0 {
+            lower = upper;
+            f_lower = f_upper;
+        } else {
+            f_lower /= 2.0;
+        }
+        upper = middle;
+        f_upper = f_middle;
+    }
+    (lower / 2.0).exp()
+}
+
+/// Expected score of player with `rating` against `opponent_rating` in Elo system.
+pub fn elo_expected_score(rating: f64, opponent_rating: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent_rating - rating) / 400.0))
+}
+
+/// Ratings of player after one of matches.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RatingChange {
+    pub match_id: Uuid,
+    pub opponent: Uuid,
+    pub wins: u32,
+    pub losses: u32,
+    pub elo: f64,
+    pub glicko: Glicko2Rating
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerRating {
+    pub user: Uuid,
+    pub nickname: String,
+    pub wins: u32,
+    pub losses: u32,
+    pub elo: f64,
+    pub glicko: Glicko2Rating,
+    /// One entry for every match with played games, in order matches were replayed.
+    pub history: Vec<RatingChange>
+}
+
+impl PlayerRating {
+    pub fn games(&self) -> u32 {
+        self.wins + self.losses
+    }
+}
+
+/// Elo and Glicko-2 ratings of players, sorted by Glicko-2 rating so position in list can be used as seed.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Ratings {
+    pub players: Vec<PlayerRating>
+}
+
+impl Ratings {
+    /// Replays counted games of tournaments in order they are given, matches and games in order model has them.
+    /// Elo changes after every game, for Glicko-2 every match is rating period of its two players,
+    /// deviation of players that don't play doesn't grow as tournaments have no time between rounds.
+    /// Players are matched between tournaments by user id, nickname is taken from latest tournament.
+    pub fn compute(models: &[&TournamentStatsModel], settings: &RatingSettings) -> Result<Self, Error> {
+        let mut players: Vec<PlayerRating> = vec![];
+        let mut positions: HashMap<Uuid, usize> = HashMap::new();
+        for model in models {
+            for user in &model.users {
+                match positions.get(&user.id) {
+                    Some(position) => players[*position].nickname = user.nickname.clone(),
+                    None => {
+                        positions.insert(user.id, players.len());
+                        players.push(PlayerRating {
+                            user: user.id,
+                            nickname: user.nickname.clone(),
+                            wins: 0,
+                            losses: 0,
+                            elo: settings.elo_initial,
+                            glicko: settings.glicko_initial,
+                            history: vec![]
+                        });
+                    }
+                }
+            }
+
+            for tournament_match in &model.matches {
+                let position = |user: Uuid| positions.get(&user).copied()
+                    .ok_or(Error::Other(format!("No user found with id {}", user)));
+                let (first, second) = (position(tournament_match.first_player)?, position(tournament_match.second_player)?);
+                let first_player_results = model.counted_games()
+                    .filter(|game| game.match_id == tournament_match.id)
+                    .filter_map(|game| match game.result {
+                        GameResult::FirstPlayerWon => Some(true),
+                        GameResult::SecondPlayerWon => Some(false),
+                        GameResult::NotSelected => None
+                    })
+                    .collect::<Vec<_>>();
+                if first_player_results.is_empty() || first == second {
+                    continue;
+                }
+
+                let glicko_before = (players[first].glicko, players[second].glicko);
+                for first_won in &first_player_results {
+                    let (first_elo, second_elo) = (players[first].elo, players[second].elo);
+                    let first_score = if *first_won { 1.0 } else { 0.0 };
+                    let change = settings.elo_k * (first_score - elo_expected_score(first_elo, second_elo));
+                    players[first].elo += change;
+                    players[second].elo -= change;
+                }
+
+                let first_wins = first_player_results.iter().filter(|won| **won).count() as u32;
+                let second_wins = first_player_results.len() as u32 - first_wins;
+                for (player, opponent, opponent_glicko, wins, losses) in [
+                    (first, second, glicko_before.1, first_wins, second_wins),
+                    (second, first, glicko_before.0, second_wins, first_wins)
+                ] {
+                    let results = first_player_results.iter()
+                        .map(|first_won| (opponent_glicko, if *first_won == (player == first) { 1.0 } else { 0.0 }))
+                        .collect::<Vec<_>>();
+                    let opponent_user = players[opponent].user;
+                    let player = &mut players[player];
+                    player.glicko = player.glicko.updated(&results, settings.glicko_tau);
+                    player.wins += wins;
+                    player.losses += losses;
+                    player.history.push(RatingChange {
+                        match_id: tournament_match.id,
+                        opponent: opponent_user,
+                        wins,
+                        losses,
+                        elo: player.elo,
+                        glicko: player.glicko
+                    });
+                }
+            }
+        }
+        players.sort_by(|a, b| b.glicko.rating.total_cmp(&a.glicko.rating));
+        Ok(Ratings { players })
+    }
+
+    pub fn player(&self, user: Uuid) -> Option<&PlayerRating> {
+        self.players.iter().find(|p| p.user == user)
+    }
+}
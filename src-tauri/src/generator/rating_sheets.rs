@@ -0,0 +1,70 @@
+use rust_xlsxwriter::workbook::Workbook;
+
+use crate::error::Error;
+
+use super::{locale::Messages, rating::Ratings, styles::{Style, STYLES}};
+
+/// Ratings are shown rounded, volatility keeps this many digits after point.
+const VOLATILITY_DIGITS: i32 = 4;
+
+fn rounded(value: f64, digits: i32) -> f64 {
+    let factor = 10f64.powi(digits);
+    (value * factor).round() / factor
+}
+
+/// Adds sheet with current ratings of players in seeding order.
+pub fn build_rating_sheet(ratings: &Ratings, messages: &Messages, workbook: &mut Workbook) -> Result<(), Error> {
+    let worksheet = workbook.add_worksheet().set_name(messages.rating_sheet_name)?;
+    worksheet.merge_range(0, 0, 0, messages.rating_headers.len() as u16 - 1, messages.rating_title, STYLES.get(&Style::TextBoldCentered)?)?;
+    for (col, header) in messages.rating_headers.iter().enumerate() {
+        worksheet.write_with_format(1, col as u16, *header, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+    }
+    worksheet.set_column_width(1, 25)?;
+    worksheet.set_column_width(7, 16)?;
+    worksheet.set_column_width(8, 14)?;
+
+    for (index, player) in ratings.players.iter().enumerate() {
+        let row = 2 + index as u32;
+        worksheet.write_with_format(row, 0, index as u32 + 1, STYLES.get(&Style::ThinBorderTextCenter)?)?;
+        worksheet.write_with_format(row, 1, &player.nickname, STYLES.get(&Style::ThinBorderTextCenter)?)?;
+        worksheet.write_with_format(row, 2, player.games(), STYLES.get(&Style::ThinBorderTextCenter)?)?;
+        worksheet.write_with_format(row, 3, player.wins, STYLES.get(&Style::ThinBorderTextCenter)?)?;
+        worksheet.write_with_format(row, 4, player.losses, STYLES.get(&Style::ThinBorderTextCenter)?)?;
+        worksheet.write_with_format(row, 5, player.elo.round(), STYLES.get(&Style::ThinBorderTextCenter)?)?;
+        worksheet.write_with_format(row, 6, player.glicko.rating.round(), STYLES.get(&Style::ThinBorderTextCenter)?)?;
+        worksheet.write_with_format(row, 7, player.glicko.deviation.round(), STYLES.get(&Style::ThinBorderTextCenter)?)?;
+        worksheet.write_with_format(row, 8, rounded(player.glicko.volatility, VOLATILITY_DIGITS), STYLES.get(&Style::ThinBorderTextCenter)?)?;
+    }
+    Ok(())
+}
+
+/// Adds sheet with table of rating changes for every player that played, tables go one under another.
+pub fn build_rating_history_sheet(ratings: &Ratings, messages: &Messages, workbook: &mut Workbook) -> Result<(), Error> {
+    let worksheet = workbook.add_worksheet().set_name(messages.rating_history_sheet_name)?;
+    let last_col = messages.rating_history_headers.len() as u16 - 1;
+    worksheet.set_column_width(1, 25)?;
+    worksheet.set_column_width(6, 16)?;
+
+    let mut row = 0;
+    for player in ratings.players.iter().filter(|p| !p.history.is_empty()) {
+        worksheet.merge_range(row, 0, row, last_col, &player.nickname, STYLES.get(&Style::TextBoldCentered)?)?;
+        for (col, header) in messages.rating_history_headers.iter().enumerate() {
+            worksheet.write_with_format(row + 1, col as u16, *header, STYLES.get(&Style::ThinBorderTextWrap)?)?;
+        }
+        row += 2;
+        for (index, change) in player.history.iter().enumerate() {
+            let opponent = ratings.player(change.opponent)
+                .ok_or(Error::Other(format!("No rating found for user {}", change.opponent)))?;
+            worksheet.write_with_format(row, 0, index as u32 + 1, STYLES.get(&Style::ThinBorderTextCenter)?)?;
+            worksheet.write_with_format(row, 1, &opponent.nickname, STYLES.get(&Style::ThinBorderTextCenter)?)?;
+            worksheet.write_with_format(row, 2, change.wins, STYLES.get(&Style::ThinBorderTextCenter)?)?;
+            worksheet.write_with_format(row, 3, change.losses, STYLES.get(&Style::ThinBorderTextCenter)?)?;
+            worksheet.write_with_format(row, 4, change.elo.round(), STYLES.get(&Style::ThinBorderTextCenter)?)?;
+            worksheet.write_with_format(row, 5, change.glicko.rating.round(), STYLES.get(&Style::ThinBorderTextCenter)?)?;
+            worksheet.write_with_format(row, 6, change.glicko.deviation.round(), STYLES.get(&Style::ThinBorderTextCenter)?)?;
+            row += 1;
+        }
+        row += 1;
+    }
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{error::Error, services::tournament::types::{GameType, ModType}};
+
+use super::{pipeline::tournament_name, stats::{TournamentBreakdown, TournamentStats}, validation::validate, TournamentStatsModel};
+
+/// Tournaments of season must be of the same mod and game type, otherwise races and heroes stats make no sense together.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeasonFilter {
+    pub mod_type: ModType,
+    pub game_type: GameType
+}
+
+impl SeasonFilter {
+    /// Filter keeping tournaments of the same types as tournament of `model`.
+    pub fn of(model: &TournamentStatsModel) -> Result<Self, Error> {
+        let tournament = model.tournament.as_ref().ok_or(Error::Other("No tournament provided for generation".to_string()))?;
+        Ok(SeasonFilter {
+            mod_type: tournament.mod_type.clone().try_into()?,
+            game_type: tournament.game_type.clone().try_into()?
+        })
+    }
+
+    pub fn matches(&self, model: &TournamentStatsModel) -> Result<bool, Error> {
+        let tournament = model.tournament.as_ref().ok_or(Error::Other("No tournament provided for generation".to_string()))?;
+        Ok(ModType::try_from(tournament.mod_type.clone())? == self.mod_type &&
+            GameType::try_from(tournament.game_type.clone())? == self.game_type)
+    }
+}
+
+/// Several tournaments reported as one.
+pub struct Season {
+    /// Users of all tournaments merged by id, matches and games are concatenated in order of tournaments.
+    pub model: TournamentStatsModel,
+    /// Tournaments merged into `model`.
+    pub tournaments: Vec<TournamentStatsModel>,
+    /// Names of tournaments left out by filter.
+    pub skipped: Vec<String>
+}
+
+impl Season {
+    /// Merges tournaments matching `filter`, fails if there are none.
+    pub fn merge(models: Vec<TournamentStatsModel>, filter: &SeasonFilter) -> Result<Self, Error> {
+        let mut tournaments = vec![];
+        let mut skipped = vec![];
+        for model in models {
+            if filter.matches(&model)? {
+                tournaments.push(model);
+            } else {
+                skipped.push(tournament_name(&model)?.to_string());
+            }
+        }
+        let first = tournaments.first()
+            .ok_or(Error::Other(format!("No {} {} tournaments given", filter.mod_type, filter.game_type)))?;
+
+        let mut tournament = first.tournament.clone()
+            .ok_or(Error::Other("No tournament provided for generation".to_string()))?;
+        tournament.name = tournaments.iter()
+            .map(tournament_name)
+            .collect::<Result<Vec<_>, Error>>()?
+            .join(", ");
+        // games of tournaments without bargains would be counted as played without them
+        tournament.with_bargains = tournaments.iter().all(|t| t.tournament.as_ref().is_some_and(|t| t.with_bargains));
+        tournament.with_bargains_color = tournaments.iter().all(|t| t.tournament.as_ref().is_some_and(|t| t.with_bargains_color));
+
+        let mut model = TournamentStatsModel {
+            tournament: Some(tournament),
+            races: first.races.clone(),
+            heroes: first.heroes.clone(),
+            ..Default::default()
+        };
+        for other in &tournaments {
+            for user in &other.users {
+                // nickname from the latest tournament is used
+                match model.users.iter_mut().find(|u| u.id == user.id) {
+                    Some(existing) => *existing = user.clone(),
+                    None => model.users.push(user.clone())
+                }
+            }
+            model.matches.extend(other.matches.iter().cloned());
+            model.games.extend(other.games.iter().cloned());
+            model.rejected_games.extend(other.rejected_games.iter().cloned());
+        }
+        tracing::info!(tournaments = tournaments.len(), skipped = skipped.len(), games = model.games.len(), "Season merged");
+        Ok(Season { model, tournaments, skipped })
+    }
+
+    /// Stats of merged model with breakdown by tournament.
+    pub fn stats(&self) -> Result<TournamentStats, Error> {
+        let mut stats = TournamentStats::compute(&self.model)?;
+        // bargains settings may differ, so every game is validated against its own tournament
+        stats.issues = self.tournaments.iter()
+            .flat_map(validate)
+            .collect();
+        stats.breakdown = self.tournaments.iter()
+            .map(TournamentBreakdown::compute)
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(stats)
+    }
+}
@@ -0,0 +1,89 @@
+use std::{convert::Infallible, fs::File, io::{BufReader, BufWriter}, path::{Path, PathBuf}, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{error::Error, services::tournament::service::TournamentService};
+
+use super::{pipeline::load_stats_model, progress::ProgressReporter, TournamentStatsModel};
+
+/// Must be increased on every incompatible change of `TournamentStatsModel` layout.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotMetadata {
+    pub format_version: u32,
+    pub tournament_id: Uuid,
+    /// RFC 3339 time data was downloaded at.
+    pub fetched_at: String,
+    pub api_url: String
+}
+
+/// Everything generation needs, saved to disk so stats can be built without API.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TournamentSnapshot {
+    pub metadata: SnapshotMetadata,
+    pub model: TournamentStatsModel
+}
+
+impl TournamentSnapshot {
+    pub async fn fetch(tournament_service: &TournamentService, tournament_id: Uuid, progress: &ProgressReporter) -> Result<Self, Error> {
+        let model = load_stats_model(tournament_service, tournament_id, progress).await?;
+        Ok(TournamentSnapshot {
+            metadata: SnapshotMetadata {
+                format_version: SNAPSHOT_FORMAT_VERSION,
+                tournament_id,
+                fetched_at: chrono::Utc::now().to_rfc3339(),
+                api_url: tournament_service.api_url().to_string()
+            },
+            model
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(writer, self)?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let reader = BufReader::new(File::open(path)?);
+        let snapshot: TournamentSnapshot = serde_json::from_reader(reader)?;
+        if snapshot.metadata.format_version != SNAPSHOT_FORMAT_VERSION {
+            return Err(Error::Other(format!(
+                "Snapshot {path:?} has format version {}, but {SNAPSHOT_FORMAT_VERSION} is expected",
+                snapshot.metadata.format_version
+            )));
+        }
+        Ok(snapshot)
+    }
+}
+
+/// Where tournament data is taken from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TournamentSource {
+    Tournament(Uuid),
+    Snapshot(PathBuf)
+}
+
+impl TournamentSource {
+    pub async fn load(&self, tournament_service: &TournamentService, progress: &ProgressReporter) -> Result<TournamentStatsModel, Error> {
+        match self {
+            TournamentSource::Tournament(tournament_id) => load_stats_model(tournament_service, *tournament_id, progress).await,
+            TournamentSource::Snapshot(path) => Ok(TournamentSnapshot::load(path)?.model)
+        }
+    }
+}
+
+/// Anything that isn't tournament id is taken as path of snapshot.
+impl FromStr for TournamentSource {
+    type Err = Infallible;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(match Uuid::parse_str(value) {
+            Ok(tournament_id) => TournamentSource::Tournament(tournament_id),
+            Err(_) => TournamentSource::Snapshot(PathBuf::from(value))
+        })
+    }
+}
@@ -0,0 +1,579 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{error::Error, services::tournament::types::{BargainsColor, GameResult}};
+
+use super::{rating::{RatingSettings, Ratings}, types::{GameEntry, ResultOutput}, validation::{validate, GameIssue}, TournamentStatsModel};
+
+/// Quantile of normal distribution for 95% confidence intervals of winrates.
+pub const WINRATE_CONFIDENCE_Z: f64 = 1.96;
+
+/// Wins and losses of something(race, hero, player) in some set of games.
+/// Serialized together with winrate so consumers of exported stats don't have to compute it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "WinLossRecord", from = "WinLossRecord")]
+pub struct WinLoss {
+    pub wins: u32,
+    pub losses: u32
+}
+
+impl WinLoss {
+    pub fn games(&self) -> u32 {
+        self.wins + self.losses
+    }
+
+    /// Winrate in percents, `None` if nothing was played.
+    pub fn winrate(&self) -> Option<f64> {
+        self.win_ratio().map(|ratio| ratio * 100.0)
+    }
+
+    /// Share of won games from 0 to 1, `None` if nothing was played.
+    pub fn win_ratio(&self) -> Option<f64> {
+        if self.games() == 0 {
+            None
+        } else {
+            Some(self.wins as f64 / self.games() as f64)
+        }
+    }
+
+    /// 95% Wilson score interval of winrate in percents, `None` if nothing was played.
+    /// Unlike winrate itself it stays wide for small samples, e.g. 1 win of 1 game gives 20.7%..100%.
+    pub fn winrate_interval(&self) -> Option<(f64, f64)> {
+        if self.games() == 0 {
+            return None;
+        }
+        let (n, z) = (self.games() as f64, WINRATE_CONFIDENCE_Z);
+        let p = self.wins as f64 / n;
+        let center = (p + z * z / (2.0 * n)) / (1.0 + z * z / n);
+        let margin = z / (1.0 + z * z / n) * (p * (1.0 - p) / n + z * z / (4.0 * n * n)).sqrt();
+        Some(((center - margin).max(0.0) * 100.0, (center + margin).min(1.0) * 100.0))
+    }
+
+    pub fn add_result(&mut self, won: bool) {
+        if won {
+            self.wins += 1;
+        } else {
+            self.losses += 1;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct WinLossRecord {
+    wins: u32,
+    losses: u32,
+    #[serde(default)]
+    winrate: Option<f64>,
+    #[serde(default)]
+    winrate_interval: Option<(f64, f64)>
+}
+
+impl From<WinLoss> for WinLossRecord {
+    fn from(value: WinLoss) -> Self {
+        WinLossRecord { wins: value.wins, losses: value.losses, winrate: value.winrate(), winrate_interval: value.winrate_interval() }
+    }
+}
+
+impl From<WinLossRecord> for WinLoss {
+    fn from(value: WinLossRecord) -> Self {
+        WinLoss { wins: value.wins, losses: value.losses }
+    }
+}
+
+impl std::ops::AddAssign for WinLoss {
+    fn add_assign(&mut self, rhs: Self) {
+        self.wins += rhs.wins;
+        self.losses += rhs.losses;
+    }
+}
+
+/// Overall results of race, mirrors aren't counted as wins or losses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RaceTotals {
+    pub race: i64,
+    pub results: WinLoss,
+    pub mirrors: u32
+}
+
+impl RaceTotals {
+    pub fn games(&self) -> u32 {
+        self.results.games() + self.mirrors
+    }
+}
+
+/// Results of `race` against `opponent_race`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RacePairStats {
+    pub race: i64,
+    pub opponent_race: i64,
+    pub results: WinLoss
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeroVsRaceStats {
+    pub opponent_race: i64,
+    pub results: WinLoss
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeroVsHeroStats {
+    pub opponent_hero: i64,
+    pub results: WinLoss
+}
+
+/// Results of hero picked for some race. Opponent heroes without games aren't listed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeroStats {
+    pub hero: i64,
+    pub results: WinLoss,
+    pub vs_races: Vec<HeroVsRaceStats>,
+    pub vs_heroes: Vec<HeroVsHeroStats>
+}
+
+impl HeroStats {
+    pub fn vs_race(&self, race: i64) -> WinLoss {
+        self.vs_races.iter()
+            .find(|s| s.opponent_race == race)
+            .map(|s| s.results)
+            .unwrap_or_default()
+    }
+
+    pub fn vs_hero(&self, hero: i64) -> WinLoss {
+        self.vs_heroes.iter()
+            .find(|s| s.opponent_hero == hero)
+            .map(|s| s.results)
+            .unwrap_or_default()
+    }
+}
+
+/// Heroes picked for race in order of first pick.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RaceHeroesStats {
+    pub race: i64,
+    /// Picks of race, mirror is two picks.
+    pub total_picks: u32,
+    pub heroes: Vec<HeroStats>
+}
+
+impl RaceHeroesStats {
+    /// Part of race picks this hero was picked in, in percents.
+    pub fn pickrate(&self, hero: &HeroStats) -> f64 {
+        self.pick_ratio(hero) * 100.0
+    }
+
+    /// Share of race picks hero was picked in, from 0 to 1.
+    pub fn pick_ratio(&self, hero: &HeroStats) -> f64 {
+        hero.results.games() as f64 / self.total_picks as f64
+    }
+}
+
+/// Games of race with the same side of gold bargains.
+/// `extreme_amount` is the biggest gold difference(negative for minus bargains), `amount_sum` is used for averages.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct BargainsBucket {
+    pub results: WinLoss,
+    pub extreme_amount: Option<i64>,
+    pub amount_sum: i64
+}
+
+impl BargainsBucket {
+    pub fn average_amount(&self) -> Option<f64> {
+        if self.results.games() == 0 {
+            None
+        } else {
+            Some(self.amount_sum as f64 / self.results.games() as f64)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BargainsVsRaceStats {
+    pub opponent_race: i64,
+    pub plus_gold: BargainsBucket,
+    pub minus_gold: BargainsBucket,
+    pub no_bargains: BargainsBucket
+}
+
+impl BargainsVsRaceStats {
+    /// Average bargain of both plus and minus games against this race.
+    pub fn average_amount(&self) -> f64 {
+        let sum = self.plus_gold.amount_sum + self.minus_gold.amount_sum;
+        if sum == 0 {
+            0.0
+        } else {
+            sum as f64 / (self.plus_gold.results.games() + self.minus_gold.results.games()) as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RaceBargainsStats {
+    pub race: i64,
+    pub vs_races: Vec<BargainsVsRaceStats>,
+    pub average_amount: f64,
+    pub plus_gold: WinLoss,
+    pub minus_gold: WinLoss,
+    pub no_bargains: WinLoss
+}
+
+impl RaceBargainsStats {
+    pub fn total(&self) -> WinLoss {
+        let mut total = self.plus_gold;
+        total += self.minus_gold;
+        total += self.no_bargains;
+        total
+    }
+}
+
+/// Single game from player's point of view.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerGameRecord {
+    pub match_id: Uuid,
+    pub opponent: Uuid,
+    pub player_race: i64,
+    pub player_hero: i64,
+    pub opponent_race: i64,
+    pub opponent_hero: i64,
+    pub bargains_amount: Option<i64>,
+    pub bargains_color: Option<BargainsColor>,
+    pub result: ResultOutput
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PickStats {
+    pub id: i64,
+    pub results: WinLoss
+}
+
+/// Games of player in order they were played and race/hero picks in order of first pick.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerStats {
+    pub user: Uuid,
+    pub nickname: String,
+    pub games: Vec<PlayerGameRecord>,
+    pub races: Vec<PickStats>,
+    pub heroes: Vec<PickStats>
+}
+
+impl PlayerStats {
+    pub fn total(&self) -> WinLoss {
+        let mut total = WinLoss::default();
+        for race in &self.races {
+            total += race.results;
+        }
+        total
+    }
+}
+
+/// Race totals of one tournament of season, shown next to totals of all its tournaments.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TournamentBreakdown {
+    pub tournament: Uuid,
+    pub name: String,
+    pub races: Vec<RaceTotals>
+}
+
+impl TournamentBreakdown {
+    pub fn compute(model: &TournamentStatsModel) -> Result<Self, Error> {
+        let tournament = model.tournament.as_ref().ok_or(Error::Other("No tournament provided for generation".to_string()))?;
+        Ok(TournamentBreakdown {
+            tournament: tournament.id,
+            name: tournament.name.clone(),
+            races: compute_race_results(model).0
+        })
+    }
+
+    pub fn race(&self, race: i64) -> Option<&RaceTotals> {
+        self.races.iter().find(|r| r.race == race)
+    }
+}
+
+/// Everything generated reports show, computed from `TournamentStatsModel` without any rendering.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TournamentStats {
+    pub races: Vec<RaceTotals>,
+    pub race_pairs: Vec<RacePairStats>,
+    pub race_heroes: Vec<RaceHeroesStats>,
+    /// Empty for tournaments without bargains.
+    pub race_bargains: Vec<RaceBargainsStats>,
+    pub players: Vec<PlayerStats>,
+    /// Problems found in games, games with `Error` issues aren't counted in any stats above.
+    #[serde(default)]
+    pub issues: Vec<GameIssue>,
+    /// Ratings after all games of tournament, computed with default settings.
+    #[serde(default)]
+    pub ratings: Ratings,
+    /// Totals of every tournament when stats are computed over several ones, empty otherwise.
+    #[serde(default)]
+    pub breakdown: Vec<TournamentBreakdown>
+}
+
+impl TournamentStats {
+    pub fn compute(model: &TournamentStatsModel) -> Result<Self, Error> {
+        let tournament = model.tournament.as_ref().ok_or(Error::Other("No tournament provided for generation".to_string()))?;
+        let (races, race_pairs) = compute_race_results(model);
+        Ok(TournamentStats {
+            races,
+            race_pairs,
+            race_heroes: model.races.iter()
+                .map(|race| compute_race_heroes(model, race.id))
+                .collect(),
+            race_bargains: if tournament.with_bargains {
+                model.races.iter()
+                    .map(|race| compute_race_bargains(model, race.id))
+                    .collect()
+            } else {
+                vec![]
+            },
+            players: model.users.iter()
+                .map(|user| compute_player(model, user.id, &user.nickname))
+                .collect::<Result<Vec<PlayerStats>, Error>>()?,
+            issues: validate(model),
+            ratings: Ratings::compute(&[model], &RatingSettings::default())?,
+            breakdown: vec![]
+        })
+    }
+
+    pub fn race(&self, race: i64) -> Option<&RaceTotals> {
+        self.races.iter().find(|r| r.race == race)
+    }
+
+    pub fn race_pair(&self, race: i64, opponent_race: i64) -> Option<&RacePairStats> {
+        self.race_pairs.iter().find(|p| p.race == race && p.opponent_race == opponent_race)
+    }
+
+    pub fn race_heroes(&self, race: i64) -> Option<&RaceHeroesStats> {
+        self.race_heroes.iter().find(|r| r.race == race)
+    }
+
+    pub fn race_bargains(&self, race: i64) -> Option<&RaceBargainsStats> {
+        self.race_bargains.iter().find(|r| r.race == race)
+    }
+}
+
+/// Returns winner side of game, `None` if result isn't known.
+fn first_player_won(game: &GameEntry) -> Option<bool> {
+    match game.result {
+        GameResult::FirstPlayerWon => Some(true),
+        GameResult::SecondPlayerWon => Some(false),
+        GameResult::NotSelected => None
+    }
+}
+
+fn compute_race_results(model: &TournamentStatsModel) -> (Vec<RaceTotals>, Vec<RacePairStats>) {
+    let mut pairs: HashMap<(i64, i64), WinLoss> = HashMap::new();
+    let mut mirrors: HashMap<i64, u32> = HashMap::new();
+
+    for game in model.counted_games() {
+        if game.first_player_race == game.second_player_race {
+            *mirrors.entry(game.first_player_race).or_default() += 1;
+            continue;
+        }
+        if let Some(first_won) = first_player_won(game) {
+            pairs.entry((game.first_player_race, game.second_player_race)).or_default().add_result(first_won);
+            pairs.entry((game.second_player_race, game.first_player_race)).or_default().add_result(!first_won);
+        }
+    }
+
+    let mut races = vec![];
+    let mut race_pairs = vec![];
+    for race in &model.races {
+        let mut results = WinLoss::default();
+        for opponent_race in model.races.iter().filter(|r| r.id != race.id) {
+            let pair_results = pairs.get(&(race.id, opponent_race.id)).copied().unwrap_or_default();
+            results += pair_results;
+            race_pairs.push(RacePairStats { race: race.id, opponent_race: opponent_race.id, results: pair_results });
+        }
+        races.push(RaceTotals {
+            race: race.id,
+            results,
+            mirrors: mirrors.get(&race.id).copied().unwrap_or(0)
+        });
+    }
+    (races, race_pairs)
+}
+
+fn compute_race_heroes(model: &TournamentStatsModel, race: i64) -> RaceHeroesStats {
+    let mut total_picks = 0;
+    let mut picked_heroes = vec![];
+    let mut results: HashMap<i64, WinLoss> = HashMap::new();
+    let mut vs_races: HashMap<i64, Vec<HeroVsRaceStats>> = HashMap::new();
+    let mut vs_heroes: HashMap<i64, Vec<HeroVsHeroStats>> = HashMap::new();
+
+    for game in model.counted_games() {
+        // in mirrors heroes of both players are picks of race
+        for (side_race, hero) in [(game.first_player_race, game.first_player_hero), (game.second_player_race, game.second_player_hero)] {
+            if side_race != race {
+                continue;
+            }
+            total_picks += 1;
+            if !picked_heroes.contains(&hero) && model.heroes.iter().any(|h| h.id == hero) {
+                picked_heroes.push(hero);
+            }
+        }
+
+        let Some(first_won) = first_player_won(game) else {
+            continue;
+        };
+
+        let sides = [
+            (game.first_player_race, game.first_player_hero, game.second_player_race, game.second_player_hero, first_won),
+            (game.second_player_race, game.second_player_hero, game.first_player_race, game.first_player_hero, !first_won)
+        ];
+        for (side_race, hero, opponent_race, opponent_hero, won) in sides {
+            if side_race != race {
+                continue;
+            }
+            results.entry(hero).or_default().add_result(won);
+            if opponent_race != race {
+                let hero_vs_races = vs_races.entry(hero).or_default();
+                match hero_vs_races.iter_mut().find(|s| s.opponent_race == opponent_race) {
+                    Some(stats) => stats.results.add_result(won),
+                    None => {
+                        let mut stats = HeroVsRaceStats { opponent_race, results: WinLoss::default() };
+                        stats.results.add_result(won);
+                        hero_vs_races.push(stats);
+                    }
+                }
+            }
+            let hero_vs_heroes = vs_heroes.entry(hero).or_default();
+            match hero_vs_heroes.iter_mut().find(|s| s.opponent_hero == opponent_hero) {
+                Some(stats) => stats.results.add_result(won),
+                None => {
+                    let mut stats = HeroVsHeroStats { opponent_hero, results: WinLoss::default() };
+                    stats.results.add_result(won);
+                    hero_vs_heroes.push(stats);
+                }
+            }
+        }
+    }
+
+    RaceHeroesStats {
+        race,
+        total_picks,
+        heroes: picked_heroes.into_iter()
+            .map(|hero| HeroStats {
+                hero,
+                results: results.get(&hero).copied().unwrap_or_default(),
+                vs_races: vs_races.remove(&hero).unwrap_or_default(),
+                vs_heroes: vs_heroes.remove(&hero).unwrap_or_default()
+            })
+            .collect()
+    }
+}
+
+fn compute_race_bargains(model: &TournamentStatsModel, race: i64) -> RaceBargainsStats {
+    let mut vs_races = vec![];
+    let mut plus_gold = WinLoss::default();
+    let mut minus_gold = WinLoss::default();
+    let mut no_bargains = WinLoss::default();
+
+    for opponent_race in model.races.iter().filter(|r| r.id != race) {
+        let mut stats = BargainsVsRaceStats {
+            opponent_race: opponent_race.id,
+            plus_gold: BargainsBucket::default(),
+            minus_gold: BargainsBucket::default(),
+            no_bargains: BargainsBucket::default()
+        };
+        for game in model.counted_games() {
+            let (race_is_first, opponent_is_first) = (game.first_player_race == race, game.first_player_race == opponent_race.id);
+            let race_is_second = game.second_player_race == race;
+            let opponent_is_second = game.second_player_race == opponent_race.id;
+            if !((race_is_first && opponent_is_second) || (race_is_second && opponent_is_first)) {
+                continue;
+            }
+            let Some(first_won) = first_player_won(game) else {
+                continue;
+            };
+            let won = if race_is_first { first_won } else { !first_won };
+            // bargains amount is stored from the first player's side
+            let amount = if race_is_first { game.bargains_amount } else { -game.bargains_amount };
+            let bucket = match amount {
+                0 => &mut stats.no_bargains,
+                a if a > 0 => &mut stats.plus_gold,
+                _ => &mut stats.minus_gold
+            };
+            bucket.results.add_result(won);
+            if amount != 0 {
+                bucket.amount_sum += amount;
+                bucket.extreme_amount = Some(match bucket.extreme_amount {
+                    Some(extreme) if extreme.abs() >= amount.abs() => extreme,
+                    _ => amount
+                });
+            }
+        }
+        plus_gold += stats.plus_gold.results;
+        minus_gold += stats.minus_gold.results;
+        no_bargains += stats.no_bargains.results;
+        vs_races.push(stats);
+    }
+
+    let average_amount = if vs_races.is_empty() {
+        0.0
+    } else {
+        vs_races.iter().map(|s| s.average_amount()).sum::<f64>() / vs_races.len() as f64
+    };
+
+    RaceBargainsStats { race, vs_races, average_amount, plus_gold, minus_gold, no_bargains }
+}
+
+fn compute_player(model: &TournamentStatsModel, user: Uuid, nickname: &str) -> Result<PlayerStats, Error> {
+    let mut games = vec![];
+    let mut races: Vec<PickStats> = vec![];
+    let mut heroes: Vec<PickStats> = vec![];
+
+    for user_match in model.matches.iter().filter(|m| m.first_player == user || m.second_player == user) {
+        let is_first_player = user_match.first_player == user;
+        let opponent = if is_first_player { user_match.second_player } else { user_match.first_player };
+        if !model.users.iter().any(|u| u.id == opponent) {
+            return Err(Error::Other(format!("No user found with id {}", opponent)));
+        }
+
+        for game in model.counted_games().filter(|game| game.match_id == user_match.id) {
+            let (player_race, player_hero, opponent_race, opponent_hero) = if is_first_player {
+                (game.first_player_race, game.first_player_hero, game.second_player_race, game.second_player_hero)
+            } else {
+                (game.second_player_race, game.second_player_hero, game.first_player_race, game.first_player_hero)
+            };
+            let Some(first_won) = first_player_won(game) else {
+                continue;
+            };
+            let won = first_won == is_first_player;
+            add_pick(&mut races, player_race, won);
+            add_pick(&mut heroes, player_hero, won);
+
+            games.push(PlayerGameRecord {
+                match_id: user_match.id,
+                opponent,
+                player_race,
+                player_hero,
+                opponent_race,
+                opponent_hero,
+                bargains_amount: if game.bargains_amount == -1 {
+                    None
+                } else if !is_first_player {
+                    Some(-game.bargains_amount)
+                } else {
+                    Some(game.bargains_amount)
+                },
+                bargains_color: game.bargains_color.clone(),
+                result: if won { ResultOutput::Win } else { ResultOutput::Loss }
+            });
+        }
+    }
+
+    Ok(PlayerStats { user, nickname: nickname.to_string(), games, races, heroes })
+}
+
+fn add_pick(picks: &mut Vec<PickStats>, id: i64, won: bool) {
+    match picks.iter_mut().find(|p| p.id == id) {
+        Some(pick) => pick.results.add_result(won),
+        None => {
+            let mut pick = PickStats { id, results: WinLoss::default() };
+            pick.results.add_result(won);
+            picks.push(pick);
+        }
+    }
+}
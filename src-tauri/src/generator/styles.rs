@@ -12,20 +12,33 @@ pub enum Style {
     BackgroundSilver,
     BackgroundBlack,
     BackgroundGreen,
-    BackgroundRed
+    BackgroundRed,
+    /// Same as `ThinBorderTextWrap`, but numbers are shown as percents.
+    ThinBorderPercent,
+    /// Change of percents with sign.
+    ThinBorderPercentDelta,
+    /// Same as `ThinBorderTextWrap`, but numbers are shown with two digits after point.
+    ThinBorderDecimal
 }
 
+/// Number format of winrates and pickrates, they are written as fractions.
+pub const PERCENT_FORMAT: &str = "0.000%";
+/// Number format of winrate and pickrate changes, they are written as fractions too.
+pub const PERCENT_DELTA_FORMAT: &str = "+0.0%;-0.0%;0.0%";
+/// Number format of gold amounts averages.
+pub const DECIMAL_FORMAT: &str = "0.00";
+
 pub struct Styles {
     pub data: HashMap<Style, Format>
 }
 
 impl Styles {
     pub fn get(&self, style: &Style) -> Result<&Format, crate::error::Error> {
-        Ok(self.data.get(style).ok_or(crate::error::Error::Other("Incorrect style".to_string()))?)
+        self.data.get(style).ok_or(crate::error::Error::Other("Incorrect style".to_string()))
     }
 }
 
-pub const STYLES: LazyLock<Styles> = LazyLock::new(|| {
+pub static STYLES: LazyLock<Styles> = LazyLock::new(|| {
     Styles {
         data: HashMap::from([
             (Style::ThinBorder, Format::new().set_border(FormatBorder::Thin)),
@@ -37,7 +50,10 @@ pub const STYLES: LazyLock<Styles> = LazyLock::new(|| {
             (Style::BackgroundSilver, Format::new().set_border(FormatBorder::Thin).set_background_color(Color::Silver)),
             (Style::BackgroundBlack, Format::new().set_border(FormatBorder::Thin).set_background_color(Color::Black)),
             (Style::BackgroundGreen, Format::new().set_border(FormatBorder::Thin).set_background_color(Color::Green).set_text_wrap().set_align(FormatAlign::Center)),
-            (Style::BackgroundRed, Format::new().set_border(FormatBorder::Thin).set_background_color(Color::Red).set_text_wrap().set_align(FormatAlign::Center))
+            (Style::BackgroundRed, Format::new().set_border(FormatBorder::Thin).set_background_color(Color::Red).set_text_wrap().set_align(FormatAlign::Center)),
+            (Style::ThinBorderPercent, Format::new().set_border(FormatBorder::Thin).set_align(FormatAlign::Center).set_text_wrap().set_num_format(PERCENT_FORMAT)),
+            (Style::ThinBorderPercentDelta, Format::new().set_border(FormatBorder::Thin).set_align(FormatAlign::Center).set_text_wrap().set_num_format(PERCENT_DELTA_FORMAT)),
+            (Style::ThinBorderDecimal, Format::new().set_border(FormatBorder::Thin).set_align(FormatAlign::Center).set_text_wrap().set_num_format(DECIMAL_FORMAT))
         ])  
     }
 });
\ No newline at end of file
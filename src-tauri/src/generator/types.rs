@@ -2,45 +2,47 @@ use rust_xlsxwriter::worksheet::Worksheet;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{error::Error, graphql::queries::{get_all_games, get_tournament::{self, GetTournamentTournament}}, services::tournament::types::{BargainsColor, GameOutcome, GameResult}};
+use crate::{error::Error, graphql::queries::{get_all_games, get_tournament::GetTournamentTournament}, services::tournament::types::{BargainsColor, GameOutcome, GameResult}};
 
-use super::styles::{Style, STYLES};
+use super::{locale::Messages, styles::{Style, STYLES}};
 
 pub struct PlayerMatchHistoryHeaders<'a> {
     pub headers: Vec<&'a str>
 }
 
 impl<'a> PlayerMatchHistoryHeaders<'a> {
-    pub fn new(tournament_info: &GetTournamentTournament) -> Self {
-        let mut headers = vec!["Фракция игрока", "Герой игрока", "Фракция оппонента", "Герой оппонента"];
-        if tournament_info.with_bargains {
-            headers.push("Торг игрока");
+    pub fn new(tournament_info: &GetTournamentTournament, messages: &'a Messages) -> Self {
+        Self::with_bargains(tournament_info.with_bargains, tournament_info.with_bargains_color, messages)
+    }
+
+    pub fn with_bargains(with_bargains: bool, with_bargains_color: bool, messages: &'a Messages) -> Self {
+        let mut headers = vec![messages.player_race, messages.player_hero, messages.opponent_race, messages.opponent_hero];
+        if with_bargains {
+            headers.push(messages.player_bargains);
         }
-        if tournament_info.with_bargains_color {
-            headers.push("Цвет торга");
+        if with_bargains_color {
+            headers.push(messages.bargains_color);
         }
-        headers.push("Результат");
+        headers.push(messages.result);
         // if tournament_info.game_type == get_tournament::GameType::RMG {
         //     headers.push("Исход");
         // }
-        PlayerMatchHistoryHeaders { headers: headers }
+        PlayerMatchHistoryHeaders { headers }
     }
 
-    pub fn to_xlsx(&self, worksheet: &mut Worksheet) -> Result<(), Error> {
-        worksheet.merge_range(0, 0, 0, self.headers.len() as u16, "История игр", STYLES.get(&Style::TextBoldCentered)?)?;
+    pub fn to_xlsx(&self, messages: &Messages, worksheet: &mut Worksheet) -> Result<(), Error> {
+        worksheet.merge_range(0, 0, 0, self.headers.len() as u16, messages.games_history_title, STYLES.get(&Style::TextBoldCentered)?)?;
         worksheet.set_column_width(0, 14)?;
         worksheet.write_with_format(1, 0, "VS", STYLES.get(&Style::TextCenterColorRed)?)?;
-        let mut col_offset = 1;
-        for cell_name in &self.headers {
+        for (col_offset, cell_name) in (1..).zip(self.headers.iter()) {
             worksheet.set_column_width(col_offset, 14)?;
             worksheet.write_with_format(1, col_offset, *cell_name, STYLES.get(&Style::ThinBorderTextWrap)?)?;
-            col_offset += 1;
         }
         Ok(())
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ResultOutput {
     Win,
     Loss
@@ -60,7 +62,7 @@ pub struct GameHistoryEntry<'a> {
 }
 
 impl<'a> GameHistoryEntry<'a> {
-    pub fn to_xlsx(&self, worksheet: &mut Worksheet, row: u32) -> Result<(), Error> {
+    pub fn to_xlsx(&self, messages: &Messages, worksheet: &mut Worksheet, row: u32) -> Result<(), Error> {
         let mut col = 0;
         worksheet.write_with_format(row, col, self.opponent, STYLES.get(&Style::ThinBorderTextWrap)?)?;
         col+=1;
@@ -77,16 +79,15 @@ impl<'a> GameHistoryEntry<'a> {
             col+=1;
         }
         if let Some(bargains_color) = self.bargains_color {
-            println!("Bargains color: {bargains_color}");
             worksheet.write_with_format(row, col, bargains_color, STYLES.get(&Style::ThinBorderTextWrap)?)?;
             col+=1;
         };
         match self.result {
             ResultOutput::Win => {
-                worksheet.write_with_format(row, col, "Победа".to_string(), STYLES.get(&Style::BackgroundGreen)?)?;
+                worksheet.write_with_format(row, col, messages.victory, STYLES.get(&Style::BackgroundGreen)?)?;
             },
             ResultOutput::Loss => {
-                worksheet.write_with_format(row, col, "Поражение".to_string(), STYLES.get(&Style::BackgroundRed)?)?;
+                worksheet.write_with_format(row, col, messages.defeat, STYLES.get(&Style::BackgroundRed)?)?;
             }
         }
         col+=1;
@@ -97,8 +98,9 @@ impl<'a> GameHistoryEntry<'a> {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameEntry {
+    pub id: Uuid,
     pub match_id: Uuid,
     pub first_player_race: i64,
     pub first_player_hero: i64,
@@ -110,24 +112,36 @@ pub struct GameEntry {
     pub outcome: GameOutcome
 }
 
-impl Into<GameResult> for get_all_games::GameResult {
-    fn into(self) -> GameResult {
-        match self {
-            get_all_games::GameResult::FIRST_PLAYER_WON => GameResult::FirstPlayerWon,
-            get_all_games::GameResult::SECOND_PLAYER_WON => GameResult::SecondPlayerWon,
-            get_all_games::GameResult::NOT_SELECTED => GameResult::NotSelected,
-            _=> unreachable!()
+/// Game from `get_all_games` that failed conversion into `GameEntry`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectedGame {
+    pub game_id: Uuid,
+    pub match_id: Uuid,
+    pub reason: String
+}
+
+impl TryFrom<get_all_games::GameResult> for GameResult {
+    type Error = crate::error::Error;
+
+    fn try_from(value: get_all_games::GameResult) -> Result<Self, Self::Error> {
+        match value {
+            get_all_games::GameResult::FIRST_PLAYER_WON => Ok(GameResult::FirstPlayerWon),
+            get_all_games::GameResult::SECOND_PLAYER_WON => Ok(GameResult::SecondPlayerWon),
+            get_all_games::GameResult::NOT_SELECTED => Ok(GameResult::NotSelected),
+            get_all_games::GameResult::Other(value) => Err(Error::UnknownVariant { enum_name: "GameResult", value })
         }
     }
 }
 
-impl Into<GameOutcome> for get_all_games::GameOutcome {
-    fn into(self) -> GameOutcome {
-        match self {
-            get_all_games::GameOutcome::FINAL_BATTLE_VICTORY => GameOutcome::FinalBattleVictory,
-            get_all_games::GameOutcome::NEUTRALS_VICTORY => GameOutcome::NeutralsVictory,
-            get_all_games::GameOutcome::OPPONENT_SURRENDER => GameOutcome::OpponentSurrender,
-            _=> unreachable!()
+impl TryFrom<get_all_games::GameOutcome> for GameOutcome {
+    type Error = crate::error::Error;
+
+    fn try_from(value: get_all_games::GameOutcome) -> Result<Self, Self::Error> {
+        match value {
+            get_all_games::GameOutcome::FINAL_BATTLE_VICTORY => Ok(GameOutcome::FinalBattleVictory),
+            get_all_games::GameOutcome::NEUTRALS_VICTORY => Ok(GameOutcome::NeutralsVictory),
+            get_all_games::GameOutcome::OPPONENT_SURRENDER => Ok(GameOutcome::OpponentSurrender),
+            get_all_games::GameOutcome::Other(value) => Err(Error::UnknownVariant { enum_name: "GameOutcome", value })
         }
     }
 }
@@ -142,6 +156,7 @@ impl TryFrom<get_all_games::GetAllGamesGamesAll> for GameEntry {
         let second_player_hero = value.second_player_hero.ok_or(Error::NoGameField {field: "second_player_hero".to_string(), game_id: value.id})?;
 
         Ok(GameEntry {
+            id: value.id,
             match_id: value.match_id,
             first_player_race,
             first_player_hero,
@@ -152,13 +167,13 @@ impl TryFrom<get_all_games::GetAllGamesGamesAll> for GameEntry {
                 match color {
                     get_all_games::BargainsColor::BARGAINS_COLOR_BLUE => Some(BargainsColor::BargainsColorBlue),
                     get_all_games::BargainsColor::BARGAINS_COLOR_RED => Some(BargainsColor::BargainsColorRed),
-                    _=> unreachable!()
+                    _=> None
                 }
             } else {
                 None
             },
-            result: value.result.into(),
-            outcome: value.outcome.into()
+            result: value.result.try_into()?,
+            outcome: value.outcome.try_into()?
         })
     }
 }
\ No newline at end of file
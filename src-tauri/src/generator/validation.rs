@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::services::tournament::types::GameResult;
+
+use super::{locale::Messages, types::GameEntry, TournamentStatsModel};
+
+/// `Error` issues exclude game from stats, `Warning` ones are only reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IssueSeverity {
+    Error,
+    Warning
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum IssueKind {
+    /// Game couldn't be converted into `GameEntry`, reason is text of conversion error(missing field or unknown enum value).
+    MissingField { reason: String },
+    ResultNotSelected,
+    UnknownRace { race: i64 },
+    UnknownHero { hero: i64 },
+    HeroNotOfRace { hero: i64, race: i64 },
+    /// Tournament is played without bargains, but game has them.
+    UnexpectedBargains { amount: i64 }
+}
+
+impl IssueKind {
+    pub fn severity(&self) -> IssueSeverity {
+        match self {
+            IssueKind::MissingField { .. } | IssueKind::ResultNotSelected | IssueKind::UnknownRace { .. } | IssueKind::UnknownHero { .. } => IssueSeverity::Error,
+            IssueKind::HeroNotOfRace { .. } | IssueKind::UnexpectedBargains { .. } => IssueSeverity::Warning
+        }
+    }
+
+    pub fn describe(&self, model: &TournamentStatsModel, messages: &Messages) -> String {
+        match self {
+            IssueKind::MissingField { reason } => messages.issue_missing_field.replace("{reason}", reason),
+            IssueKind::ResultNotSelected => messages.issue_result_not_selected.to_string(),
+            IssueKind::UnknownRace { race } => messages.issue_unknown_race.replace("{race}", &race.to_string()),
+            IssueKind::UnknownHero { hero } => messages.issue_unknown_hero.replace("{hero}", &hero.to_string()),
+            IssueKind::HeroNotOfRace { hero, race } => messages.issue_hero_not_of_race
+                .replace("{hero}", model.hero_name(*hero).map(|n| n.as_str()).unwrap_or("?"))
+                .replace("{race}", model.race_name(*race).map(|n| n.as_str()).unwrap_or("?")),
+            IssueKind::UnexpectedBargains { amount } => messages.issue_unexpected_bargains.replace("{amount}", &amount.to_string())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameIssue {
+    pub game_id: Uuid,
+    pub match_id: Uuid,
+    pub kind: IssueKind
+}
+
+/// Issue with everything resolved for showing to user.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GameIssueFrontendModel {
+    pub game_id: Uuid,
+    pub match_id: Uuid,
+    pub players: String,
+    pub severity: IssueSeverity,
+    pub description: String
+}
+
+impl GameIssue {
+    pub fn severity(&self) -> IssueSeverity {
+        self.kind.severity()
+    }
+
+    /// Nicknames of match players in `first vs second` form.
+    pub fn players(&self, model: &TournamentStatsModel) -> String {
+        match model.matches.iter().find(|m| m.id == self.match_id) {
+            Some(game_match) => format!(
+                "{} vs {}",
+                model.user_name(game_match.first_player).map(|n| n.as_str()).unwrap_or("?"),
+                model.user_name(game_match.second_player).map(|n| n.as_str()).unwrap_or("?")
+            ),
+            None => self.match_id.to_string()
+        }
+    }
+
+    pub fn into_frontend_model(&self, model: &TournamentStatsModel, messages: &Messages) -> GameIssueFrontendModel {
+        GameIssueFrontendModel {
+            game_id: self.game_id,
+            match_id: self.match_id,
+            players: self.players(model),
+            severity: self.severity(),
+            description: self.kind.describe(model, messages)
+        }
+    }
+}
+
+/// Checks every loaded game and returns problems in order of games.
+pub fn validate(model: &TournamentStatsModel) -> Vec<GameIssue> {
+    let with_bargains = model.tournament.as_ref().is_some_and(|t| t.with_bargains);
+    let mut issues = model.rejected_games.iter()
+        .map(|game| GameIssue {
+            game_id: game.game_id,
+            match_id: game.match_id,
+            kind: IssueKind::MissingField { reason: game.reason.clone() }
+        })
+        .collect::<Vec<GameIssue>>();
+
+    for game in &model.games {
+        let mut push = |kind| issues.push(GameIssue { game_id: game.id, match_id: game.match_id, kind });
+        if game.result == GameResult::NotSelected {
+            push(IssueKind::ResultNotSelected);
+        }
+        for (race, hero) in [(game.first_player_race, game.first_player_hero), (game.second_player_race, game.second_player_hero)] {
+            if !model.races.iter().any(|r| r.id == race) {
+                push(IssueKind::UnknownRace { race });
+            }
+            match model.heroes.iter().find(|h| h.id == hero) {
+                None => push(IssueKind::UnknownHero { hero }),
+                Some(hero_info) if hero_info.race != race => push(IssueKind::HeroNotOfRace { hero, race }),
+                _ => {}
+            }
+        }
+        // -1 is set for games without bargains, 0 is what server stores by default
+        if !with_bargains && game.bargains_amount != -1 && game.bargains_amount != 0 {
+            push(IssueKind::UnexpectedBargains { amount: game.bargains_amount });
+        }
+    }
+    issues
+}
+
+/// Whether game can be used for stats, matches games without `Error` issues.
+pub fn is_counted(model: &TournamentStatsModel, game: &GameEntry) -> bool {
+    game.result != GameResult::NotSelected &&
+        [game.first_player_race, game.second_player_race].iter().all(|race| model.races.iter().any(|r| r.id == *race)) &&
+        [game.first_player_hero, game.second_player_hero].iter().all(|hero| model.heroes.iter().any(|h| h.id == *hero))
+}
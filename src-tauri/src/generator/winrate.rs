@@ -0,0 +1,68 @@
+use rust_xlsxwriter::{worksheet::Worksheet, Color, Format, Note};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+use super::{locale::Messages, stats::WinLoss};
+
+/// How winrate cells of workbook are shown.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WinrateSettings {
+    /// Adds note with 95% Wilson interval to every winrate cell.
+    pub show_intervals: bool,
+    /// Winrates of fewer games are greyed out.
+    pub min_games: Option<u32>
+}
+
+impl Default for WinrateSettings {
+    fn default() -> Self {
+        WinrateSettings { show_intervals: true, min_games: None }
+    }
+}
+
+/// Writes winrate cells according to `WinrateSettings`.
+pub struct WinrateWriter<'a> {
+    settings: &'a WinrateSettings,
+    messages: &'a Messages
+}
+
+impl<'a> WinrateWriter<'a> {
+    pub fn new(settings: &'a WinrateSettings, messages: &'a Messages) -> Self {
+        WinrateWriter { settings, messages }
+    }
+
+    /// Writes winrate of `results` as fraction, `format` is expected to show it as percents.
+    /// Without games `empty_text` is written, cell is left blank if there is none.
+    pub fn write(&self, worksheet: &mut Worksheet, row: u32, col: u16, results: &WinLoss, empty_text: Option<&str>, format: &Format) -> Result<(), Error> {
+        let small_sample = self.settings.min_games.is_some_and(|min_games| results.games() < min_games);
+        let greyed_format;
+        let format = if small_sample {
+            greyed_format = format.clone().set_font_color(Color::Gray);
+            &greyed_format
+        } else {
+            format
+        };
+        match (results.win_ratio(), empty_text) {
+            (Some(ratio), _) => worksheet.write_with_format(row, col, ratio, format)?,
+            (None, Some(text)) => worksheet.write_with_format(row, col, text, format)?,
+            (None, None) => worksheet.write_blank(row, col, format)?
+        };
+
+        if !self.settings.show_intervals {
+            return Ok(());
+        }
+        if let Some((low, high)) = results.winrate_interval() {
+            let mut note = self.messages.winrate_interval
+                .replace("{low}", &format!("{low:.1}"))
+                .replace("{high}", &format!("{high:.1}"))
+                .replace("{games}", &results.games().to_string());
+            if let (true, Some(min_games)) = (small_sample, self.settings.min_games) {
+                note.push('\n');
+                note.push_str(&self.messages.winrate_small_sample.replace("{min}", &min_games.to_string()));
+            }
+            worksheet.insert_note(row, col, &Note::new(note).add_author_prefix(false))?;
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 use graphql_client::GraphQLQuery;
 use uuid::Uuid;
 
+// graphql_client maps scalars by their schema name
+#[allow(clippy::upper_case_acronyms)]
 type UUID = Uuid;
 
 #[derive(GraphQLQuery)]
@@ -15,7 +17,7 @@ pub struct GetGames;
 #[graphql(
     schema_path = "src/graphql/schema.json",
     query_path = "src/graphql/queries/get_matches.graphql",
-    response_derives = "Debug, Serialize, Deserialize"
+    response_derives = "Debug, Serialize, Deserialize, Clone"
 )]
 pub struct GetMatches;
 
@@ -23,7 +25,7 @@ pub struct GetMatches;
 #[graphql(
     schema_path = "src/graphql/schema.json",
     query_path = "src/graphql/queries/get_users.graphql",
-    response_derives = "Debug, Serialize, Deserialize"
+    response_derives = "Debug, Serialize, Deserialize, Clone"
 )]
 pub struct GetUsers;
 
@@ -56,7 +58,7 @@ pub struct GetTournament;
 #[graphql(
     schema_path = "src/graphql/schema.json",
     query_path = "src/graphql/queries/get_heroes.graphql",
-    response_derives = "Debug, Serialize, Deserialize"
+    response_derives = "Debug, Serialize, Deserialize, Clone"
 )]
 pub struct GetHeroes;
 
@@ -1,13 +1,18 @@
 use app::{commands::{
-    get_heroes_of_race, load_games, load_heroes, load_matches, load_tournament, load_tournaments_list, load_users, select_game, update_game_bargains_amount, update_game_bargains_color, update_game_first_player_hero, update_game_first_player_race, update_game_outcome, update_game_result, update_game_second_player_hero, update_game_second_player_race
+    get_diagnostics, get_heroes_of_race, load_games, load_log_level, update_log_level, load_service_config, update_service_config, cancel_operation, load_heroes, load_matches, load_races, load_tournament, load_tournaments_list, load_users, select_game, update_game_bargains_amount, update_game_bargains_color, update_game_first_player_hero, update_game_first_player_race, update_game_outcome, update_game_result, update_game_second_player_hero, update_game_second_player_race
 }, manager::AppManager};
-use generator::commands::invoke_generation;
-use services::tournament::service::TournamentService;
-use tokio::sync::{Mutex, RwLock};
+use generator::commands::{
+    invoke_comparison, invoke_games_export, invoke_generation, invoke_head_to_head, invoke_head_to_head_export, invoke_html_export, invoke_json_export, invoke_season_generation, invoke_snapshot_generation, invoke_snapshot_save, invoke_validation
+};
+use logging::{LogSettings, Logging, LOG_SETTINGS_FILE_NAME};
+use services::tournament::{config::ServiceConfig, service::TournamentService};
+use tauri::Manager;
+use tokio::sync::RwLock;
 
 pub mod app;
 pub mod generator;
 pub mod graphql;
+pub mod logging;
 pub mod services;
 pub mod error;
 
@@ -15,12 +20,27 @@ pub mod error;
 pub async fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
-        .manage(TournamentService::new())
+        .plugin(tauri_plugin_dialog::init())
+        .setup(|app| {
+            // app must open even if it can't log, so problems with logging are only reported
+            let log_settings = LogSettings::load(&app.path().app_config_dir()?.join(LOG_SETTINGS_FILE_NAME));
+            let level = log_settings.as_ref().map(|settings| settings.level).unwrap_or_default();
+            app.manage(Logging::init_or_fallback(&app.path().app_data_dir()?.join("logs"), level));
+            if let Err(error) = log_settings {
+                tracing::warn!(%error, "Log settings can't be read, defaults are used");
+            }
+            tracing::info!(version = env!("CARGO_PKG_VERSION"), "App started");
+            let settings_path = ServiceConfig::settings_path(Some(&app.path().app_config_dir()?));
+            let config = ServiceConfig::load(settings_path.as_deref())?;
+            app.manage(TournamentService::new(config)?);
+            Ok(())
+        })
         .manage(AppManager {current_heroes: RwLock::new(vec![]), current_users: RwLock::new(vec![]), current_games: RwLock::new(vec![])})
         .invoke_handler(tauri::generate_handler![
             load_tournaments_list,
             load_tournament,
             load_heroes,
+            load_races,
             load_users,
             load_matches,
             load_games,
@@ -34,7 +54,23 @@ pub async fn run() {
             update_game_bargains_amount,
             update_game_result,
             update_game_outcome,
-            invoke_generation
+            load_service_config,
+            update_service_config,
+            cancel_operation,
+            load_log_level,
+            update_log_level,
+            get_diagnostics,
+            invoke_generation,
+            invoke_season_generation,
+            invoke_json_export,
+            invoke_html_export,
+            invoke_games_export,
+            invoke_validation,
+            invoke_snapshot_save,
+            invoke_snapshot_generation,
+            invoke_comparison,
+            invoke_head_to_head,
+            invoke_head_to_head_export
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,172 @@
+use std::{fs, path::{Path, PathBuf}, sync::RwLock};
+
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::{filter::LevelFilter, fmt, layer::SubscriberExt, reload, util::SubscriberInitExt, Registry};
+
+use crate::{error::Error, services::tournament::config::ServiceConfig};
+
+pub const LOG_SETTINGS_FILE_NAME: &str = "logging.json";
+const LOG_FILE_PREFIX: &str = "h5-stats";
+const LOG_FILE_SUFFIX: &str = "log";
+/// Files are rotated daily, older ones are removed.
+const LOG_FILES_KEPT: usize = 7;
+/// Lines of newest log file included into diagnostics.
+const DIAGNOSTICS_LOG_LINES: usize = 200;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumString, Display)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(value: LogLevel) -> Self {
+        match value {
+            LogLevel::Error => LevelFilter::ERROR,
+            LogLevel::Warn => LevelFilter::WARN,
+            LogLevel::Info => LevelFilter::INFO,
+            LogLevel::Debug => LevelFilter::DEBUG,
+            LogLevel::Trace => LevelFilter::TRACE
+        }
+    }
+}
+
+/// Logging options chosen by user, kept next to service settings.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogSettings {
+    pub level: LogLevel
+}
+
+impl LogSettings {
+    /// Missing file means defaults.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        if !path.exists() {
+            return Ok(LogSettings::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Installed log subscriber, level can be changed while app runs.
+pub struct Logging {
+    /// `None` when file logging is off.
+    directory: Option<PathBuf>,
+    level: RwLock<LogLevel>,
+    /// `None` when subscriber of app couldn't be installed, its level can't be changed then.
+    reload_handle: Option<reload::Handle<LevelFilter, Registry>>
+}
+
+impl Logging {
+    /// Installs global subscriber writing into daily rotated files inside of `directory`.
+    /// Fails if subscriber is installed already.
+    pub fn init(directory: &Path, level: LogLevel) -> Result<Self, Error> {
+        let appender = RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix(LOG_FILE_PREFIX)
+            .filename_suffix(LOG_FILE_SUFFIX)
+            .max_log_files(LOG_FILES_KEPT)
+            .build(directory)
+            .map_err(|error| Error::Other(format!("Can't create log file in {directory:?}: {error}")))?;
+        let (filter, reload_handle) = reload::Layer::new(LevelFilter::from(level));
+        tracing_subscriber::registry()
+            .with(filter)
+            .with(fmt::layer().with_writer(appender).with_ansi(false))
+            .try_init()
+            .map_err(|error| Error::Other(format!("Can't install logger: {error}")))?;
+        Ok(Logging { directory: Some(directory.to_path_buf()), level: RwLock::new(level), reload_handle: Some(reload_handle) })
+    }
+
+    /// Same as `init`, but app keeps running without log file if it can't be written:
+    /// messages go to stderr then, or to subscriber installed already.
+    pub fn init_or_fallback(directory: &Path, level: LogLevel) -> Self {
+        match Self::init(directory, level) {
+            Ok(logging) => logging,
+            Err(error) => {
+                let (filter, reload_handle) = reload::Layer::new(LevelFilter::from(level));
+                let installed = tracing_subscriber::registry()
+                    .with(filter)
+                    .with(fmt::layer().with_writer(std::io::stderr))
+                    .try_init()
+                    .is_ok();
+                tracing::warn!(%error, "File logging is off");
+                Logging { directory: None, level: RwLock::new(level), reload_handle: installed.then_some(reload_handle) }
+            }
+        }
+    }
+
+    pub fn level(&self) -> LogLevel {
+        *self.level.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn set_level(&self, level: LogLevel) -> Result<(), Error> {
+        self.reload_handle.as_ref()
+            .ok_or(Error::Other("Log level can't be changed, logger of app isn't installed".to_string()))?
+            .reload(LevelFilter::from(level))
+            .map_err(|error| Error::Other(format!("Can't change log level: {error}")))?;
+        *self.level.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = level;
+        Ok(())
+    }
+
+    /// `None` when file logging is off.
+    pub fn directory(&self) -> Option<&Path> {
+        self.directory.as_deref()
+    }
+
+    /// Last `lines` lines of newest log file, empty if nothing was logged yet or file logging is off.
+    pub fn tail(&self, lines: usize) -> Result<String, Error> {
+        let Some(directory) = &self.directory else {
+            return Ok(String::new());
+        };
+        let newest = fs::read_dir(directory)?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with(LOG_FILE_PREFIX))
+            .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok());
+        let Some(newest) = newest else {
+            return Ok(String::new());
+        };
+        let text = fs::read_to_string(newest.path())?;
+        let all_lines = text.lines().collect::<Vec<_>>();
+        Ok(all_lines[all_lines.len().saturating_sub(lines)..].join("\n"))
+    }
+
+    /// Text for bug reports: app version, platform, service settings without auth value and recent log.
+    pub fn diagnostics(&self, config: &ServiceConfig) -> Result<String, Error> {
+        let auth_header = config.auth_header.as_ref()
+            .map(|header| format!("{} (value hidden)", header.name))
+            .unwrap_or("none".to_string());
+        let directory = match &self.directory {
+            Some(directory) => directory.display().to_string(),
+            None => "none, file logging is off".to_string()
+        };
+        Ok(format!(
+            "Version: {}\nPlatform: {} {}\nEndpoint: {}\nTimeout: {}s, connect timeout: {}s\nRetry: {:?}\nAuth header: {auth_header}\nLog level: {}\nLog directory: {}\n\n{}",
+            env!("CARGO_PKG_VERSION"),
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            config.endpoint,
+            config.timeout_secs,
+            config.connect_timeout_secs,
+            config.retry,
+            self.level(),
+            directory,
+            self.tail(DIAGNOSTICS_LOG_LINES)?
+        ))
+    }
+}
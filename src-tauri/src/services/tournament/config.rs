@@ -0,0 +1,118 @@
+use std::{fs, path::{Path, PathBuf}, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+pub const DEFAULT_ENDPOINT: &str = "https://h5-tournaments-api-5epg.shuttle.app/";
+pub const CONFIG_FILE_NAME: &str = "service.json";
+/// Path to settings file, used instead of the one in app config directory.
+pub const CONFIG_PATH_ENV: &str = "H5_STATS_CONFIG";
+/// Overrides endpoint from settings file.
+pub const ENDPOINT_ENV: &str = "H5_STATS_API_URL";
+/// Overrides value of auth header from settings file, `Authorization` header is used if file has none.
+pub const AUTH_ENV: &str = "H5_STATS_API_AUTH";
+
+/// Delay before n-th retry is `initial_backoff_ms * 2^(n-1)`, but not more than `max_backoff_ms`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Total number of tries, 1 means no retries.
+    pub max_attempts: u32,
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy { max_attempts: 3, initial_backoff_ms: 500, max_backoff_ms: 10_000 }
+    }
+}
+
+impl RetryPolicy {
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self.initial_backoff_ms.saturating_mul(2u64.saturating_pow(attempt.saturating_sub(1)));
+        Duration::from_millis(delay.min(self.max_backoff_ms))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthHeader {
+    pub name: String,
+    pub value: String
+}
+
+/// Where and how `TournamentService` sends its requests.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServiceConfig {
+    pub endpoint: String,
+    /// Limit for whole request including reading of response.
+    pub timeout_secs: u64,
+    pub connect_timeout_secs: u64,
+    pub retry: RetryPolicy,
+    pub auth_header: Option<AuthHeader>
+}
+
+impl Default for ServiceConfig {
+    fn default() -> Self {
+        ServiceConfig {
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+            timeout_secs: 30,
+            connect_timeout_secs: 10,
+            retry: RetryPolicy::default(),
+            auth_header: None
+        }
+    }
+}
+
+impl ServiceConfig {
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into();
+        self
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
+
+    pub fn connect_timeout(&self) -> Duration {
+        Duration::from_secs(self.connect_timeout_secs)
+    }
+
+    /// Settings file from `H5_STATS_CONFIG` or the one inside of `config_dir` if variable isn't set.
+    pub fn settings_path(config_dir: Option<&Path>) -> Option<PathBuf> {
+        std::env::var_os(CONFIG_PATH_ENV)
+            .map(PathBuf::from)
+            .or(config_dir.map(|dir| dir.join(CONFIG_FILE_NAME)))
+    }
+
+    /// Reads config from settings file, missing file means defaults.
+    /// Environment variables are applied on top of file.
+    pub fn load(path: Option<&Path>) -> Result<Self, Error> {
+        let mut config = match path {
+            Some(path) if path.exists() => Self::read(path)?,
+            _ => ServiceConfig::default()
+        };
+        if let Ok(endpoint) = std::env::var(ENDPOINT_ENV) {
+            config.endpoint = endpoint;
+        }
+        if let Ok(value) = std::env::var(AUTH_ENV) {
+            let name = config.auth_header.take().map(|h| h.name).unwrap_or("Authorization".to_string());
+            config.auth_header = Some(AuthHeader { name, value });
+        }
+        Ok(config)
+    }
+
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
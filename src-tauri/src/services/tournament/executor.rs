@@ -0,0 +1,157 @@
+use std::{sync::{Arc, RwLock}, time::Instant};
+
+use graphql_client::{GraphQLQuery, QueryBody, Response};
+use reqwest::{header::{HeaderMap, HeaderName, HeaderValue}, Client, StatusCode};
+use serde::de::DeserializeOwned;
+use tracing::Instrument;
+
+use crate::error::Error;
+
+use super::config::ServiceConfig;
+
+/// Longest part of non-JSON response body kept in error.
+const MAX_ERROR_BODY_LENGTH: usize = 1000;
+
+/// Decides how request is retried and how errors in response are treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestKind {
+    /// Retried on any transport failure, partial data is accepted.
+    Query,
+    /// Retried only if it couldn't be sent at all, unless `retry_safe` says repeating it changes nothing.
+    /// Any error in response fails the request.
+    Mutation { retry_safe: bool }
+}
+
+impl RequestKind {
+    fn is_idempotent(&self) -> bool {
+        match self {
+            RequestKind::Query => true,
+            RequestKind::Mutation { retry_safe } => *retry_safe
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Connection {
+    client: Client,
+    config: Arc<ServiceConfig>
+}
+
+impl Connection {
+    fn new(config: ServiceConfig) -> Result<Self, Error> {
+        let mut headers = HeaderMap::new();
+        if let Some(auth_header) = &config.auth_header {
+            let name = HeaderName::from_bytes(auth_header.name.as_bytes())
+                .map_err(|error| Error::Other(format!("Incorrect auth header name: {error}")))?;
+            let mut value = HeaderValue::from_str(&auth_header.value)
+                .map_err(|error| Error::Other(format!("Incorrect auth header value: {error}")))?;
+            value.set_sensitive(true);
+            headers.insert(name, value);
+        }
+        let client = Client::builder()
+            .timeout(config.timeout())
+            .connect_timeout(config.connect_timeout())
+            .default_headers(headers)
+            .build()?;
+        Ok(Connection { client, config: Arc::new(config) })
+    }
+}
+
+/// Sends every `TournamentService` request, applying timeouts and retries.
+/// Connection can be replaced at runtime with `reconfigure`, requests already sent keep using the old one.
+/// Request is stopped by dropping its future, e.g. when operation it belongs to is cancelled.
+pub struct RequestExecutor {
+    connection: RwLock<Connection>
+}
+
+impl RequestExecutor {
+    pub fn new(config: ServiceConfig) -> Result<Self, Error> {
+        Ok(RequestExecutor {
+            connection: RwLock::new(Connection::new(config)?)
+        })
+    }
+
+    pub fn reconfigure(&self, config: ServiceConfig) -> Result<(), Error> {
+        let connection = Connection::new(config)?;
+        *self.connection.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = connection;
+        Ok(())
+    }
+
+    pub fn config(&self) -> Arc<ServiceConfig> {
+        self.connection().config
+    }
+
+    fn connection(&self) -> Connection {
+        self.connection.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+    }
+
+    pub async fn execute<Q: GraphQLQuery>(&self, variables: Q::Variables, kind: RequestKind) -> Result<Q::ResponseData, Error> {
+        let query = Q::build_query(variables);
+        let span = tracing::info_span!("request", operation = query.operation_name, ?kind);
+        let started = Instant::now();
+        let result = self.execute_with_retries::<Q>(&query, kind).instrument(span.clone()).await;
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+        span.in_scope(|| match &result {
+            Ok(_) => tracing::debug!(elapsed_ms, "Request finished"),
+            Err(error) => tracing::warn!(elapsed_ms, %error, "Request failed")
+        });
+        result
+    }
+
+    async fn execute_with_retries<Q: GraphQLQuery>(&self, query: &QueryBody<Q::Variables>, kind: RequestKind) -> Result<Q::ResponseData, Error> {
+        let connection = self.connection();
+        let retry = &connection.config.retry;
+        let mut attempt = 1;
+        loop {
+            let can_retry = attempt < retry.max_attempts;
+            match connection.client.post(&connection.config.endpoint).json(query).send().await {
+                // request didn't reach server, so repeating it is always safe
+                Err(error) if can_retry && error.is_connect() => tracing::info!(attempt, %error, "Connection failed, retrying"),
+                Err(error) if can_retry && kind.is_idempotent() && (error.is_timeout() || error.is_request()) => tracing::info!(attempt, %error, "Request failed, retrying"),
+                Ok(response) if can_retry && kind.is_idempotent() && is_retryable_status(response.status()) => {
+                    tracing::info!(attempt, status = response.status().as_u16(), "Retryable status, retrying");
+                },
+                Ok(response) => return read_response(query.operation_name, response, kind == RequestKind::Query).await,
+                Err(error) => return Err(error.into())
+            }
+            tokio::time::sleep(retry.backoff(attempt)).await;
+            attempt += 1;
+        }
+    }
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Turns response into query data, keeping whatever server said about failure.
+/// With `allow_partial` data is returned even if response has errors too, errors are only logged then.
+async fn read_response<T: DeserializeOwned>(operation: &str, response: reqwest::Response, allow_partial: bool) -> Result<T, Error> {
+    let status = response.status();
+    let body = response.text().await?;
+    let result = match serde_json::from_str::<Response<T>>(&body) {
+        Ok(result) => result,
+        Err(error) => {
+            if status.is_success() && serde_json::from_str::<serde_json::Value>(&body).is_ok() {
+                return Err(error.into());
+            }
+            return Err(Error::Http {
+                operation: operation.to_string(),
+                status: status.as_u16(),
+                body: body.chars().take(MAX_ERROR_BODY_LENGTH).collect()
+            });
+        }
+    };
+    let errors = result.errors.unwrap_or_default();
+    match result.data {
+        Some(data) if errors.is_empty() => Ok(data),
+        Some(data) if allow_partial => {
+            for error in &errors {
+                tracing::warn!(operation, %error, "Partial data received");
+            }
+            Ok(data)
+        },
+        _ if !errors.is_empty() => Err(Error::GraphQl { operation: operation.to_string(), errors }),
+        _ => Err(Error::IncorrectData(operation.to_string()))
+    }
+}
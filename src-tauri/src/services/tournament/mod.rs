@@ -1,3 +1,5 @@
+pub mod config;
+pub mod executor;
 pub mod payloads;
 pub mod service;
 pub mod types;
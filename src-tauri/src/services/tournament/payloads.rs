@@ -19,7 +19,7 @@ pub struct UpdateGamePayload {
 impl UpdateGamePayload {
     pub fn new(id: Uuid) -> Self {
         UpdateGamePayload {
-            id: id,
+            id,
             ..Default::default()
         }
     }
@@ -65,18 +65,18 @@ impl UpdateGamePayload {
     }
 }
 
-impl Into<update_game::Variables> for UpdateGamePayload {
-    fn into(self) -> update_game::Variables {
+impl From<UpdateGamePayload> for update_game::Variables {
+    fn from(payload: UpdateGamePayload) -> Self {
         update_game::Variables {
-            id: self.id,
-            first_player_race: self.first_player_race,
-            first_player_hero: self.first_player_hero,
-            second_player_race: self.second_player_race,
-            second_player_hero: self.second_player_hero,
-            bargains_color: self.bargains_color,
-            bargains_amount: self.bargains_amount,
-            result: self.result,
-            outcome: self.outcome,
+            id: payload.id,
+            first_player_race: payload.first_player_race,
+            first_player_hero: payload.first_player_hero,
+            second_player_race: payload.second_player_race,
+            second_player_hero: payload.second_player_hero,
+            bargains_color: payload.bargains_color,
+            bargains_amount: payload.bargains_amount,
+            result: payload.result,
+            outcome: payload.outcome,
         }
     }
 }
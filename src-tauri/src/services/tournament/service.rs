@@ -1,72 +1,109 @@
-use graphql_client::{GraphQLQuery, Response};
-use reqwest::Client;
+use std::{collections::HashMap, sync::{Arc, Mutex}};
+
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 use crate::graphql::queries::{
-    get_all_games::{self, GetAllGamesGamesAll}, get_games::{self, GetGamesGames}, get_heroes::{self, GetHeroesHeroesNewHeroesEntities}, get_matches::{self, GetMatchesMatches}, get_tournament::{self, GetTournamentTournament}, get_tournaments::{self, GetTournamentsTournamentsAll}, get_users::{self, GetUsersUsers}, update_game, GetAllGames, GetGames, GetHeroes, GetMatches, GetTournament, GetTournaments, GetUsers, UpdateGame
+    get_all_games::{self, GetAllGamesGamesAll}, get_games::{self, GetGamesGames}, get_heroes::{self, GetHeroesHeroesNewHeroesEntities}, get_matches::{self, GetMatchesMatches}, get_tournament::{self, GetTournamentTournament}, get_tournaments::{self, GetTournamentsTournamentsAll}, get_users::{self, GetUsersUsers}, GetAllGames, GetGames, GetHeroes, GetMatches, GetTournament, GetTournaments, GetUsers, UpdateGame
 };
 
-use super::{payloads::UpdateGamePayload, types::ModType};
-
-const MAIN_URL: &'static str = "https://h5-tournaments-api-5epg.shuttle.app/";
+use super::{config::ServiceConfig, executor::{RequestExecutor, RequestKind}, payloads::UpdateGamePayload, types::ModType};
 
 pub struct TournamentService {
-    client: Client,
+    executor: RequestExecutor,
+    /// Cancellation of running operations by their ids.
+    operations: Mutex<HashMap<Uuid, CancellationToken>>
+}
+
+/// Work started with `TournamentService::start_operation`, it can be cancelled by its id while this is alive.
+pub struct Operation<'a> {
+    service: &'a TournamentService,
+    id: Uuid,
+    cancellation: CancellationToken
+}
+
+impl Operation<'_> {
+    /// Token cancelled by `TournamentService::cancel_operation` of this operation only.
+    pub fn cancellation(&self) -> CancellationToken {
+        self.cancellation.clone()
+    }
+}
+
+impl Drop for Operation<'_> {
+    fn drop(&mut self) {
+        self.service.operations().remove(&self.id);
+    }
 }
 
 impl TournamentService {
-    pub fn new() -> Self {
-        TournamentService {
-            client: Client::new(),
+    pub fn new(config: ServiceConfig) -> Result<Self, crate::error::Error> {
+        Ok(TournamentService {
+            executor: RequestExecutor::new(config)?,
+            operations: Mutex::new(HashMap::new())
+        })
+    }
+
+    pub fn reconfigure(&self, config: ServiceConfig) -> Result<(), crate::error::Error> {
+        self.executor.reconfigure(config)
+    }
+
+    pub fn config(&self) -> ServiceConfig {
+        Arc::unwrap_or_clone(self.executor.config())
+    }
+
+    /// Address data is fetched from.
+    pub fn api_url(&self) -> String {
+        self.executor.config().endpoint.clone()
+    }
+
+    fn operations(&self) -> std::sync::MutexGuard<'_, HashMap<Uuid, CancellationToken>> {
+        self.operations.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Registers operation, e.g. generation, that can be cancelled by `id` until it's finished.
+    pub fn start_operation(&self, id: Uuid) -> Operation<'_> {
+        let cancellation = CancellationToken::new();
+        self.operations().insert(id, cancellation.clone());
+        Operation { service: self, id, cancellation }
+    }
+
+    /// Stops operation with `id`, other operations and requests keep running.
+    /// Nothing happens if operation is finished already.
+    pub fn cancel_operation(&self, id: Uuid) {
+        if let Some(cancellation) = self.operations().get(&id) {
+            cancellation.cancel();
         }
     }
 
     pub async fn get_all_tournaments(
         &self,
     ) -> Result<Vec<GetTournamentsTournamentsAll>, crate::error::Error> {
-        let query = GetTournaments::build_query(get_tournaments::Variables {});
-        let response = self.client.post(MAIN_URL).json(&query).send().await?;
-        let result = response
-            .json::<Response<get_tournaments::ResponseData>>()
-            .await?;
-        match result.data {
-            Some(data) => Ok(data.tournaments_all),
-            None => Err(crate::error::Error::IncorrectData("GetTournaments".to_string())),
-        }
+        let data = self.executor.execute::<GetTournaments>(get_tournaments::Variables {}, RequestKind::Query).await?;
+        Ok(data.tournaments_all)
     }
 
     pub async fn get_tournament(
         &self,
         id: Uuid,
     ) -> Result<Option<GetTournamentTournament>, crate::error::Error> {
-        let query = GetTournament::build_query(get_tournament::Variables {
+        let variables = get_tournament::Variables {
             id: Some(id),
             register_channel_id: None,
             reports_channel_id: None,
-        });
-        let response = self.client.post(MAIN_URL).json(&query).send().await?;
-        let result = response
-            .json::<Response<get_tournament::ResponseData>>()
-            .await?;
-        match result.data {
-            Some(data) => Ok(data.tournament),
-            None => Err(crate::error::Error::IncorrectData("GetTournament".to_string())),
-        }
+        };
+        let data = self.executor.execute::<GetTournament>(variables, RequestKind::Query).await?;
+        Ok(data.tournament)
     }
 
     pub async fn get_users(
         &self,
         tournament_id: Uuid,
     ) -> Result<Vec<GetUsersUsers>, crate::error::Error> {
-        let query = GetUsers::build_query(get_users::Variables {
+        let variables = get_users::Variables {
             tournament_id,
-        });
-        let response = self.client.post(MAIN_URL).json(&query).send().await?;
-        let result = response.json::<Response<get_users::ResponseData>>().await?;
-        match result.data {
-            Some(data) => Ok(data.users),
-            None => Err(crate::error::Error::IncorrectData("GetUsers".to_string())),
-        }
+        };
+        let data = self.executor.execute::<GetUsers>(variables, RequestKind::Query).await?;
+        Ok(data.users)
     }
 
     pub async fn get_matches(
@@ -74,69 +111,38 @@ impl TournamentService {
         tournament_id: Uuid,
         player: Option<Uuid>,
     ) -> Result<Vec<GetMatchesMatches>, crate::error::Error> {
-        let query = GetMatches::build_query(get_matches::Variables {
+        let variables = get_matches::Variables {
             tournament_id,
             user_id: player,
-        });
-        let response = self.client.post(MAIN_URL).json(&query).send().await?;
-        let result = response
-            .json::<Response<get_matches::ResponseData>>()
-            .await?;
-        match result.data {
-            Some(data) => Ok(data.matches),
-            None => Err(crate::error::Error::IncorrectData("GetMatches".to_string())),
-        }
+        };
+        let data = self.executor.execute::<GetMatches>(variables, RequestKind::Query).await?;
+        Ok(data.matches)
     }
 
     pub async fn get_games(&self, match_id: Uuid) -> Result<Vec<GetGamesGames>, crate::error::Error> {
-        let query = GetGames::build_query(get_games::Variables { match_id });
-        let response = self.client.post(MAIN_URL).json(&query).send().await?;
-        let result = response.json::<Response<get_games::ResponseData>>().await?;
-        match result.data {
-            Some(data) => Ok(data.games),
-            None => Err(crate::error::Error::IncorrectData("GetGames".to_string())),
-        }
+        let data = self.executor.execute::<GetGames>(get_games::Variables { match_id }, RequestKind::Query).await?;
+        Ok(data.games)
     }
 
     pub async fn update_game(&self, payload: UpdateGamePayload) -> Result<(), crate::error::Error> {
-        let query = UpdateGame::build_query(payload.into());
-        let response = self.client.post(MAIN_URL).json(&query).send().await?;
-        let result = response
-            .json::<Response<update_game::ResponseData>>()
-            .await?;
-        match result.data {
-            Some(_data) => Ok(()),
-            None => Err(crate::error::Error::IncorrectData("UpdateGame".to_string())),
-        }
+        // payload only sets values, but repeating it after timeout may overwrite changes made in between
+        self.executor.execute::<UpdateGame>(payload.into(), RequestKind::Mutation { retry_safe: false }).await?;
+        Ok(())
     }
 
     pub async fn get_heroes(&self, mod_type: ModType) -> Result<Vec<GetHeroesHeroesNewHeroesEntities>, crate::error::Error> {
-        let query = GetHeroes::build_query(get_heroes::Variables {mod_type: mod_type.into()});
-        let response = self.client.post(MAIN_URL).json(&query).send().await?;
-        let result = response
-            .json::<Response<get_heroes::ResponseData>>()
-            .await?;
-        match result.data {
-            Some(data) => Ok(data.heroes_new.heroes.entities),
-            None => Err(crate::error::Error::IncorrectData("GetHeroes".to_string())),
-        }
+        let data = self.executor.execute::<GetHeroes>(get_heroes::Variables {mod_type: mod_type.into()}, RequestKind::Query).await?;
+        Ok(data.heroes_new.heroes.entities)
     }
 
     pub async fn get_all_games(&self, tournament_id: Uuid) -> Result<Vec<GetAllGamesGamesAll>, crate::error::Error> {
-        let query = GetAllGames::build_query(get_all_games::Variables {tournament_id});
-        let response = self.client.post(MAIN_URL).json(&query).send().await?;
-        let result = response
-            .json::<Response<get_all_games::ResponseData>>()
-            .await?;
-        match result.data {
-            Some(data) => Ok(data.games_all),
-            None => Err(crate::error::Error::IncorrectData("GetAllGames".to_string())),
-        } 
+        let data = self.executor.execute::<GetAllGames>(get_all_games::Variables {tournament_id}, RequestKind::Query).await?;
+        Ok(data.games_all)
     }
 }
 
 impl Default for TournamentService {
     fn default() -> Self {
-        Self::new()
+        Self::new(ServiceConfig::default()).expect("Default service config must be valid")
     }
 }
@@ -4,49 +4,53 @@ use tokio::sync::RwLockReadGuard;
 use uuid::Uuid;
 use crate::graphql::queries::{get_games::{self, GetGamesGames}, get_heroes, get_matches::GetMatchesMatches, get_tournament::{self, GetTournamentTournament}, get_users::GetUsersUsers, update_game};
 
-#[derive(Debug, Serialize, Deserialize, EnumString, Display, Clone)]
+#[derive(Debug, Serialize, Deserialize, EnumString, Display, Clone, PartialEq, Eq)]
 #[repr(i32)]
 pub enum ModType {
     Universe = 1,
     Hrta = 2
 }
 
-impl Into<get_heroes::ModType> for ModType {
-    fn into(self) -> get_heroes::ModType {
-        match self {
+impl From<ModType> for get_heroes::ModType {
+    fn from(value: ModType) -> Self {
+        match value {
             ModType::Hrta => get_heroes::ModType::HRTA,
             ModType::Universe => get_heroes::ModType::UNIVERSE
         }
     }
 }
 
-impl Into<ModType> for get_tournament::ModType {
-    fn into(self) -> ModType {
-        match self {
-            get_tournament::ModType::HRTA => ModType::Hrta,
-            get_tournament::ModType::UNIVERSE => ModType::Universe,
-            _=> unreachable!()
+impl TryFrom<get_tournament::ModType> for ModType {
+    type Error = crate::error::Error;
+
+    fn try_from(value: get_tournament::ModType) -> Result<Self, Self::Error> {
+        match value {
+            get_tournament::ModType::HRTA => Ok(ModType::Hrta),
+            get_tournament::ModType::UNIVERSE => Ok(ModType::Universe),
+            get_tournament::ModType::Other(value) => Err(crate::error::Error::UnknownVariant { enum_name: "ModType", value })
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, EnumString, Display, Clone, PartialEq, Eq)]
 pub enum GameType {
     Rmg,
     Arena
 }
 
-impl Into<GameType> for get_tournament::GameType {
-    fn into(self) -> GameType {
-        match self {
-            get_tournament::GameType::ARENA => GameType::Arena,
-            get_tournament::GameType::RMG => GameType::Rmg,
-            _=> unreachable!()
+impl TryFrom<get_tournament::GameType> for GameType {
+    type Error = crate::error::Error;
+
+    fn try_from(value: get_tournament::GameType) -> Result<Self, Self::Error> {
+        match value {
+            get_tournament::GameType::ARENA => Ok(GameType::Arena),
+            get_tournament::GameType::RMG => Ok(GameType::Rmg),
+            get_tournament::GameType::Other(value) => Err(crate::error::Error::UnknownVariant { enum_name: "GameType", value })
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[repr(i32)]
 pub enum GameResult {
     NotSelected = 0,
@@ -54,9 +58,9 @@ pub enum GameResult {
     SecondPlayerWon = 2,
 }
 
-impl Into<update_game::GameResult> for GameResult {
-    fn into(self) -> update_game::GameResult {
-        match self {
+impl From<GameResult> for update_game::GameResult {
+    fn from(value: GameResult) -> Self {
+        match value {
             GameResult::FirstPlayerWon => update_game::GameResult::FIRST_PLAYER_WON,
             GameResult::SecondPlayerWon => update_game::GameResult::SECOND_PLAYER_WON,
             GameResult::NotSelected => update_game::GameResult::NOT_SELECTED,
@@ -64,27 +68,29 @@ impl Into<update_game::GameResult> for GameResult {
     }
 }
 
-impl From<&get_games::GameResult> for GameResult {
-    fn from(value: &get_games::GameResult) -> Self {
+impl TryFrom<&get_games::GameResult> for GameResult {
+    type Error = crate::error::Error;
+
+    fn try_from(value: &get_games::GameResult) -> Result<Self, Self::Error> {
         match value {
-            get_games::GameResult::FIRST_PLAYER_WON => GameResult::FirstPlayerWon,
-            get_games::GameResult::SECOND_PLAYER_WON => GameResult::SecondPlayerWon,
-            get_games::GameResult::NOT_SELECTED => GameResult::NotSelected,
-            _=> unreachable!()
+            get_games::GameResult::FIRST_PLAYER_WON => Ok(GameResult::FirstPlayerWon),
+            get_games::GameResult::SECOND_PLAYER_WON => Ok(GameResult::SecondPlayerWon),
+            get_games::GameResult::NOT_SELECTED => Ok(GameResult::NotSelected),
+            get_games::GameResult::Other(value) => Err(crate::error::Error::UnknownVariant { enum_name: "GameResult", value: value.clone() })
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum GameOutcome {
     FinalBattleVictory = 0,
     NeutralsVictory = 1,
     OpponentSurrender = 2,
 }
 
-impl Into<update_game::GameOutcome> for GameOutcome {
-    fn into(self) -> update_game::GameOutcome {
-        match self {
+impl From<GameOutcome> for update_game::GameOutcome {
+    fn from(value: GameOutcome) -> Self {
+        match value {
             GameOutcome::FinalBattleVictory => update_game::GameOutcome::FINAL_BATTLE_VICTORY,
             GameOutcome::NeutralsVictory => update_game::GameOutcome::NEUTRALS_VICTORY,
             GameOutcome::OpponentSurrender => update_game::GameOutcome::OPPONENT_SURRENDER,
@@ -92,27 +98,29 @@ impl Into<update_game::GameOutcome> for GameOutcome {
     }
 }
 
-impl From<&get_games::GameOutcome> for GameOutcome {
-    fn from(value: &get_games::GameOutcome) -> Self {
+impl TryFrom<&get_games::GameOutcome> for GameOutcome {
+    type Error = crate::error::Error;
+
+    fn try_from(value: &get_games::GameOutcome) -> Result<Self, Self::Error> {
         match value {
-            get_games::GameOutcome::FINAL_BATTLE_VICTORY => GameOutcome::FinalBattleVictory,
-            get_games::GameOutcome::NEUTRALS_VICTORY => GameOutcome::NeutralsVictory,
-            get_games::GameOutcome::OPPONENT_SURRENDER => GameOutcome::OpponentSurrender,
-            _=> unreachable!()
+            get_games::GameOutcome::FINAL_BATTLE_VICTORY => Ok(GameOutcome::FinalBattleVictory),
+            get_games::GameOutcome::NEUTRALS_VICTORY => Ok(GameOutcome::NeutralsVictory),
+            get_games::GameOutcome::OPPONENT_SURRENDER => Ok(GameOutcome::OpponentSurrender),
+            get_games::GameOutcome::Other(value) => Err(crate::error::Error::UnknownVariant { enum_name: "GameOutcome", value: value.clone() })
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum BargainsColor {
     NotSelected = 0,
     BargainsColorRed = 2,
     BargainsColorBlue = 3,
 }
 
-impl Into<update_game::BargainsColor> for BargainsColor {
-    fn into(self) -> update_game::BargainsColor {
-        match self {
+impl From<BargainsColor> for update_game::BargainsColor {
+    fn from(value: BargainsColor) -> Self {
+        match value {
             BargainsColor::BargainsColorBlue => update_game::BargainsColor::BARGAINS_COLOR_BLUE,
             BargainsColor::BargainsColorRed => update_game::BargainsColor::BARGAINS_COLOR_RED,
             BargainsColor::NotSelected => update_game::BargainsColor::NOT_SELECTED,
@@ -120,13 +128,15 @@ impl Into<update_game::BargainsColor> for BargainsColor {
     }
 }
 
-impl From<&get_games::BargainsColor> for BargainsColor {
-    fn from(value: &get_games::BargainsColor) -> Self {
+impl TryFrom<&get_games::BargainsColor> for BargainsColor {
+    type Error = crate::error::Error;
+
+    fn try_from(value: &get_games::BargainsColor) -> Result<Self, Self::Error> {
         match value {
-            get_games::BargainsColor::BARGAINS_COLOR_BLUE => BargainsColor::BargainsColorBlue,
-            get_games::BargainsColor::BARGAINS_COLOR_RED => BargainsColor::BargainsColorRed,
-            get_games::BargainsColor::NOT_SELECTED => BargainsColor::NotSelected,
-            _=> unreachable!()
+            get_games::BargainsColor::BARGAINS_COLOR_BLUE => Ok(BargainsColor::BargainsColorBlue),
+            get_games::BargainsColor::BARGAINS_COLOR_RED => Ok(BargainsColor::BargainsColorRed),
+            get_games::BargainsColor::NOT_SELECTED => Ok(BargainsColor::NotSelected),
+            get_games::BargainsColor::Other(value) => Err(crate::error::Error::UnknownVariant { enum_name: "BargainsColor", value: value.clone() })
         }
     }
 }
@@ -161,17 +171,19 @@ pub struct TournamentFrontendModel {
     pub with_foreign_heroes: bool
 }
 
-impl From<GetTournamentTournament> for TournamentFrontendModel {
-    fn from(value: GetTournamentTournament) -> Self {
-        TournamentFrontendModel {
+impl TryFrom<GetTournamentTournament> for TournamentFrontendModel {
+    type Error = crate::error::Error;
+
+    fn try_from(value: GetTournamentTournament) -> Result<Self, Self::Error> {
+        Ok(TournamentFrontendModel {
             id: value.id,
             name: value.name,
-            mod_type: value.mod_type.into(),
-            game_type: value.game_type.into(),
+            mod_type: value.mod_type.try_into()?,
+            game_type: value.game_type.try_into()?,
             with_bargains: value.with_bargains,
             with_bargains_color: value.with_bargains_color,
             with_foreign_heroes: value.with_foreign_heroes
-        }
+        })
     }
 }
 
@@ -201,9 +213,9 @@ impl GetMatchesMatches {
         Ok(MatchFrontendModel { 
             id: self.id, 
             first_user_id: self.first_player, 
-            first_user_nickname: first_user_nickname, 
+            first_user_nickname, 
             second_user_id: self.second_player, 
-            second_user_nickname: second_user_nickname 
+            second_user_nickname
         })
     }
 }
@@ -222,30 +234,30 @@ pub struct GameFrontendModel {
 }
 
 impl GetGamesGames {
-    pub fn into_frontend_model(&self) -> GameFrontendModel {
+    pub fn into_frontend_model(&self) -> Result<GameFrontendModel, crate::error::Error> {
         let first_player_race = self.first_player_race.unwrap_or(-1);
         let first_player_hero = self.first_player_hero.unwrap_or(-1);
         let second_player_race = self.second_player_race.unwrap_or(-1);
         let second_player_hero = self.second_player_hero.unwrap_or(-1);
         let bargains_color;
         if let Some(color) = &self.bargains_color {
-            bargains_color = Some(BargainsColor::from(color));
+            bargains_color = Some(BargainsColor::try_from(color)?);
         } else {
             bargains_color = None;
         }
         let bargains_amount = self.bargains_amount.unwrap_or(-1);
 
-        GameFrontendModel { 
+        Ok(GameFrontendModel { 
             id: self.id, 
-            first_player_race: first_player_race, 
-            first_player_hero: first_player_hero, 
-            second_player_race: second_player_race, 
-            second_player_hero: second_player_hero, 
-            bargains_color: bargains_color, 
-            bargains_amount: bargains_amount, 
-            result: GameResult::from(&self.result), 
-            outcome: GameOutcome::from(&self.outcome) 
-        }
+            first_player_race,
+            first_player_hero,
+            second_player_race,
+            second_player_hero,
+            bargains_color,
+            bargains_amount,
+            result: GameResult::try_from(&self.result)?, 
+            outcome: GameOutcome::try_from(&self.outcome)? 
+        })
     }
 }
 
@@ -0,0 +1,297 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{MockApi, FIRST_USER_ID, GAME_ID, INCOMPLETE_GAME_ID, MATCH_ID, TOURNAMENT_ID};
+use h5_stats_generator_lib::{
+    app::{commands::*, manager::AppManager},
+    error::Error,
+    generator::{commands::*, locale::Locale, output::OutputSettings, pipeline::WorkbookSettings, validation::IssueSeverity},
+    services::tournament::{
+        config::CONFIG_PATH_ENV,
+        types::{BargainsColor, GameOutcome, GameResult, ModType}
+    }
+};
+use serde_json::json;
+use tauri::{test::{mock_app, MockRuntime}, App, Manager};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+use wiremock::ResponseTemplate;
+
+fn app(api: &MockApi) -> App<MockRuntime> {
+    let app = mock_app();
+    app.manage(api.service());
+    app.manage(AppManager { current_heroes: RwLock::new(vec![]), current_users: RwLock::new(vec![]), current_games: RwLock::new(vec![]) });
+    app
+}
+
+#[tokio::test]
+async fn tournaments_are_loaded() {
+    let api = MockApi::start().await;
+    let app = app(&api);
+    let tournaments = load_tournaments_list(app.state()).await.unwrap();
+    assert_eq!(tournaments.len(), 2);
+    let tournament = load_tournament(app.state(), TOURNAMENT_ID).await.unwrap();
+    assert_eq!(tournament.name, "Spring Cup");
+    assert!(matches!(tournament.mod_type, ModType::Hrta));
+}
+
+#[tokio::test]
+async fn missing_or_malformed_tournament_fails() {
+    let api = MockApi::start().await;
+    let app = app(&api);
+    api.respond_with_json("GetTournament", json!({"data": {"tournament": null}})).await;
+    assert!(matches!(load_tournament(app.state(), TOURNAMENT_ID).await, Err(Error::Other(_))));
+
+    let api = MockApi::start().await;
+    let app = self::app(&api);
+    let mut tournament = common::fixture("GetTournament");
+    tournament["data"]["tournament"]["gameType"] = json!("DUEL");
+    api.respond_with_json("GetTournament", tournament).await;
+    let result = load_tournament(app.state(), TOURNAMENT_ID).await;
+    assert!(matches!(result, Err(Error::UnknownVariant { enum_name: "GameType", .. })), "{result:?}");
+}
+
+#[tokio::test]
+async fn heroes_users_and_matches_are_loaded() {
+    let api = MockApi::start().await;
+    let app = app(&api);
+    load_heroes(app.state(), app.state(), ModType::Universe).await.unwrap();
+    assert_eq!(api.requests("GetHeroes").await[0]["modType"], "UNIVERSE");
+    let heroes = get_heroes_of_race(app.state(), 2).await.unwrap();
+    assert_eq!(heroes.iter().map(|h| h.name.as_str()).collect::<Vec<_>>(), ["Grawl"]);
+
+    load_users(app.state(), app.state(), TOURNAMENT_ID).await.unwrap();
+    let matches = load_matches(app.state(), app.state(), TOURNAMENT_ID, Some(FIRST_USER_ID)).await.unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].first_user_nickname, "Alice");
+    assert_eq!(matches[0].second_user_nickname, "Bob");
+}
+
+#[tokio::test]
+async fn races_are_loaded_from_catalog() {
+    for mod_type in [ModType::Universe, ModType::Hrta] {
+        let races = load_races(mod_type).await.unwrap();
+        assert_eq!(races.len(), 8);
+        assert_eq!(races[0].name, "Орден порядка");
+    }
+}
+
+#[tokio::test]
+async fn matches_of_unknown_users_are_skipped() {
+    let api = MockApi::start().await;
+    let app = app(&api);
+    let matches = load_matches(app.state(), app.state(), TOURNAMENT_ID, None).await.unwrap();
+    assert!(matches.is_empty());
+}
+
+#[tokio::test]
+async fn games_are_loaded_and_selected() {
+    let api = MockApi::start().await;
+    let app = app(&api);
+    let games = load_games(app.state(), app.state(), MATCH_ID).await.unwrap();
+    assert_eq!(games, [GAME_ID, INCOMPLETE_GAME_ID]);
+    let game = select_game(app.state(), GAME_ID).await.unwrap();
+    assert_eq!(game.first_player_hero, 1);
+    assert!(matches!(game.bargains_color, Some(BargainsColor::BargainsColorRed)));
+    assert!(matches!(select_game(app.state(), Uuid::new_v4()).await, Err(Error::Other(_))));
+}
+
+#[tokio::test]
+async fn game_with_malformed_enum_fails_to_select() {
+    let api = MockApi::start().await;
+    let app = app(&api);
+    let mut games = common::fixture("GetGames");
+    games["data"]["games"][0]["outcome"] = json!("TIMEOUT");
+    api.respond_with_json("GetGames", games).await;
+    load_games(app.state(), app.state(), MATCH_ID).await.unwrap();
+    let result = select_game(app.state(), GAME_ID).await;
+    assert!(matches!(&result, Err(Error::UnknownVariant { enum_name: "GameOutcome", value }) if value == "TIMEOUT"), "{result:?}");
+    assert!(select_game(app.state(), INCOMPLETE_GAME_ID).await.is_ok());
+}
+
+#[tokio::test]
+async fn game_updates_are_sent() {
+    let api = MockApi::start().await;
+    let app = app(&api);
+    load_games(app.state(), app.state(), MATCH_ID).await.unwrap();
+    update_game_first_player_race(app.state(), GAME_ID, 4).await.unwrap();
+    update_game_first_player_hero(app.state(), app.state(), GAME_ID, 4).await.unwrap();
+    update_game_second_player_race(app.state(), GAME_ID, 3).await.unwrap();
+    update_game_second_player_hero(app.state(), app.state(), GAME_ID, 3).await.unwrap();
+    update_game_bargains_color(app.state(), GAME_ID, BargainsColor::BargainsColorBlue).await.unwrap();
+    update_game_bargains_amount(app.state(), GAME_ID, 700).await.unwrap();
+    update_game_result(app.state(), GAME_ID, GameResult::SecondPlayerWon).await.unwrap();
+    update_game_outcome(app.state(), GAME_ID, GameOutcome::NeutralsVictory).await.unwrap();
+
+    let sent = api.requests("UpdateGame").await.iter()
+        .map(|variables| {
+            assert_eq!(variables["id"], GAME_ID.to_string());
+            variables.as_object().unwrap().iter()
+                .filter(|(name, value)| *name != "id" && !value.is_null())
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(sent, [
+        vec![("firstPlayerRace".to_string(), json!(4))],
+        vec![("firstPlayerHero".to_string(), json!(4))],
+        vec![("secondPlayerRace".to_string(), json!(3))],
+        vec![("secondPlayerHero".to_string(), json!(3))],
+        vec![("bargainsColor".to_string(), json!("BARGAINS_COLOR_BLUE"))],
+        vec![("bargainsAmount".to_string(), json!(700))],
+        vec![("result".to_string(), json!("SECOND_PLAYER_WON"))],
+        vec![("outcome".to_string(), json!("NEUTRALS_VICTORY"))]
+    ]);
+
+    // heroes are also kept in loaded games
+    let game = select_game(app.state(), GAME_ID).await.unwrap();
+    assert_eq!((game.first_player_hero, game.second_player_hero), (4, 3));
+}
+
+#[tokio::test]
+async fn hero_of_not_loaded_game_is_updated() {
+    let api = MockApi::start().await;
+    let app = app(&api);
+    update_game_first_player_hero(app.state(), app.state(), GAME_ID, 2).await.unwrap();
+    update_game_second_player_hero(app.state(), app.state(), GAME_ID, 2).await.unwrap();
+    assert_eq!(api.requests("UpdateGame").await.len(), 2);
+}
+
+#[tokio::test]
+async fn failed_update_keeps_loaded_game() {
+    let api = MockApi::start().await;
+    let app = app(&api);
+    load_games(app.state(), app.state(), MATCH_ID).await.unwrap();
+    api.respond_with_json("UpdateGame", json!({"data": null, "errors": [{"message": "Game is closed"}]})).await;
+    let result = update_game_first_player_hero(app.state(), app.state(), GAME_ID, 4).await;
+    assert!(matches!(result, Err(Error::GraphQl { .. })), "{result:?}");
+    assert_eq!(select_game(app.state(), GAME_ID).await.unwrap().first_player_hero, 1);
+}
+
+#[tokio::test]
+async fn command_errors_reach_frontend_with_kind() {
+    let api = MockApi::start().await;
+    let app = app(&api);
+    api.respond_with_json("GetTournaments", json!({"data": null, "errors": [{"message": "Not authorized"}]})).await;
+    let error = load_tournaments_list(app.state()).await.unwrap_err();
+    let serialized = serde_json::to_value(&error).unwrap();
+    assert_eq!(serialized["kind"], "GraphQl");
+    assert!(serialized["message"].as_str().is_some_and(|text| text.contains("Not authorized")), "{serialized}");
+
+    api.respond_with_json("GetUsers", json!({"data": null})).await;
+    assert!(matches!(load_users(app.state(), app.state(), TOURNAMENT_ID).await, Err(Error::IncorrectData(_))));
+    api.respond_with("GetMatches", ResponseTemplate::new(502).set_body_string("Bad Gateway")).await;
+    assert!(matches!(load_matches(app.state(), app.state(), TOURNAMENT_ID, None).await, Err(Error::Http { status: 502, .. })));
+}
+
+#[tokio::test]
+async fn service_config_is_switched_and_saved() {
+    let (old_api, new_api) = (MockApi::start().await, MockApi::start().await);
+    let app = app(&old_api);
+    let settings_dir = tempfile::tempdir().unwrap();
+    let settings_path = settings_dir.path().join("service.json");
+    // only this test reads settings path, so changing environment doesn't affect others
+    unsafe { std::env::set_var(CONFIG_PATH_ENV, &settings_path) };
+
+    assert_eq!(load_service_config(app.state()).await.unwrap().endpoint, old_api.uri());
+    update_service_config(app.handle().clone(), app.state(), new_api.config()).await.unwrap();
+    assert_eq!(load_service_config(app.state()).await.unwrap().endpoint, new_api.uri());
+    load_tournaments_list(app.state()).await.unwrap();
+    assert!(old_api.requests("GetTournaments").await.is_empty());
+    assert_eq!(new_api.requests("GetTournaments").await.len(), 1);
+
+    let saved: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&settings_path).unwrap()).unwrap();
+    assert_eq!(saved["endpoint"], new_api.uri());
+}
+
+#[tokio::test]
+async fn only_cancelled_operation_is_stopped() {
+    let api = MockApi::start().await;
+    let app = app(&api);
+    let dir = tempfile::tempdir().unwrap();
+    let fixture = common::fixture("GetTournament");
+    api.respond_with("GetTournament", ResponseTemplate::new(200).set_body_json(fixture).set_delay(Duration::from_secs(30))).await;
+    let (cancelled_id, running_id) = (Uuid::new_v4(), Uuid::new_v4());
+    let output = |name: &str| OutputSettings::with_path(dir.path().join(name));
+    let (cancelled, running, tournaments, _) = tokio::join!(
+        invoke_json_export(app.state(), cancelled_id, TOURNAMENT_ID, output("cancelled.json")),
+        tokio::time::timeout(Duration::from_millis(500), invoke_json_export(app.state(), running_id, TOURNAMENT_ID, output("running.json"))),
+        async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            load_tournaments_list(app.state()).await
+        },
+        async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            cancel_operation(app.state(), cancelled_id).await.unwrap();
+        }
+    );
+    assert!(matches!(cancelled, Err(Error::Cancelled)), "{cancelled:?}");
+    // the other export is still waiting for API when it's timed out
+    assert!(running.is_err());
+    assert_eq!(tournaments.unwrap().len(), 2);
+    // finished operation can't be cancelled anymore
+    cancel_operation(app.state(), cancelled_id).await.unwrap();
+}
+
+#[tokio::test]
+async fn stats_are_generated_and_exported() {
+    let api = MockApi::start().await;
+    let app = app(&api);
+    let dir = tempfile::tempdir().unwrap();
+    let output = |name: &str| OutputSettings::with_path(dir.path().join(name));
+
+    let workbook = invoke_generation(app.handle().clone(), app.state(), Uuid::new_v4(), TOURNAMENT_ID, output("stats.xlsx"), WorkbookSettings::default()).await.unwrap();
+    assert!(workbook.exists());
+    let report = invoke_json_export(app.state(), Uuid::new_v4(), TOURNAMENT_ID, output("stats.json")).await.unwrap();
+    let report: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(report).unwrap()).unwrap();
+    assert_eq!(report["tournament"]["name"], "Spring Cup");
+    let games = invoke_games_export(app.state(), Uuid::new_v4(), TOURNAMENT_ID, output("games.csv")).await.unwrap();
+    assert!(games.games.exists() && games.rejected.exists());
+
+    let generated = OutputSettings::with_directory(dir.path(), Some("{tournament}".to_string()));
+    assert_eq!(invoke_generation(app.handle().clone(), app.state(), Uuid::new_v4(), TOURNAMENT_ID, generated, WorkbookSettings::default()).await.unwrap(), dir.path().join("Spring Cup.xlsx"));
+    let result = invoke_generation(app.handle().clone(), app.state(), Uuid::new_v4(), TOURNAMENT_ID, output("stats.xlsx"), WorkbookSettings::default()).await;
+    assert!(matches!(result, Err(Error::OutputExists(_))), "{result:?}");
+    assert_eq!(serde_json::to_value(result.unwrap_err()).unwrap()["kind"], "OutputExists");
+}
+
+#[tokio::test]
+async fn stats_are_generated_from_snapshot() {
+    let api = MockApi::start().await;
+    let app = app(&api);
+    let dir = tempfile::tempdir().unwrap();
+    let snapshot = invoke_snapshot_save(app.state(), Uuid::new_v4(), TOURNAMENT_ID, OutputSettings::with_path(dir.path().join("snapshot.json"))).await.unwrap();
+    let requests_made = api.requests("GetAllGames").await.len();
+    let workbook = invoke_snapshot_generation(app.handle().clone(), app.state(), Uuid::new_v4(), snapshot, OutputSettings::with_path(dir.path().join("stats.xlsx")), WorkbookSettings::default()).await.unwrap();
+    assert!(workbook.exists());
+    assert_eq!(api.requests("GetAllGames").await.len(), requests_made);
+}
+
+#[tokio::test]
+async fn games_are_validated() {
+    let api = MockApi::start().await;
+    let app = app(&api);
+    let issues = invoke_validation(app.state(), TOURNAMENT_ID, Locale::Ru).await.unwrap();
+    let incomplete = issues.iter().find(|issue| issue.game_id == INCOMPLETE_GAME_ID).expect("Incomplete game must be reported");
+    assert_eq!(incomplete.severity, IssueSeverity::Error);
+    assert_eq!(incomplete.players, "Alice vs Bob");
+    assert!(incomplete.description.starts_with("Игра не заполнена"), "{}", incomplete.description);
+    assert!(issues.iter().all(|issue| issue.game_id != GAME_ID));
+
+    let issues = invoke_validation(app.state(), TOURNAMENT_ID, Locale::En).await.unwrap();
+    let incomplete = issues.iter().find(|issue| issue.game_id == INCOMPLETE_GAME_ID).unwrap();
+    assert!(incomplete.description.starts_with("Game isn't filled"), "{}", incomplete.description);
+}
+
+#[tokio::test]
+async fn generation_fails_on_api_errors() {
+    let api = MockApi::start().await;
+    let app = app(&api);
+    api.respond_with_json("GetAllGames", json!({"data": null, "errors": [{"message": "Database is down"}]})).await;
+    let dir = tempfile::tempdir().unwrap();
+    let result = invoke_generation(app.handle().clone(), app.state(), Uuid::new_v4(), TOURNAMENT_ID, OutputSettings::with_path(dir.path().join("stats.xlsx")), WorkbookSettings::default()).await;
+    assert!(matches!(result, Err(Error::GraphQl { .. })), "{result:?}");
+    assert!(!dir.path().join("stats.xlsx").exists());
+    assert!(matches!(invoke_validation(app.state(), TOURNAMENT_ID, Locale::Ru).await, Err(Error::GraphQl { .. })));
+}
@@ -0,0 +1,172 @@
+//! In-process GraphQL API for integration tests.
+//! Every operation is answered with `tests/fixtures/graphql/<OperationName>.json`,
+//! fixtures are checked against `src/graphql/schema.json` when server starts.
+#![allow(dead_code)]
+
+pub mod workbook;
+
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use h5_stats_generator_lib::{
+    generator::TournamentStatsModel,
+    services::tournament::{config::{RetryPolicy, ServiceConfig}, service::TournamentService}
+};
+use serde_json::Value;
+use uuid::{uuid, Uuid};
+use wiremock::{matchers::method, Match, Mock, MockServer, Request, ResponseTemplate};
+
+pub const OPERATIONS: [&str; 8] = [
+    "GetTournaments", "GetTournament", "GetUsers", "GetMatches", "GetGames", "GetAllGames", "GetHeroes", "UpdateGame"
+];
+
+pub const TOURNAMENT_ID: Uuid = uuid!("7b0e5a52-1c1f-4a0e-9d8e-3f2a1b6c0001");
+pub const FIRST_USER_ID: Uuid = uuid!("11111111-1111-4111-8111-111111111111");
+pub const SECOND_USER_ID: Uuid = uuid!("22222222-2222-4222-8222-222222222222");
+pub const MATCH_ID: Uuid = uuid!("33333333-3333-4333-8333-333333333333");
+/// Completed game present in both `GetGames` and `GetAllGames` fixtures.
+pub const GAME_ID: Uuid = uuid!("aaaaaaaa-0000-4000-8000-000000000001");
+/// Game without second player race and hero.
+pub const INCOMPLETE_GAME_ID: Uuid = uuid!("aaaaaaaa-0000-4000-8000-000000000002");
+
+/// Fixtures are answered with lowest priority, so any response set by test wins.
+const FIXTURE_PRIORITY: u8 = u8::MAX;
+const OVERRIDE_PRIORITY: u8 = 1;
+
+/// Matches GraphQL request by `operationName` of its body.
+struct Operation(String);
+
+impl Match for Operation {
+    fn matches(&self, request: &Request) -> bool {
+        serde_json::from_slice::<Value>(&request.body)
+            .is_ok_and(|body| body["operationName"] == self.0.as_str())
+    }
+}
+
+pub fn fixture(operation: &str) -> Value {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(format!("tests/fixtures/graphql/{operation}.json"));
+    let text = fs::read_to_string(&path).unwrap_or_else(|error| panic!("Can't read fixture {path:?}: {error}"));
+    serde_json::from_str(&text).unwrap_or_else(|error| panic!("Fixture {path:?} isn't JSON: {error}"))
+}
+
+/// Model with several players, races and games with issues, used to check built workbook.
+pub fn stats_model_fixture() -> TournamentStatsModel {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/stats_model.json");
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).expect("Fixture must be valid model")
+}
+
+pub struct MockApi {
+    server: MockServer
+}
+
+impl MockApi {
+    pub async fn start() -> Self {
+        let server = MockServer::start().await;
+        let schema = Schema::load();
+        for operation in OPERATIONS {
+            let body = fixture(operation);
+            schema.check(operation, &body);
+            Mock::given(method("POST"))
+                .and(Operation(operation.to_string()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(body))
+                .with_priority(FIXTURE_PRIORITY)
+                .mount(&server)
+                .await;
+        }
+        MockApi { server }
+    }
+
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    /// Config pointing to this server, retries don't wait.
+    pub fn config(&self) -> ServiceConfig {
+        let mut config = ServiceConfig::default().with_endpoint(self.uri());
+        config.retry = RetryPolicy { max_attempts: 3, initial_backoff_ms: 1, max_backoff_ms: 1 };
+        config
+    }
+
+    pub fn service(&self) -> TournamentService {
+        TournamentService::new(self.config()).expect("Mock config must be valid")
+    }
+
+    /// Answers `operation` with `body` instead of fixture. Body isn't checked against schema.
+    pub async fn respond_with_json(&self, operation: &str, body: Value) {
+        self.respond_with(operation, ResponseTemplate::new(200).set_body_json(body)).await;
+    }
+
+    pub async fn respond_with(&self, operation: &str, response: ResponseTemplate) {
+        Mock::given(method("POST"))
+            .and(Operation(operation.to_string()))
+            .respond_with(response)
+            .with_priority(OVERRIDE_PRIORITY)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Variables of every request received for `operation`.
+    pub async fn requests(&self, operation: &str) -> Vec<Value> {
+        self.server.received_requests().await.unwrap_or_default().iter()
+            .filter_map(|request| serde_json::from_slice::<Value>(&request.body).ok())
+            .filter(|body| body["operationName"] == operation)
+            .map(|body| body["variables"].clone())
+            .collect()
+    }
+}
+
+/// Types of introspection result, used to keep fixtures in line with real API.
+struct Schema {
+    types: HashMap<String, Value>
+}
+
+impl Schema {
+    fn load() -> Self {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/graphql/schema.json");
+        let schema: Value = serde_json::from_str(&fs::read_to_string(path).expect("Schema must exist")).expect("Schema must be JSON");
+        let schema = schema.get("data").unwrap_or(&schema);
+        let types = schema["__schema"]["types"].as_array().expect("Schema must list types").iter()
+            .map(|t| (t["name"].as_str().unwrap().to_string(), t.clone()))
+            .collect();
+        Schema { types }
+    }
+
+    fn check(&self, operation: &str, body: &Value) {
+        let root = if operation == "UpdateGame" { "Mutation" } else { "Query" };
+        self.check_object(root, &body["data"], operation);
+    }
+
+    fn check_object(&self, type_name: &str, value: &Value, path: &str) {
+        let fields = self.types[type_name]["fields"].as_array().unwrap();
+        let object = value.as_object().unwrap_or_else(|| panic!("{path}: object of `{type_name}` expected"));
+        for (name, value) in object {
+            let field = fields.iter()
+                .find(|f| f["name"] == name.as_str())
+                .unwrap_or_else(|| panic!("{path}: `{type_name}` has no field `{name}`"));
+            self.check_value(&field["type"], value, &format!("{path}.{name}"));
+        }
+    }
+
+    fn check_value(&self, type_ref: &Value, value: &Value, path: &str) {
+        match type_ref["kind"].as_str().unwrap() {
+            "NON_NULL" => {
+                assert!(!value.is_null(), "{path}: null in non-null field");
+                self.check_value(&type_ref["ofType"], value, path);
+            },
+            _ if value.is_null() => {},
+            "LIST" => {
+                let items = value.as_array().unwrap_or_else(|| panic!("{path}: list expected"));
+                for (index, item) in items.iter().enumerate() {
+                    self.check_value(&type_ref["ofType"], item, &format!("{path}[{index}]"));
+                }
+            },
+            "OBJECT" => self.check_object(type_ref["name"].as_str().unwrap(), value, path),
+            "ENUM" => {
+                let type_name = type_ref["name"].as_str().unwrap();
+                let known = self.types[type_name]["enumValues"].as_array().unwrap().iter()
+                    .any(|v| v["name"] == *value);
+                assert!(known, "{path}: {value} isn't value of `{type_name}`");
+            },
+            _ => {}
+        }
+    }
+}
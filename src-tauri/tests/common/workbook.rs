@@ -0,0 +1,296 @@
+//! Readable dump of xlsx workbook used by golden tests.
+//! Every sheet becomes text with column widths, merged ranges, cells, conditional formats, notes and charts,
+//! formats are resolved from `styles.xml` into short descriptions so changed style of a cell shows up in diff.
+
+use std::{collections::HashMap, io::{Cursor, Read}};
+
+use roxmltree::{Document, Node};
+use zip::ZipArchive;
+
+pub struct SheetDump {
+    pub name: String,
+    pub text: String
+}
+
+struct Archive(ZipArchive<Cursor<Vec<u8>>>);
+
+impl Archive {
+    fn read(&mut self, path: &str) -> Option<String> {
+        let mut file = self.0.by_name(path).ok()?;
+        let mut text = String::new();
+        file.read_to_string(&mut text).expect("Workbook part must be UTF-8");
+        Some(text)
+    }
+}
+
+pub fn dump_workbook(buffer: Vec<u8>) -> Vec<SheetDump> {
+    let mut archive = Archive(ZipArchive::new(Cursor::new(buffer)).expect("Workbook must be zip"));
+    let shared_strings = archive.read("xl/sharedStrings.xml").map(|xml| read_shared_strings(&xml)).unwrap_or_default();
+    let styles = Styles::read(&archive.read("xl/styles.xml").expect("Workbook must have styles"));
+    let workbook = archive.read("xl/workbook.xml").expect("Workbook must have workbook.xml");
+    let relations = archive.read("xl/_rels/workbook.xml.rels").expect("Workbook must have relations");
+    let relations = Document::parse(&relations).unwrap();
+    let targets = relations.descendants()
+        .filter(|n| n.has_tag_name("Relationship"))
+        .map(|n| (n.attribute("Id").unwrap().to_string(), n.attribute("Target").unwrap().to_string()))
+        .collect::<HashMap<_, _>>();
+    Document::parse(&workbook).unwrap().descendants()
+        .filter(|n| n.has_tag_name("sheet"))
+        .map(|sheet| {
+            let name = sheet.attribute("name").unwrap().to_string();
+            let relation = sheet.attributes().find(|a| a.name() == "id").unwrap().value();
+            let path = format!("xl/{}", targets[relation]);
+            let xml = archive.read(&path).expect("Sheet must exist");
+            let notes = read_notes(&mut archive, &path);
+            let charts = read_charts(&mut archive, &path);
+            let text = dump_sheet(&name, &xml, &shared_strings, &styles, &notes, &charts);
+            SheetDump { name, text }
+        })
+        .collect()
+}
+
+/// Relation ids and paths of parts related to sheet or drawing at `path` with relation type ending with `kind`.
+fn related_parts(archive: &mut Archive, path: &str, kind: &str) -> Vec<(String, String)> {
+    let (dir, file) = path.rsplit_once('/').unwrap();
+    let Some(relations) = archive.read(&format!("{dir}/_rels/{file}.rels")) else {
+        return vec![];
+    };
+    Document::parse(&relations).unwrap().descendants()
+        .filter(|n| n.has_tag_name("Relationship") && n.attribute("Type").is_some_and(|t| t.ends_with(kind)))
+        .map(|n| (n.attribute("Id").unwrap().to_string(), format!("xl/{}", n.attribute("Target").unwrap().trim_start_matches("../"))))
+        .collect()
+}
+
+/// Notes of sheet at `path` by cell reference, in order they are stored.
+fn read_notes(archive: &mut Archive, path: &str) -> Vec<(String, String)> {
+    let mut notes = vec![];
+    for (_, comments_path) in related_parts(archive, path, "/comments") {
+        let xml = archive.read(&comments_path).expect("Comments must exist");
+        notes.extend(Document::parse(&xml).unwrap().descendants()
+            .filter(|n| n.has_tag_name("comment"))
+            .map(|n| (n.attribute("ref").unwrap().to_string(), text_of(n))));
+    }
+    notes
+}
+
+/// Charts of sheet at `path`, each described by its anchor cell, type, title and series ranges.
+fn read_charts(archive: &mut Archive, path: &str) -> Vec<String> {
+    let mut lines = vec![];
+    for (_, drawing_path) in related_parts(archive, path, "/drawing") {
+        let charts = related_parts(archive, &drawing_path, "/chart").into_iter()
+            .map(|(id, chart_path)| (id, archive.read(&chart_path).expect("Chart must exist")))
+            .collect::<HashMap<_, _>>();
+        let drawing = archive.read(&drawing_path).expect("Drawing must exist");
+        let drawing = Document::parse(&drawing).unwrap();
+        for anchor in drawing.descendants().filter(|n| n.has_tag_name("twoCellAnchor")) {
+            let Some(chart) = anchor.descendants().find(|n| n.has_tag_name("chart")) else {
+                continue;
+            };
+            let relation = chart.attributes().find(|a| a.name() == "id").unwrap().value();
+            let from = child(anchor, "from").unwrap();
+            let position = |tag: &str| child(from, tag).and_then(|n| n.text()).unwrap().parse::<u32>().unwrap();
+            lines.extend(describe_chart(&cell_reference(position("row"), position("col")), &charts[relation]));
+        }
+    }
+    lines
+}
+
+fn describe_chart(cell: &str, xml: &str) -> Vec<String> {
+    let document = Document::parse(xml).unwrap();
+    let title = document.descendants().find(|n| n.has_tag_name("title")).map(text_of).unwrap_or_default();
+    let plots = document.descendants()
+        .find(|n| n.has_tag_name("plotArea")).unwrap()
+        .children()
+        .filter(|n| n.tag_name().name().ends_with("Chart"))
+        .collect::<Vec<_>>();
+    let kinds = plots.iter().map(|n| n.tag_name().name().trim_end_matches("Chart")).collect::<Vec<_>>();
+    let mut lines = vec![format!("chart {cell} {} {title:?}", kinds.join("+"))];
+    for series in plots.iter().flat_map(|plot| plot.children().filter(|n| n.has_tag_name("ser"))) {
+        let reference = |tag: &str| child(series, tag)
+            .and_then(|n| n.descendants().find(|d| d.has_tag_name("f") || d.has_tag_name("v")))
+            .and_then(|n| n.text())
+            .unwrap_or("-")
+            .to_string();
+        lines.push(format!("  series {:?} categories {} values {}", reference("tx"), reference("cat"), reference("val")));
+    }
+    lines
+}
+
+/// A1 style reference of zero based cell position.
+fn cell_reference(row: u32, col: u32) -> String {
+    let mut letters = String::new();
+    let mut col = col + 1;
+    while col > 0 {
+        letters.insert(0, (b'A' + ((col - 1) % 26) as u8) as char);
+        col = (col - 1) / 26;
+    }
+    format!("{letters}{}", row + 1)
+}
+
+fn read_shared_strings(xml: &str) -> Vec<String> {
+    Document::parse(xml).unwrap().root_element().children()
+        .filter(|n| n.has_tag_name("si"))
+        .map(text_of)
+        .collect()
+}
+
+/// Text of all `<t>` elements inside of node, rich text runs are joined.
+fn text_of(node: Node) -> String {
+    node.descendants().filter(|n| n.has_tag_name("t")).filter_map(|n| n.text()).collect()
+}
+
+fn dump_sheet(name: &str, xml: &str, shared_strings: &[String], styles: &Styles, notes: &[(String, String)], charts: &[String]) -> String {
+    let document = Document::parse(xml).unwrap();
+    let mut lines = vec![format!("sheet: {name}")];
+    for column in document.descendants().filter(|n| n.has_tag_name("col")) {
+        lines.push(format!(
+            "column {}-{}: width {}",
+            column.attribute("min").unwrap(),
+            column.attribute("max").unwrap(),
+            column.attribute("width").unwrap_or("default")
+        ));
+    }
+    for merged in document.descendants().filter(|n| n.has_tag_name("mergeCell")) {
+        lines.push(format!("merged: {}", merged.attribute("ref").unwrap()));
+    }
+    for cell in document.descendants().filter(|n| n.has_tag_name("c")) {
+        let value = child(cell, "v").and_then(|v| v.text()).unwrap_or("");
+        let mut text = match cell.attribute("t") {
+            Some("s") => format!("{:?}", shared_strings[value.parse::<usize>().unwrap()]),
+            Some("inlineStr") => format!("{:?}", text_of(cell)),
+            Some("str") => format!("{value:?}"),
+            Some("b") => (value == "1").to_string(),
+            _ => value.to_string()
+        };
+        if let Some(formula) = child(cell, "f").and_then(|f| f.text()) {
+            text = format!("={formula} -> {text}");
+        }
+        let mut line = format!("{} {text}", cell.attribute("r").unwrap()).trim_end().to_string();
+        if let Some(format) = styles.describe(cell.attribute("s").map(|s| s.parse().unwrap()).unwrap_or(0)) {
+            line = format!("{line} | {format}");
+        }
+        lines.push(line);
+    }
+    // Excel 2010 extensions repeat data bars with their own ranges, only main formats are dumped
+    for formatting in document.descendants().filter(|n| n.has_tag_name("conditionalFormatting") && n.has_attribute("sqref")) {
+        for rule in formatting.children().filter(|n| n.has_tag_name("cfRule")) {
+            let values = rule.descendants()
+                .filter(|n| n.has_tag_name("cfvo"))
+                .map(|n| format!("{} {}", n.attribute("type").unwrap(), n.attribute("val").unwrap_or("")).trim_end().to_string())
+                .collect::<Vec<_>>();
+            let colors = rule.descendants()
+                .filter(|n| n.has_tag_name("color"))
+                .filter_map(|n| n.attribute("rgb"))
+                .collect::<Vec<_>>();
+            lines.push(format!(
+                "conditional {}: {} {} | {}",
+                formatting.attribute("sqref").unwrap(),
+                rule.attribute("type").unwrap(),
+                values.join(", "),
+                colors.join(" ")
+            ));
+        }
+    }
+    for (cell, text) in notes {
+        lines.push(format!("note {cell} {text:?}"));
+    }
+    lines.extend(charts.iter().cloned());
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, tag: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|n| n.has_tag_name(tag))
+}
+
+/// Cell formats of workbook, each described by its non-default parts.
+struct Styles {
+    formats: Vec<Option<String>>
+}
+
+impl Styles {
+    fn read(xml: &str) -> Self {
+        let document = Document::parse(xml).unwrap();
+        let section = |tag: &str| -> Vec<Node> {
+            document.descendants().find(|n| n.has_tag_name(tag)).map(|n| n.children().filter(|c| c.is_element()).collect()).unwrap_or_default()
+        };
+        let number_formats = section("numFmts").iter()
+            .map(|n| (n.attribute("numFmtId").unwrap().to_string(), n.attribute("formatCode").unwrap().to_string()))
+            .collect::<HashMap<_, _>>();
+        let fonts = section("fonts").iter().map(|n| describe_font(*n)).collect::<Vec<_>>();
+        let fills = section("fills").iter().map(|n| describe_fill(*n)).collect::<Vec<_>>();
+        let borders = section("borders").iter().map(|n| describe_border(*n)).collect::<Vec<_>>();
+        let formats = section("cellXfs").iter()
+            .map(|xf| {
+                let index = |name: &str| xf.attribute(name).map(|v| v.parse::<usize>().unwrap()).unwrap_or(0);
+                let mut parts = vec![];
+                match xf.attribute("numFmtId").unwrap_or("0") {
+                    "0" => {},
+                    id => parts.push(format!("number {}", number_formats.get(id).cloned().unwrap_or(format!("#{id}"))))
+                }
+                parts.extend(fonts[index("fontId")].clone());
+                parts.extend(fills[index("fillId")].clone());
+                parts.extend(borders[index("borderId")].clone());
+                if let Some(alignment) = child(*xf, "alignment") {
+                    parts.push(format!("align({})", attributes(alignment)));
+                }
+                (!parts.is_empty()).then(|| parts.join(", "))
+            })
+            .collect();
+        Styles { formats }
+    }
+
+    fn describe(&self, index: usize) -> Option<String> {
+        self.formats[index].clone()
+    }
+}
+
+/// `name=value` pairs of all attributes in document order.
+fn attributes(node: Node) -> String {
+    node.attributes().map(|a| format!("{}={}", a.name(), a.value())).collect::<Vec<_>>().join(" ")
+}
+
+/// Font different from default Calibri 11.
+fn describe_font(font: Node) -> Option<String> {
+    let parts = font.children()
+        .filter(|n| n.is_element())
+        .filter_map(|n| match (n.tag_name().name(), n.attribute("val")) {
+            ("name", Some("Calibri")) | ("sz", Some("11")) | ("family", _) | ("scheme", _) => None,
+            ("color", _) if n.attribute("theme") == Some("1") => None,
+            (tag, Some(value)) => Some(format!("{tag}={value}")),
+            (tag, None) if n.attributes().len() > 0 => Some(format!("{tag}({})", attributes(n))),
+            (tag, None) => Some(tag.to_string())
+        })
+        .collect::<Vec<_>>();
+    (!parts.is_empty()).then(|| format!("font({})", parts.join(" ")))
+}
+
+fn describe_fill(fill: Node) -> Option<String> {
+    let pattern = child(fill, "patternFill")?;
+    match pattern.attribute("patternType") {
+        None | Some("none") | Some("gray125") => None,
+        Some(pattern_type) => {
+            let color = child(pattern, "fgColor").map(|c| format!(" {}", attributes(c))).unwrap_or_default();
+            Some(format!("fill({pattern_type}{color})"))
+        }
+    }
+}
+
+/// Sides with style, all four sides with same style are written once.
+fn describe_border(border: Node) -> Option<String> {
+    let sides = border.children()
+        .filter(|n| n.is_element())
+        .filter_map(|side| side.attribute("style").map(|style| {
+            let color = child(side, "color")
+                .filter(|c| c.attribute("auto") != Some("1"))
+                .map(|c| format!(" {}", attributes(c)))
+                .unwrap_or_default();
+            (side.tag_name().name(), format!("{style}{color}"))
+        }))
+        .collect::<Vec<_>>();
+    match sides.as_slice() {
+        [] => None,
+        [(_, first), ..] if sides.len() == 4 && sides.iter().all(|(_, style)| style == first) => Some(format!("border({first})")),
+        _ => Some(format!("border({})", sides.iter().map(|(side, style)| format!("{side}={style}")).collect::<Vec<_>>().join(" ")))
+    }
+}
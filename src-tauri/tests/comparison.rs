@@ -0,0 +1,116 @@
+mod common;
+
+use common::{stats_model_fixture, workbook::dump_workbook};
+use h5_stats_generator_lib::{
+    generator::{
+        comparison::{ComparisonSettings, Proportion, Shift, TournamentComparison}, locale::Locale, pipeline::build_comparison_workbook
+    },
+    graphql::queries::get_tournament
+};
+
+fn shift(previous: (u32, u32), current: (u32, u32)) -> Shift {
+    Shift {
+        previous: Proportion { count: previous.0, total: previous.1 },
+        current: Proportion { count: current.0, total: current.1 }
+    }
+}
+
+#[test]
+fn shifts_are_tested_for_significance() {
+    let fall = shift((60, 100), (40, 100));
+    assert!((fall.delta().unwrap() + 0.2).abs() < 1e-9);
+    assert!((fall.z_score().unwrap() + 2.828).abs() < 0.001);
+    assert!(fall.is_significant());
+
+    assert!(!shift((52, 100), (50, 100)).is_significant());
+    // one game can't be significant whatever it changes
+    assert!(!shift((0, 1), (1, 1)).is_significant());
+    assert_eq!(shift((0, 0), (1, 2)).delta(), None);
+    assert!(!shift((0, 0), (1, 2)).is_significant());
+}
+
+#[test]
+fn tournament_compared_to_itself_has_no_changes() {
+    let model = stats_model_fixture();
+    let comparison = TournamentComparison::compute(&model, &model, &ComparisonSettings::default()).unwrap();
+    assert_eq!(comparison.races.len(), model.races.len());
+    assert_eq!(comparison.match_ups.len(), model.races.len() * (model.races.len() - 1));
+    assert!(comparison.races.iter().all(|r| r.pickrate.delta().is_none_or(|delta| delta == 0.0) && !r.winrate.is_significant()));
+    assert!(comparison.match_ups.iter().all(|m| !m.winrate.is_significant()));
+    assert!(comparison.entered_heroes.is_empty());
+    assert!(comparison.dropped_heroes.is_empty());
+    assert!(comparison.bargains.iter().all(|b| b.delta().is_none_or(|delta| delta == 0.0)));
+}
+
+#[test]
+fn heroes_of_new_tournament_enter_the_meta() {
+    let current = stats_model_fixture();
+    let mut previous = current.clone();
+    previous.games.clear();
+    let settings = ComparisonSettings::default();
+    let comparison = TournamentComparison::compute(&previous, &current, &settings).unwrap();
+
+    let meta_heroes = comparison.entered_heroes.len();
+    assert!(meta_heroes > 0);
+    for race in &current.races {
+        let race_heroes = comparison.entered_heroes.iter().filter(|h| h.race == race.id).collect::<Vec<_>>();
+        // shares of one race are of the same picks, so they never sum above 100%
+        assert!(race_heroes.iter().map(|h| h.pickrate.current.count).sum::<u32>() <= race_heroes.first().map_or(0, |h| h.pickrate.current.total));
+        assert!(race_heroes.iter().all(|h| h.pickrate.current.ratio().unwrap() >= settings.meta_pick_ratio));
+    }
+    assert!(comparison.dropped_heroes.is_empty());
+    assert!(comparison.races.iter().all(|r| r.pickrate.delta().is_none()));
+
+    let reversed = TournamentComparison::compute(&current, &previous, &settings).unwrap();
+    assert_eq!(reversed.dropped_heroes.len(), meta_heroes);
+}
+
+#[test]
+fn both_heroes_of_mirror_are_picks_of_race() {
+    let mut previous = stats_model_fixture();
+    let mut current = previous.clone();
+    let race = current.races[0].id;
+    let mut race_heroes = current.heroes.iter().filter(|h| h.race == race).map(|h| h.id);
+    let (first_hero, second_hero) = (race_heroes.next().unwrap(), race_heroes.next().unwrap());
+    let mut mirror = current.games[0].clone();
+    mirror.first_player_race = race;
+    mirror.first_player_hero = first_hero;
+    mirror.second_player_race = race;
+    mirror.second_player_hero = second_hero;
+    previous.games.clear();
+    current.games = vec![mirror];
+
+    let comparison = TournamentComparison::compute(&previous, &current, &ComparisonSettings::default()).unwrap();
+    assert_eq!(comparison.entered_heroes.len(), 2);
+    for hero in [first_hero, second_hero] {
+        let entered = comparison.entered_heroes.iter().find(|h| h.hero == hero).expect("Mirror hero must enter the meta");
+        assert_eq!(entered.pickrate.current.ratio(), Some(0.5));
+    }
+}
+
+#[test]
+fn tournaments_of_different_mods_are_not_compared() {
+    let previous = stats_model_fixture();
+    let mut current = previous.clone();
+    current.tournament.as_mut().unwrap().mod_type = get_tournament::ModType::HRTA;
+    assert!(TournamentComparison::compute(&previous, &current, &ComparisonSettings::default()).is_err());
+}
+
+#[test]
+fn comparison_workbook_has_sheet_per_aggregation() {
+    let current = stats_model_fixture();
+    let mut previous = current.clone();
+    previous.tournament.as_mut().unwrap().name = "Previous Cup".to_string();
+    // second half of games shifts the meta
+    previous.games.truncate(current.games.len() / 2);
+    let mut workbook = build_comparison_workbook(&previous, &current, &ComparisonSettings::default(), Locale::En).unwrap();
+    let sheets = dump_workbook(workbook.save_to_buffer().unwrap());
+    assert_eq!(
+        sheets.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(),
+        ["Race changes", "Match-up changes", "Hero meta", "Bargain changes"]
+    );
+    assert!(sheets[0].text.contains("\"Previous Cup → Fixture Cup\""), "{}", sheets[0].text);
+    assert!(sheets[0].text.contains("\"Pick rate change\""));
+    assert!(sheets[1].text.contains("\"VS\""));
+    assert!(sheets[2].text.contains("\"Entered the meta\""));
+}
@@ -0,0 +1,33 @@
+mod common;
+
+use std::fs;
+
+use common::stats_model_fixture;
+use h5_stats_generator_lib::generator::{games_export::export_games, TournamentStatsModel};
+use uuid::Uuid;
+
+const GAMES_HEADER: &str = "match_id,first_player,second_player,first_player_race,first_player_hero,second_player_race,second_player_hero,bargains_amount,bargains_color,result,outcome";
+
+#[test]
+fn both_files_have_headers_without_games() {
+    let dir = tempfile::tempdir().unwrap();
+    let model = TournamentStatsModel { games: vec![], rejected_games: vec![], ..stats_model_fixture() };
+    let paths = export_games(&model, &dir.path().join("games.csv"), false).unwrap();
+    assert_eq!(fs::read_to_string(paths.games).unwrap().trim_end(), GAMES_HEADER);
+    assert_eq!(fs::read_to_string(paths.rejected).unwrap().trim_end(), "game_id,match_id,reason");
+}
+
+#[test]
+fn game_of_unknown_match_is_exported_without_players() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut model = stats_model_fixture();
+    let unknown_match = Uuid::new_v4();
+    model.games[0].match_id = unknown_match;
+    let paths = export_games(&model, &dir.path().join("games.csv"), false).unwrap();
+
+    let text = fs::read_to_string(paths.games).unwrap();
+    let lines = text.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], GAMES_HEADER);
+    assert_eq!(lines.len(), model.games.len() + 1);
+    assert!(lines[1].starts_with(&format!("{unknown_match},,,")), "{}", lines[1]);
+}
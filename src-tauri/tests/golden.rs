@@ -0,0 +1,67 @@
+//! Compares workbook built from `tests/fixtures/stats_model.json` with dumps in `tests/golden/workbook`.
+//! Run with `BLESS=1` to rewrite dumps after intended change of workbook layout.
+
+mod common;
+
+use std::{fs, path::{Path, PathBuf}};
+
+use common::{stats_model_fixture, workbook::dump_workbook};
+use h5_stats_generator_lib::generator::{pipeline::{build_workbook, WorkbookSettings}, progress::ProgressReporter};
+use similar::TextDiff;
+
+const BLESS_ENV: &str = "BLESS";
+
+fn golden_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden/workbook")
+}
+
+fn golden_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = fs::read_dir(dir).map(|entries| {
+        entries.map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|e| e == "txt"))
+            .collect::<Vec<_>>()
+    }).unwrap_or_default();
+    files.sort();
+    files
+}
+
+#[test]
+fn workbook_matches_golden_files() {
+    let mut workbook = build_workbook(&stats_model_fixture(), &WorkbookSettings::default(), &ProgressReporter::default()).unwrap();
+    let sheets = dump_workbook(workbook.save_to_buffer().unwrap());
+    let dir = golden_dir();
+    let expected_files = sheets.iter().enumerate()
+        .map(|(index, sheet)| (dir.join(format!("sheet{:02}.txt", index + 1)), sheet))
+        .collect::<Vec<_>>();
+
+    if std::env::var_os(BLESS_ENV).is_some() {
+        fs::create_dir_all(&dir).unwrap();
+        for path in golden_files(&dir) {
+            fs::remove_file(path).unwrap();
+        }
+        for (path, sheet) in &expected_files {
+            fs::write(path, &sheet.text).unwrap();
+        }
+        return;
+    }
+
+    let mut failures = vec![];
+    for (path, sheet) in &expected_files {
+        match fs::read_to_string(path) {
+            Ok(golden) if golden == sheet.text => {},
+            Ok(golden) => failures.push(format!(
+                "Sheet `{}` differs from {}:\n{}",
+                sheet.name,
+                path.display(),
+                TextDiff::from_lines(&golden, &sheet.text).unified_diff().context_radius(2).header("golden", "actual")
+            )),
+            Err(_) => failures.push(format!("Sheet `{}` has no golden file {}", sheet.name, path.display()))
+        }
+    }
+    for path in golden_files(&dir) {
+        if !expected_files.iter().any(|(expected, _)| *expected == path) {
+            failures.push(format!("Golden file {} has no matching sheet", path.display()));
+        }
+    }
+    assert!(failures.is_empty(), "{}\nRun with {BLESS_ENV}=1 if changes are intended.", failures.join("\n"));
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use common::{stats_model_fixture, workbook::dump_workbook};
+use h5_stats_generator_lib::{
+    generator::{
+        head_to_head::HeadToHead, head_to_head_report::{build_head_to_head_sheet, render_head_to_head_page}, locale::Locale,
+        pipeline::build_head_to_head, winrate::{WinrateSettings, WinrateWriter}, TournamentStatsModel
+    },
+    graphql::queries::get_tournament
+};
+use rust_xlsxwriter::workbook::Workbook;
+use uuid::Uuid;
+
+/// Players of the first match.
+fn players(model: &TournamentStatsModel) -> (Uuid, Uuid) {
+    let first_match = model.matches.first().unwrap();
+    (first_match.first_player, first_match.second_player)
+}
+
+#[test]
+fn sides_of_head_to_head_mirror_each_other() {
+    let model = stats_model_fixture();
+    let (first, second) = players(&model);
+    let head_to_head = build_head_to_head(vec![model.clone()], first, second, Locale::Ru).unwrap();
+    assert!(!head_to_head.games.is_empty());
+    assert!(head_to_head.skipped.is_empty());
+    assert_eq!(head_to_head.first.results.wins, head_to_head.second.results.losses);
+    assert_eq!(head_to_head.first.results.games(), head_to_head.games.len() as u32);
+    assert_eq!(head_to_head.games.iter().filter(|g| g.first_won).count() as u32, head_to_head.first.results.wins);
+    for player in [&head_to_head.first, &head_to_head.second] {
+        assert_eq!(player.races.iter().map(|r| r.results.games()).sum::<u32>(), player.results.games());
+        assert_eq!(player.heroes.iter().map(|h| h.results.games()).sum::<u32>(), player.results.games());
+        assert!(player.in_opponent_main_races.len() <= 3);
+        assert!(player.bargains.games >= player.bargains_vs_opponent.games);
+    }
+
+    // the same games replayed in the next cup are added to history
+    let mut next = model.clone();
+    next.tournament.as_mut().unwrap().id = Uuid::new_v4();
+    for next_match in &mut next.matches {
+        let id = Uuid::new_v4();
+        for game in next.games.iter_mut().filter(|g| g.match_id == next_match.id) {
+            game.match_id = id;
+        }
+        next_match.id = id;
+    }
+    // games of other mod are left out, but the cup is named for the user
+    let mut other_mod = next.clone();
+    let other_tournament = other_mod.tournament.as_mut().unwrap();
+    other_tournament.id = Uuid::new_v4();
+    other_tournament.name = "Hrta Cup".to_string();
+    other_tournament.mod_type = get_tournament::ModType::HRTA;
+    let both = build_head_to_head(vec![model, other_mod, next], first, second, Locale::Ru).unwrap();
+    assert_eq!(both.games.len(), 2 * head_to_head.games.len());
+    assert_eq!(both.skipped, ["Hrta Cup"]);
+}
+
+#[test]
+fn head_to_head_needs_two_known_players() {
+    let model = stats_model_fixture();
+    let (first, _) = players(&model);
+    assert!(build_head_to_head(vec![model.clone()], first, first, Locale::Ru).is_err());
+    assert!(build_head_to_head(vec![model], first, Uuid::new_v4(), Locale::Ru).is_err());
+}
+
+fn fixture_head_to_head(locale: Locale) -> HeadToHead {
+    let mut model = stats_model_fixture();
+    let (first, second) = players(&model);
+    model.users.iter_mut().find(|u| u.id == first).unwrap().nickname = "<Alpha & co>".to_string();
+    build_head_to_head(vec![model], first, second, locale).unwrap()
+}
+
+#[test]
+fn head_to_head_is_rendered_as_escaped_page() {
+    let head_to_head = fixture_head_to_head(Locale::En);
+    let page = render_head_to_head_page(&head_to_head, Locale::En.messages());
+    assert!(page.starts_with("<!DOCTYPE html>"));
+    assert!(page.contains(&format!("<title>&lt;Alpha &amp; co&gt; vs {}</title>", head_to_head.second.nickname)));
+    assert!(!page.contains("<Alpha"));
+    assert!(page.contains("<h3>Races against opponent</h3>"));
+    assert_eq!(page.matches("class=\"win\"").count() as u32, head_to_head.first.results.wins);
+}
+
+#[test]
+fn head_to_head_sheet_has_score_and_tables_of_both_players() {
+    let head_to_head = fixture_head_to_head(Locale::Ru);
+    let messages = Locale::Ru.messages();
+    let mut workbook = Workbook::new();
+    build_head_to_head_sheet(&head_to_head, messages, &WinrateWriter::new(&WinrateSettings::default(), messages), &mut workbook).unwrap();
+    let sheets = dump_workbook(workbook.save_to_buffer().unwrap());
+    assert_eq!(sheets[0].name, "Личные встречи");
+    let results = &head_to_head.first.results;
+    let score = format!("\"<Alpha & co> {}:{} {}\"", results.wins, results.losses, head_to_head.second.nickname);
+    assert!(sheets[0].text.contains(&score), "{}", sheets[0].text);
+    assert_eq!(sheets[0].text.matches("\"Расы против соперника\"").count(), 2);
+    assert_eq!(sheets[0].text.matches("\"Торги\"").count(), 2);
+}
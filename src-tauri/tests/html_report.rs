@@ -0,0 +1,56 @@
+mod common;
+
+use common::stats_model_fixture;
+use h5_stats_generator_lib::generator::{
+    locale::Locale, pipeline::{build_html_report, WorkbookSettings}, winrate::WinrateSettings
+};
+
+fn attribute_values<'a>(page: &'a str, attribute: &str) -> Vec<&'a str> {
+    page.split(attribute).skip(1).map(|rest| &rest[..rest.find('"').unwrap()]).collect()
+}
+
+#[test]
+fn every_link_of_page_leads_to_its_section() {
+    let mut model = stats_model_fixture();
+    model.users[0].nickname = "<Alpha & co>".to_string();
+    let page = build_html_report(&model, &WorkbookSettings::default().with_locale(Locale::En)).unwrap();
+
+    let sections = attribute_values(&page, "<section id=\"");
+    assert_eq!(sections.len(), 2 + model.races.len() + model.users.len());
+    assert_eq!(sections[0], "index");
+    for link in attribute_values(&page, "href=\"#") {
+        assert!(sections.contains(&link), "No section for link {link}");
+    }
+    assert!(page.contains(&format!("<a href=\"#player-{}\">&lt;Alpha &amp; co&gt;</a>", model.users[0].id)));
+    assert!(!page.contains("<Alpha"));
+    assert!(page.contains("<table class=\"data\">"));
+    assert!(page.contains("<h3>Match-up winrates</h3>\n<input class=\"filter\""));
+    assert!(page.contains("<h3>Games by match-up</h3>\n<input class=\"filter\""));
+}
+
+#[test]
+fn race_section_has_winrates_only_against_other_races() {
+    let model = stats_model_fixture();
+    let page = build_html_report(&model, &WorkbookSettings::default().with_locale(Locale::En)).unwrap();
+    for race in &model.races {
+        let section = page.split(&format!("<section id=\"race-{}\">", race.id)).nth(1).unwrap();
+        let section = &section[..section.find("</section>").unwrap()];
+        if section.contains("<table") {
+            assert!(!section.contains(&format!("<th>Winrate vs {}</th>", race.name)), "{section}");
+            assert_eq!(section.matches("<th>Winrate vs ").count(), model.races.len() - 1);
+        }
+    }
+}
+
+#[test]
+fn winrates_of_small_samples_are_greyed_out_instead_of_colored() {
+    let model = stats_model_fixture();
+    let colored = build_html_report(&model, &WorkbookSettings::default()).unwrap();
+    assert!(!colored.contains("<td class=\"muted\">"));
+
+    let settings = WorkbookSettings { winrates: WinrateSettings { min_games: Some(u32::MAX), ..Default::default() }, ..Default::default() };
+    let greyed = build_html_report(&model, &settings).unwrap();
+    assert!(greyed.contains("<td class=\"muted\">"));
+    // only game counts are colored then
+    assert!(greyed.matches("style=\"background").count() < colored.matches("style=\"background").count());
+}
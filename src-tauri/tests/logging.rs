@@ -0,0 +1,42 @@
+mod common;
+
+use common::MockApi;
+use h5_stats_generator_lib::{
+    logging::{LogLevel, Logging},
+    services::tournament::{config::AuthHeader, service::TournamentService}
+};
+
+/// Subscriber is global, so everything about it is checked in one test.
+#[tokio::test]
+async fn requests_are_logged_and_diagnostics_hide_auth_value() {
+    let dir = tempfile::tempdir().unwrap();
+    let logging = Logging::init(dir.path(), LogLevel::Warn).unwrap();
+    let api = MockApi::start().await;
+    let mut config = api.config();
+    config.auth_header = Some(AuthHeader { name: "X-Api-Key".to_string(), value: "secret-key".to_string() });
+    let service = TournamentService::new(config.clone()).unwrap();
+
+    service.get_all_tournaments().await.unwrap();
+    assert!(!logging.tail(100).unwrap().contains("Request finished"), "debug messages must be filtered out on warn level");
+
+    logging.set_level(LogLevel::Debug).unwrap();
+    service.get_all_tournaments().await.unwrap();
+    let log = logging.tail(100).unwrap();
+    assert!(log.contains("Request finished"), "{log}");
+    assert!(log.contains("operation=\"GetTournaments\""), "{log}");
+    assert!(log.contains("elapsed_ms="), "{log}");
+
+    let diagnostics = logging.diagnostics(&config).unwrap();
+    assert!(diagnostics.contains("Log level: debug"), "{diagnostics}");
+    assert!(diagnostics.contains("X-Api-Key (value hidden)"), "{diagnostics}");
+    assert!(diagnostics.contains("Request finished"), "{diagnostics}");
+    assert!(!diagnostics.contains("secret-key"), "{diagnostics}");
+
+    // subscriber is installed already, so the second one can only work without file
+    let fallback = Logging::init_or_fallback(&dir.path().join("other"), LogLevel::Info);
+    assert_eq!(fallback.directory(), None);
+    assert_eq!(fallback.tail(100).unwrap(), "");
+    assert!(fallback.set_level(LogLevel::Debug).is_err());
+    let diagnostics = fallback.diagnostics(&config).unwrap();
+    assert!(diagnostics.contains("Log directory: none, file logging is off"), "{diagnostics}");
+}
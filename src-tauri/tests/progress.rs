@@ -0,0 +1,86 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use common::{stats_model_fixture, workbook::dump_workbook, MockApi, TOURNAMENT_ID};
+use h5_stats_generator_lib::{
+    error::Error,
+    generator::{
+        pipeline::{build_workbook, load_stats_model, WorkbookSettings},
+        progress::{FetchStage, GenerationProgress, ProgressReporter}, validation::validate
+    }
+};
+use tokio_util::sync::CancellationToken;
+
+/// Reporter collecting everything reported, `on_report` is called with every event after it's collected.
+fn collecting_reporter(
+    cancellation: CancellationToken,
+    on_report: impl Fn(&GenerationProgress) + Send + Sync + 'static
+) -> (ProgressReporter, Arc<Mutex<Vec<GenerationProgress>>>) {
+    let events = Arc::new(Mutex::new(vec![]));
+    let sink_events = events.clone();
+    let reporter = ProgressReporter::new(move |progress| {
+        on_report(&progress);
+        sink_events.lock().unwrap().push(progress);
+    }, cancellation);
+    (reporter, events)
+}
+
+#[tokio::test]
+async fn fetch_stages_are_reported() {
+    let api = MockApi::start().await;
+    let (reporter, events) = collecting_reporter(CancellationToken::new(), |_| {});
+    load_stats_model(&api.service(), TOURNAMENT_ID, &reporter).await.unwrap();
+    let stages = events.lock().unwrap().iter()
+        .filter_map(|event| match event {
+            GenerationProgress::Fetching { stage } => Some(*stage),
+            _ => None
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(stages, [FetchStage::Tournament, FetchStage::Users, FetchStage::Matches, FetchStage::Games, FetchStage::Heroes]);
+}
+
+#[test]
+fn every_sheet_and_player_is_reported() {
+    let model = stats_model_fixture();
+    let (reporter, events) = collecting_reporter(CancellationToken::new(), |_| {});
+    let mut workbook = build_workbook(&model, &WorkbookSettings::default(), &reporter).unwrap();
+    let sheets = dump_workbook(workbook.save_to_buffer().unwrap());
+    let events = events.lock().unwrap();
+
+    let built = events.iter()
+        .filter_map(|event| match event {
+            GenerationProgress::SheetBuilt { name, built, total } => Some((name.as_str(), *built, *total)),
+            _ => None
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(built.iter().map(|(name, ..)| *name).collect::<Vec<_>>(), sheets.iter().map(|s| s.name.as_str()).collect::<Vec<_>>());
+    assert!(built.iter().enumerate().all(|(index, (_, built, total))| *built == index as u32 + 1 && *total == sheets.len() as u32));
+
+    let players = events.iter()
+        .filter_map(|event| match event {
+            GenerationProgress::PlayerProcessed { nickname, processed, total } => Some((nickname.as_str(), *processed, *total)),
+            _ => None
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(players.len(), model.users.len());
+    assert_eq!(players.last().map(|(_, processed, total)| (*processed, *total)), Some((6, 6)));
+
+    let warnings = events.iter().filter(|event| matches!(event, GenerationProgress::Warning { .. })).count();
+    assert_eq!(warnings, validate(&model).len());
+}
+
+#[test]
+fn generation_stops_when_cancelled() {
+    let cancellation = CancellationToken::new();
+    let token = cancellation.clone();
+    let (reporter, events) = collecting_reporter(cancellation, move |event| {
+        if matches!(event, GenerationProgress::SheetBuilt { built: 2, .. }) {
+            token.cancel();
+        }
+    });
+    let result = build_workbook(&stats_model_fixture(), &WorkbookSettings::default(), &reporter);
+    assert!(matches!(result, Err(Error::Cancelled)), "{:?}", result.err());
+    let built = events.lock().unwrap().iter().filter(|event| matches!(event, GenerationProgress::SheetBuilt { .. })).count();
+    assert_eq!(built, 2);
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use common::stats_model_fixture;
+use h5_stats_generator_lib::generator::{
+    rating::{elo_expected_score, Glicko2Rating, RatingSettings, Ratings},
+    stats::TournamentStats
+};
+
+fn assert_close(actual: f64, expected: f64, precision: f64) {
+    assert!((actual - expected).abs() < precision, "{actual} isn't close to {expected}");
+}
+
+#[test]
+fn glicko2_matches_reference_example() {
+    // example from Glickman's description of Glicko-2
+    let player = Glicko2Rating { rating: 1500.0, deviation: 200.0, volatility: 0.06 };
+    let opponent = |rating, deviation| Glicko2Rating { rating, deviation, volatility: 0.06 };
+    let updated = player.updated(&[(opponent(1400.0, 30.0), 1.0), (opponent(1550.0, 100.0), 0.0), (opponent(1700.0, 300.0), 0.0)], 0.5);
+    assert_close(updated.rating, 1464.06, 0.01);
+    assert_close(updated.deviation, 151.52, 0.01);
+    assert_close(updated.volatility, 0.05999, 0.00001);
+
+    let idle = player.updated(&[], 0.5);
+    assert_eq!(idle.rating, player.rating);
+    assert!(idle.deviation > player.deviation);
+}
+
+#[test]
+fn ratings_are_replayed_over_counted_games() {
+    let model = stats_model_fixture();
+    let stats = TournamentStats::compute(&model).unwrap();
+    let settings = RatingSettings::default();
+    let ratings = Ratings::compute(&[&model], &settings).unwrap();
+    assert_eq!(ratings.players.len(), model.users.len());
+    assert!(ratings.players.windows(2).all(|pair| pair[0].glicko.rating >= pair[1].glicko.rating));
+
+    // Elo is zero-sum
+    let elo_sum = ratings.players.iter().map(|p| p.elo).sum::<f64>();
+    assert_close(elo_sum, settings.elo_initial * model.users.len() as f64, 0.000001);
+
+    for player in &ratings.players {
+        let player_stats = stats.players.iter().find(|p| p.user == player.user).unwrap();
+        assert_eq!(player.games(), player_stats.games.len() as u32, "{}", player.nickname);
+        assert_eq!(player.history.iter().map(|c| c.wins).sum::<u32>(), player.wins);
+        if let Some(last) = player.history.last() {
+            assert_eq!(last.elo, player.elo);
+            assert_eq!(last.glicko, player.glicko);
+        }
+    }
+}
+
+#[test]
+fn ratings_continue_over_several_tournaments() {
+    let model = stats_model_fixture();
+    let settings = RatingSettings::default();
+    let once = Ratings::compute(&[&model], &settings).unwrap();
+    let twice = Ratings::compute(&[&model, &model], &settings).unwrap();
+    assert_eq!(twice.players.len(), once.players.len());
+    for player in &twice.players {
+        let single = once.player(player.user).unwrap();
+        assert_eq!(player.games(), single.games() * 2);
+        assert_eq!(player.history.len(), single.history.len() * 2);
+        if single.games() > 0 {
+            assert!(player.glicko.deviation < single.glicko.deviation, "{}", player.nickname);
+        }
+    }
+    assert_close(elo_expected_score(1500.0, 1500.0), 0.5, f64::EPSILON);
+}
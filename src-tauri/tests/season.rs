@@ -0,0 +1,87 @@
+mod common;
+
+use common::{stats_model_fixture, workbook::dump_workbook};
+use h5_stats_generator_lib::{
+    generator::{
+        pipeline::{build_season_workbook, WorkbookSettings}, progress::ProgressReporter, season::{Season, SeasonFilter},
+        validation::validate, TournamentStatsModel
+    },
+    graphql::queries::get_tournament,
+    services::tournament::types::{GameType, ModType}
+};
+use uuid::Uuid;
+
+fn tournament(name: &str, with_bargains: bool, mod_type: get_tournament::ModType) -> TournamentStatsModel {
+    let mut model = stats_model_fixture();
+    let tournament = model.tournament.as_mut().unwrap();
+    tournament.id = Uuid::new_v4();
+    tournament.name = name.to_string();
+    tournament.with_bargains = with_bargains;
+    tournament.mod_type = mod_type;
+    model
+}
+
+fn universe_rmg() -> SeasonFilter {
+    SeasonFilter { mod_type: ModType::Universe, game_type: GameType::Rmg }
+}
+
+#[test]
+fn tournaments_of_other_types_are_skipped() {
+    let first = stats_model_fixture();
+    let mut second = tournament("Second Cup", false, get_tournament::ModType::UNIVERSE);
+    second.users[0].nickname = "Alpha Renamed".to_string();
+    let models = vec![first.clone(), tournament("Hrta Cup", true, get_tournament::ModType::HRTA), second.clone()];
+
+    let season = Season::merge(models, &universe_rmg()).unwrap();
+    assert_eq!(season.tournaments.len(), 2);
+    assert_eq!(season.skipped, ["Hrta Cup"]);
+    let tournament = season.model.tournament.as_ref().unwrap();
+    assert_eq!(tournament.name, "Fixture Cup, Second Cup");
+    assert!(!tournament.with_bargains);
+
+    // the same players took part in both tournaments
+    assert_eq!(season.model.users.len(), first.users.len());
+    assert_eq!(season.model.user_name(first.users[0].id).unwrap(), "Alpha Renamed");
+    assert_eq!(season.model.games.len(), 2 * first.games.len());
+    assert_eq!(season.model.matches.len(), 2 * first.matches.len());
+
+    let stats = season.stats().unwrap();
+    // unexpected bargains are reported only for tournament without them
+    assert_eq!(stats.issues.len(), validate(&first).len() + validate(&second).len());
+    assert!(stats.race_bargains.is_empty());
+    assert_eq!(stats.breakdown.iter().map(|b| b.name.as_str()).collect::<Vec<_>>(), ["Fixture Cup", "Second Cup"]);
+    for race in &stats.races {
+        let tournament_games = stats.breakdown.iter()
+            .map(|b| b.race(race.race).unwrap().games())
+            .sum::<u32>();
+        assert_eq!(race.games(), tournament_games);
+    }
+}
+
+#[test]
+fn season_without_matching_tournaments_fails() {
+    let filter = SeasonFilter { mod_type: ModType::Universe, game_type: GameType::Arena };
+    assert!(Season::merge(vec![stats_model_fixture()], &filter).is_err());
+}
+
+#[test]
+fn breakdown_columns_follow_totals() {
+    let models = vec![stats_model_fixture(), tournament("Second Cup", true, get_tournament::ModType::UNIVERSE)];
+    let season = Season::merge(models, &universe_rmg()).unwrap();
+    let mut workbook = build_season_workbook(&season, &WorkbookSettings::default(), &ProgressReporter::default()).unwrap();
+    let sheets = dump_workbook(workbook.save_to_buffer().unwrap());
+
+    let pairs = &sheets[0].text;
+    let cell = |reference: &str| pairs.lines()
+        .find(|line| line.starts_with(&format!("{reference} ")))
+        .unwrap_or_else(|| panic!("No cell {reference} in sheet:\n{pairs}"));
+    // eight races take columns B:Q, totals are in R
+    assert!(cell("R1").contains("\"Всего игр\""));
+    assert!(cell("S1").contains("\"Fixture Cup\""));
+    assert!(cell("T1").contains("\"Second Cup\""));
+    assert!(cell("C12").contains("\"Fixture Cup\""));
+    assert!(cell("D12").contains("\"Second Cup\""));
+    assert!(pairs.contains("chart V1 "), "{pairs}");
+    assert!(pairs.contains("conditional S3:T10: dataBar"), "{pairs}");
+    assert!(pairs.contains("conditional B13:B20 C13:D20: colorScale"), "{pairs}");
+}
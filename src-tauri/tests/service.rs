@@ -0,0 +1,266 @@
+mod common;
+
+use std::time::Duration;
+
+use common::{MockApi, FIRST_USER_ID, GAME_ID, INCOMPLETE_GAME_ID, MATCH_ID, OPERATIONS, TOURNAMENT_ID};
+use h5_stats_generator_lib::{
+    error::Error,
+    generator::{pipeline::load_stats_model, progress::ProgressReporter, types::GameEntry},
+    services::tournament::{
+        payloads::UpdateGamePayload,
+        service::TournamentService,
+        types::{GameResult, GameType, ModType, TournamentFrontendModel}
+    }
+};
+use serde_json::json;
+use uuid::Uuid;
+use wiremock::ResponseTemplate;
+
+/// Calls service method sending `operation`, its data is dropped.
+async fn call(service: &TournamentService, operation: &str) -> Result<(), Error> {
+    match operation {
+        "GetTournaments" => service.get_all_tournaments().await.map(drop),
+        "GetTournament" => service.get_tournament(TOURNAMENT_ID).await.map(drop),
+        "GetUsers" => service.get_users(TOURNAMENT_ID).await.map(drop),
+        "GetMatches" => service.get_matches(TOURNAMENT_ID, None).await.map(drop),
+        "GetGames" => service.get_games(MATCH_ID).await.map(drop),
+        "GetAllGames" => service.get_all_games(TOURNAMENT_ID).await.map(drop),
+        "GetHeroes" => service.get_heroes(ModType::Hrta).await.map(drop),
+        "UpdateGame" => service.update_game(UpdateGamePayload::new(GAME_ID).with_bargains_amount(1)).await,
+        _ => unreachable!("Unknown operation {operation}")
+    }
+}
+
+#[tokio::test]
+async fn every_operation_succeeds_with_fixtures() {
+    let api = MockApi::start().await;
+    let service = api.service();
+    for operation in OPERATIONS {
+        call(&service, operation).await.unwrap_or_else(|error| panic!("{operation} failed: {error}"));
+        assert_eq!(api.requests(operation).await.len(), 1, "{operation} must be sent once");
+    }
+}
+
+#[tokio::test]
+async fn tournaments_are_loaded() {
+    let api = MockApi::start().await;
+    let service = api.service();
+    let tournaments = service.get_all_tournaments().await.unwrap();
+    assert_eq!(tournaments.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(), ["Spring Cup", "Winter Cup"]);
+
+    let tournament = service.get_tournament(TOURNAMENT_ID).await.unwrap().expect("Tournament must be found");
+    let tournament = TournamentFrontendModel::try_from(tournament).unwrap();
+    assert_eq!(tournament.id, TOURNAMENT_ID);
+    assert!(matches!(tournament.mod_type, ModType::Hrta));
+    assert!(matches!(tournament.game_type, GameType::Rmg));
+    assert_eq!(api.requests("GetTournament").await[0]["id"], TOURNAMENT_ID.to_string());
+}
+
+#[tokio::test]
+async fn missing_tournament_is_none() {
+    let api = MockApi::start().await;
+    api.respond_with_json("GetTournament", json!({"data": {"tournament": null}})).await;
+    assert!(api.service().get_tournament(TOURNAMENT_ID).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn users_matches_and_heroes_are_loaded() {
+    let api = MockApi::start().await;
+    let service = api.service();
+    let users = service.get_users(TOURNAMENT_ID).await.unwrap();
+    assert_eq!(users.iter().map(|u| u.nickname.as_str()).collect::<Vec<_>>(), ["Alice", "Bob"]);
+
+    let matches = service.get_matches(TOURNAMENT_ID, Some(FIRST_USER_ID)).await.unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].first_player, FIRST_USER_ID);
+    assert_eq!(api.requests("GetMatches").await[0]["userId"], FIRST_USER_ID.to_string());
+
+    let heroes = service.get_heroes(ModType::Hrta).await.unwrap();
+    assert_eq!(heroes.len(), 4);
+    assert_eq!(api.requests("GetHeroes").await[0]["modType"], "HRTA");
+}
+
+#[tokio::test]
+async fn games_are_loaded() {
+    let api = MockApi::start().await;
+    let service = api.service();
+    let games = service.get_games(MATCH_ID).await.unwrap();
+    let game = games[0].into_frontend_model().unwrap();
+    assert_eq!(game.id, GAME_ID);
+    assert_eq!(game.result, GameResult::FirstPlayerWon);
+    assert_eq!(game.bargains_amount, 1500);
+    let incomplete_game = games[1].into_frontend_model().unwrap();
+    assert_eq!(incomplete_game.second_player_race, -1);
+
+    let all_games = service.get_all_games(TOURNAMENT_ID).await.unwrap();
+    assert_eq!(all_games.len(), 3);
+    let entries = all_games.into_iter().map(GameEntry::try_from).collect::<Vec<_>>();
+    assert!(entries[0].is_ok());
+    assert!(matches!(&entries[1], Err(Error::NoGameField { game_id, .. }) if *game_id == INCOMPLETE_GAME_ID));
+    assert!(entries[2].is_ok());
+}
+
+#[tokio::test]
+async fn update_sends_only_changed_fields() {
+    let api = MockApi::start().await;
+    let payload = UpdateGamePayload::new(GAME_ID)
+        .with_second_player_hero(4)
+        .with_result(GameResult::SecondPlayerWon);
+    api.service().update_game(payload).await.unwrap();
+    let variables = &api.requests("UpdateGame").await[0];
+    assert_eq!(variables["id"], GAME_ID.to_string());
+    assert_eq!(variables["secondPlayerHero"], 4);
+    assert_eq!(variables["result"], "SECOND_PLAYER_WON");
+    assert!(variables["firstPlayerHero"].is_null());
+    assert!(variables["outcome"].is_null());
+}
+
+#[tokio::test]
+async fn null_data_is_reported_for_every_operation() {
+    for operation in OPERATIONS {
+        let api = MockApi::start().await;
+        api.respond_with_json(operation, json!({"data": null})).await;
+        let result = call(&api.service(), operation).await;
+        assert!(matches!(&result, Err(Error::IncorrectData(name)) if name == operation), "{operation}: {result:?}");
+    }
+}
+
+#[tokio::test]
+async fn graphql_errors_are_reported_for_every_operation() {
+    for operation in OPERATIONS {
+        let api = MockApi::start().await;
+        api.respond_with_json(operation, json!({
+            "data": null,
+            "errors": [{"message": "Tournament is locked", "path": ["tournament"], "extensions": {"code": "LOCKED"}}]
+        })).await;
+        match call(&api.service(), operation).await {
+            Err(error @ Error::GraphQl { .. }) => {
+                let text = error.to_string();
+                assert!(text.contains(operation) && text.contains("Tournament is locked") && text.contains("LOCKED"), "{text}");
+            },
+            result => panic!("{operation}: GraphQL error expected, got {result:?}")
+        }
+    }
+}
+
+#[tokio::test]
+async fn partial_data_is_accepted_only_for_queries() {
+    let api = MockApi::start().await;
+    let service = api.service();
+    let mut users = common::fixture("GetUsers");
+    users["errors"] = json!([{"message": "Some users are hidden"}]);
+    api.respond_with_json("GetUsers", users).await;
+    assert_eq!(service.get_users(TOURNAMENT_ID).await.unwrap().len(), 2);
+
+    let mut update = common::fixture("UpdateGame");
+    update["errors"] = json!([{"message": "Game is closed"}]);
+    api.respond_with_json("UpdateGame", update).await;
+    let result = call(&service, "UpdateGame").await;
+    assert!(matches!(result, Err(Error::GraphQl { .. })), "{result:?}");
+}
+
+#[tokio::test]
+async fn malformed_enums_are_reported() {
+    let api = MockApi::start().await;
+    let service = api.service();
+
+    let mut tournament = common::fixture("GetTournament");
+    tournament["data"]["tournament"]["modType"] = json!("HOTA");
+    api.respond_with_json("GetTournament", tournament).await;
+    let tournament = service.get_tournament(TOURNAMENT_ID).await.unwrap().unwrap();
+    let result = TournamentFrontendModel::try_from(tournament);
+    assert!(matches!(&result, Err(Error::UnknownVariant { enum_name: "ModType", value }) if value == "HOTA"), "{result:?}");
+
+    let mut games = common::fixture("GetGames");
+    games["data"]["games"][0]["result"] = json!("DRAW");
+    games["data"]["games"][1]["bargainsColor"] = json!("BARGAINS_COLOR_GREEN");
+    api.respond_with_json("GetGames", games).await;
+    let games = service.get_games(MATCH_ID).await.unwrap();
+    assert!(matches!(games[0].into_frontend_model(), Err(Error::UnknownVariant { enum_name: "GameResult", .. })));
+    assert!(matches!(games[1].into_frontend_model(), Err(Error::UnknownVariant { enum_name: "BargainsColor", .. })));
+
+    let mut all_games = common::fixture("GetAllGames");
+    all_games["data"]["gamesAll"][0]["outcome"] = json!("TIMEOUT");
+    api.respond_with_json("GetAllGames", all_games).await;
+    let game = service.get_all_games(TOURNAMENT_ID).await.unwrap().remove(0);
+    assert!(matches!(GameEntry::try_from(game), Err(Error::UnknownVariant { enum_name: "GameOutcome", .. })));
+}
+
+#[tokio::test]
+async fn games_with_malformed_enums_are_rejected_from_stats() {
+    let api = MockApi::start().await;
+    let mut all_games = common::fixture("GetAllGames");
+    all_games["data"]["gamesAll"][0]["result"] = json!("DRAW");
+    api.respond_with_json("GetAllGames", all_games).await;
+    let model = load_stats_model(&api.service(), TOURNAMENT_ID, &ProgressReporter::default()).await.unwrap();
+    assert_eq!(model.games.len(), 1);
+    let rejected = model.rejected_games.iter().map(|g| g.game_id).collect::<Vec<_>>();
+    assert_eq!(rejected, [GAME_ID, INCOMPLETE_GAME_ID]);
+    assert!(model.rejected_games[0].reason.contains("DRAW"));
+}
+
+#[tokio::test]
+async fn malformed_tournament_stops_stats_loading() {
+    let api = MockApi::start().await;
+    let mut tournament = common::fixture("GetTournament");
+    tournament["data"]["tournament"]["modType"] = json!("HOTA");
+    api.respond_with_json("GetTournament", tournament).await;
+    let result = load_stats_model(&api.service(), TOURNAMENT_ID, &ProgressReporter::default()).await;
+    assert!(matches!(result, Err(Error::UnknownVariant { enum_name: "ModType", .. })));
+}
+
+#[tokio::test]
+async fn server_errors_are_retried_for_queries() {
+    let api = MockApi::start().await;
+    api.respond_with("GetUsers", ResponseTemplate::new(503).set_body_string("Service Unavailable")).await;
+    let result = api.service().get_users(TOURNAMENT_ID).await;
+    assert!(matches!(&result, Err(Error::Http { status: 503, body, .. }) if body == "Service Unavailable"), "{result:?}");
+    assert_eq!(api.requests("GetUsers").await.len(), 3);
+}
+
+#[tokio::test]
+async fn mutation_is_not_retried_after_server_error() {
+    let api = MockApi::start().await;
+    api.respond_with("UpdateGame", ResponseTemplate::new(500).set_body_string("Internal Server Error")).await;
+    let result = call(&api.service(), "UpdateGame").await;
+    assert!(matches!(result, Err(Error::Http { status: 500, .. })), "{result:?}");
+    assert_eq!(api.requests("UpdateGame").await.len(), 1);
+}
+
+#[tokio::test]
+async fn non_json_response_is_reported_with_body() {
+    let api = MockApi::start().await;
+    api.respond_with("GetTournaments", ResponseTemplate::new(200).set_body_string("<html>Maintenance</html>")).await;
+    let result = api.service().get_all_tournaments().await;
+    assert!(matches!(&result, Err(Error::Http { status: 200, body, .. }) if body.contains("Maintenance")), "{result:?}");
+}
+
+#[tokio::test]
+async fn requests_of_cancelled_operation_are_stopped() {
+    let api = MockApi::start().await;
+    let service = api.service();
+    let fixture = common::fixture("GetTournaments");
+    api.respond_with("GetTournaments", ResponseTemplate::new(200).set_body_json(fixture).set_delay(Duration::from_secs(30))).await;
+    let (cancelled_id, other_id) = (Uuid::new_v4(), Uuid::new_v4());
+    let (cancelled, other) = (service.start_operation(cancelled_id), service.start_operation(other_id));
+    let progress = ProgressReporter::cancelled_by(cancelled.cancellation());
+    let (result, _) = tokio::join!(progress.cancellable(service.get_all_tournaments()), async {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        service.cancel_operation(cancelled_id);
+    });
+    assert!(matches!(result, Err(Error::Cancelled)), "{result:?}");
+    assert!(!other.cancellation().is_cancelled());
+    // requests outside of cancelled operation work as usual
+    assert!(service.get_users(TOURNAMENT_ID).await.is_ok());
+}
+
+#[tokio::test]
+async fn reconfigured_service_uses_new_endpoint() {
+    let (old_api, new_api) = (MockApi::start().await, MockApi::start().await);
+    let service = old_api.service();
+    service.reconfigure(new_api.config()).unwrap();
+    service.get_all_tournaments().await.unwrap();
+    assert_eq!(service.api_url(), new_api.uri());
+    assert!(old_api.requests("GetTournaments").await.is_empty());
+    assert_eq!(new_api.requests("GetTournaments").await.len(), 1);
+}
@@ -0,0 +1,27 @@
+mod common;
+
+use common::stats_model_fixture;
+use h5_stats_generator_lib::generator::stats::TournamentStats;
+
+#[test]
+fn pickrates_of_race_heroes_sum_to_one_with_mirrors() {
+    let mut model = stats_model_fixture();
+    let race = model.races[0].id;
+    let mut race_heroes = model.heroes.iter().filter(|h| h.race == race).map(|h| h.id);
+    let (first_hero, second_hero) = (race_heroes.next().unwrap(), race_heroes.next().unwrap());
+    let mut mirror = model.games[0].clone();
+    mirror.first_player_race = race;
+    mirror.first_player_hero = first_hero;
+    mirror.second_player_race = race;
+    mirror.second_player_hero = second_hero;
+    model.games.push(mirror);
+
+    let stats = TournamentStats::compute(&model).unwrap();
+    assert!(stats.race(race).unwrap().mirrors > 0);
+    for race_heroes in stats.race_heroes.iter().filter(|r| r.total_picks > 0) {
+        let sum = race_heroes.heroes.iter().map(|hero| race_heroes.pick_ratio(hero)).sum::<f64>();
+        assert!((sum - 1.0).abs() < 1e-9, "Pickrates of race {} sum to {sum}", race_heroes.race);
+    }
+    let mirror_heroes = stats.race_heroes(race).unwrap();
+    assert!([first_hero, second_hero].iter().all(|hero| mirror_heroes.heroes.iter().any(|h| h.hero == *hero)));
+}
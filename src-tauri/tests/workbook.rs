@@ -0,0 +1,148 @@
+mod common;
+
+use common::{stats_model_fixture, workbook::dump_workbook};
+use h5_stats_generator_lib::generator::{
+    heatmap::{Heatmap, HeatmapSettings, HeatmapTable}, locale::Locale, pair::build_pair_stats, pipeline::{build_workbook, WorkbookSettings}, progress::ProgressReporter,
+    stats::TournamentStats, winrate::{WinrateSettings, WinrateWriter}, RaceInfo, TournamentStatsModel
+};
+use rust_xlsxwriter::workbook::Workbook;
+
+fn cell<'a>(sheet: &'a str, reference: &str) -> &'a str {
+    sheet.lines()
+        .find(|line| line.starts_with(&format!("{reference} ")))
+        .unwrap_or_else(|| panic!("No cell {reference} in sheet:\n{sheet}"))
+}
+
+#[test]
+fn tables_are_laid_out_from_races_count() {
+    let mut model = stats_model_fixture();
+    // ids don't start from 1 and aren't sequential, last race has no games
+    model.races = vec![
+        RaceInfo { id: 3, name: "Некрополис".to_string() },
+        RaceInfo { id: 7, name: "Северные кланы".to_string() },
+        RaceInfo { id: 2, name: "Инферно".to_string() },
+        RaceInfo { id: 42, name: "Пустая раса".to_string() }
+    ];
+    let mut workbook = build_workbook(&model, &WorkbookSettings::default(), &ProgressReporter::default()).unwrap();
+    let sheets = dump_workbook(workbook.save_to_buffer().unwrap());
+    assert_eq!(
+        sheets.iter().take(5).map(|s| s.name.as_str()).collect::<Vec<_>>(),
+        ["Общая статистика по расам", "Некрополис", "Северные кланы", "Инферно", "Пустая раса"]
+    );
+
+    let pairs = &sheets[0].text;
+    // wins/losses: two columns per race, total games right after them
+    assert!(cell(pairs, "B1").contains("\"Некрополис\""));
+    assert!(cell(pairs, "H1").contains("\"Пустая раса\""));
+    assert!(cell(pairs, "J1").contains("\"Всего игр\""));
+    assert!(cell(pairs, "A3").contains("\"Некрополис\""));
+    assert!(cell(pairs, "A6").contains("\"Пустая раса\""));
+    // totals and match-up tables follow each other
+    assert!(cell(pairs, "A8").contains("\"Общий винрейт\""));
+    assert!(cell(pairs, "A12").contains("\"Пустая раса\""));
+    assert!(cell(pairs, "D14").contains("\"Число игр по матчапам\""));
+    assert!(cell(pairs, "E16").contains("\"Пустая раса\""));
+    assert!(cell(pairs, "D22").contains("\"Винрейты матчапов\""));
+    assert!(cell(pairs, "A28").contains("\"Пустая раса\""));
+    assert!(!pairs.contains("\"Лига теней\""));
+    // winrates are numbers, race without games has blank cells instead of NaN
+    assert!(cell(pairs, "B9").contains(" | number 0.000%"), "{}", cell(pairs, "B9"));
+    assert!(cell(pairs, "B12").starts_with("B12 | number 0.000%"), "{}", cell(pairs, "B12"));
+    assert!(sheets.iter().all(|sheet| !sheet.text.contains("NaN")));
+
+    // heroes table starts right after bargains table
+    let race = &sheets[1].text;
+    assert!(cell(race, "A7").contains("\"Общий средний торг\""));
+    assert!(cell(race, "E11").contains("\"Общая статистика использования героев\""));
+}
+
+#[test]
+fn charts_and_heatmap_reference_tables() {
+    let mut model = stats_model_fixture();
+    model.races = vec![
+        RaceInfo { id: 3, name: "Некрополис".to_string() },
+        RaceInfo { id: 7, name: "Северные кланы".to_string() },
+        RaceInfo { id: 2, name: "Инферно".to_string() },
+        RaceInfo { id: 42, name: "Пустая раса".to_string() }
+    ];
+    let mut workbook = build_workbook(&model, &WorkbookSettings::default(), &ProgressReporter::default()).unwrap();
+    let sheets = dump_workbook(workbook.save_to_buffer().unwrap());
+
+    let pairs = &sheets[0].text;
+    assert!(pairs.contains("conditional B25:E28: colorScale num 0, num 0.5, num 1"), "{pairs}");
+    assert!(pairs.contains("chart L1 bar+line \"Игры и винрейт рас\""), "{pairs}");
+    assert!(pairs.contains("values 'Общая статистика по расам'!$J$3:$J$6"));
+    assert!(pairs.contains("values 'Общая статистика по расам'!$B$9:$B$12"));
+
+    // race without picked heroes has nothing to draw
+    assert!(sheets[1].text.contains("chart M12 pie \"Процент выбора героев\""), "{}", sheets[1].text);
+    assert!(!sheets[4].text.contains("chart "));
+
+    let player = sheets.iter().find(|s| s.name == "Alpha").unwrap();
+    assert!(cell(&player.text, "I2").contains("\"Винрейт после игры\""));
+    assert!(cell(&player.text, "I3").contains(" | number 0.000%"), "{}", cell(&player.text, "I3"));
+    assert!(player.text.contains("values Alpha!$I$3:$I$4"), "{}", player.text);
+}
+
+#[test]
+fn heatmaps_are_configured_per_table() {
+    let heatmaps = HeatmapSettings::all(Heatmap::Off).with_table(HeatmapTable::MatchUpWinrates, Heatmap::DataBar);
+    let settings = WorkbookSettings { heatmaps, ..Default::default() };
+    let mut workbook = build_workbook(&stats_model_fixture(), &settings, &ProgressReporter::default()).unwrap();
+    let sheets = dump_workbook(workbook.save_to_buffer().unwrap());
+
+    let conditionals = sheets.iter()
+        .flat_map(|sheet| sheet.text.lines().filter(|line| line.starts_with("conditional ")))
+        .collect::<Vec<_>>();
+    assert_eq!(conditionals, ["conditional B37:I44: dataBar num 0, num 1 | FF63BE7B"]);
+    // most and least played cells aren't painted by hand anymore
+    assert!(sheets.iter().take(9).all(|sheet| !sheet.text.contains("fill(solid rgb=FF008000)")));
+}
+
+#[test]
+fn workbook_is_localized() {
+    let mut workbook = build_workbook(&stats_model_fixture(), &WorkbookSettings::default().with_locale(Locale::En), &ProgressReporter::default()).unwrap();
+    let sheets = dump_workbook(workbook.save_to_buffer().unwrap());
+    let names = sheets.iter().map(|s| s.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names[..3], ["Race stats", "Haven", "Inferno"]);
+    assert_eq!(names.last(), Some(&"Game issues"));
+    // fixture players and heroes have latin names, so nothing russian may be left
+    for sheet in &sheets {
+        assert!(!sheet.text.chars().any(|c| matches!(c, 'А'..='я' | 'Ё' | 'ё')), "Untranslated text in sheet {}:\n{}", sheet.name, sheet.text);
+    }
+    let pairs = &sheets[0].text;
+    assert!(cell(pairs, "B1").contains("\"Haven\""));
+    assert!(cell(pairs, "B2").contains("\"Wins\""));
+}
+
+#[test]
+fn winrates_of_small_samples_are_marked() {
+    let settings = WorkbookSettings { winrates: WinrateSettings { show_intervals: true, min_games: Some(5) }, ..Default::default() };
+    let mut workbook = build_workbook(&stats_model_fixture(), &settings, &ProgressReporter::default()).unwrap();
+    let pairs = &dump_workbook(workbook.save_to_buffer().unwrap())[0].text;
+    // total winrate of race with 2 games and of race with 6 games
+    assert!(cell(pairs, "B17").contains("color(rgb=FF808080)"), "{}", cell(pairs, "B17"));
+    assert!(cell(pairs, "note B17").contains("игр: 2\\nМеньше 5 игр"), "{}", cell(pairs, "note B17"));
+    assert!(!cell(pairs, "B13").contains("color(rgb=FF808080)"), "{}", cell(pairs, "B13"));
+    assert!(!cell(pairs, "note B13").contains("Меньше"));
+
+    let settings = WorkbookSettings { winrates: WinrateSettings { show_intervals: false, min_games: None }, ..Default::default() };
+    let mut workbook = build_workbook(&stats_model_fixture(), &settings, &ProgressReporter::default()).unwrap();
+    assert!(dump_workbook(workbook.save_to_buffer().unwrap()).iter().all(|sheet| !sheet.text.contains("\nnote ")));
+}
+
+#[test]
+fn pair_sheet_needs_races() {
+    let model = TournamentStatsModel { races: vec![], ..stats_model_fixture() };
+    let stats = TournamentStats::compute(&model).unwrap();
+    let messages = Locale::Ru.messages();
+    let result = build_pair_stats(
+        &model,
+        &stats,
+        messages,
+        &WinrateWriter::new(&WinrateSettings::default(), messages),
+        &HeatmapSettings::default(),
+        &mut Workbook::new()
+    );
+    assert!(result.is_err());
+}
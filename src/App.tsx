@@ -1,14 +1,16 @@
 import { invoke } from "@tauri-apps/api/core";
 import { Select } from "antd";
 import { useEffect, useState } from "react";
-import { Tournament } from "./types/tournament";
+import { Race, Tournament } from "./types/tournament";
 import useTournamentsStore from "./stores/tournament";
 import MatchesList from "./components/matchesList";
 import TournamentCore from "./components/tournamentCore";
 import { useShallow } from "zustand/shallow";
-import { Route, Routes } from "react-router";
+import { Link, Route, Routes } from "react-router";
 import MatchFocused from "./components/matchFocused";
 import Generator from "./components/generator";
+import GameIssues from "./components/gameIssues";
+import ServiceSettings from "./components/serviceSettings";
 
 type TournamentData = {
     id: string,
@@ -18,35 +20,51 @@ type TournamentData = {
 function App() {
 
     const [tournaments, setTournaments] = useState<TournamentData[]>([]);
-    const [setCurrentTournament, setTournamentActive] = useTournamentsStore(useShallow((state) => [state.set_id, state.set_active]))
+    const [setCurrentTournament, setTournamentActive, setRaces] = useTournamentsStore(useShallow((state) => [state.set_id, state.set_active, state.set_races]))
 
     useEffect(() => {
+        loadTournaments()
+    }, [])
+
+    function loadTournaments() {
         invoke<TournamentData[]>("load_tournaments_list")
             .then((value) => setTournaments(value))
-    }, [])
+    }
 
     async function selectTournament(selectedId: string) {
         setTournamentActive(false);
         await invoke<Tournament>("load_tournament", {tournamentId: selectedId})
             .then((value) => {
                 invoke("load_heroes", {modType: value.mod_type});
+                invoke<Race[]>("load_races", {modType: value.mod_type})
+                    .then((races) => setRaces(races));
                 setCurrentTournament(value.id)
             })
     }
 
     return (
         <>
-            <Select
-                onChange={selectTournament}
-            >{tournaments.map((t, i) => (
-                <Select.Option key={i} value={t.id}>{t.name}</Select.Option>
-            ))}</Select>
+            <div style={{display: 'flex', flexDirection: 'row', gap: 10, alignItems: 'center'}}>
+                <Select
+                    style={{flexGrow: 1}}
+                    onChange={selectTournament}
+                >{tournaments.map((t, i) => (
+                    <Select.Option key={i} value={t.id}>{t.name}</Select.Option>
+                ))}</Select>
+                <Link to="settings">Settings</Link>
+            </div>
             <TournamentCore/>
             <div style={{height: '80%', width: '100%', display: 'flex', flexDirection: 'row'}}>
                 <div style={{height: '100%', width: '35%'}}>
                     <MatchesList/>
                 </div>
                 <Routes>
+                    <Route
+                        path="settings"
+                        element={
+                            <ServiceSettings onSaved={loadTournaments}/>
+                        }
+                    />
                     <Route
                         path="focus_match/:id"
                         element={
@@ -55,6 +73,7 @@ function App() {
                     />
                 </Routes>
             </div>
+            <GameIssues/>
             <Generator/>
         </>
     )
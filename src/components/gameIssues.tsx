@@ -0,0 +1,41 @@
+import { Button, List, Typography } from "antd";
+import { invoke } from "@tauri-apps/api/core";
+import { useState } from "react";
+import { Link } from "react-router";
+import useTournamentsStore from "../stores/tournament";
+import { errorMessage } from "../types/error";
+import { GameIssue, IssueSeverity } from "../types/tournament";
+
+function GameIssues() {
+    const currentTournament = useTournamentsStore((state) => state.selected_id);
+    const locale = useTournamentsStore((state) => state.locale);
+    const [issues, setIssues] = useState<GameIssue[] | null>(null);
+    const [error, setError] = useState<string | null>(null);
+
+    async function checkGames() {
+        setError(null);
+        await invoke<GameIssue[]>("invoke_validation", {tournamentId: currentTournament, locale: locale})
+            .then((values) => setIssues(values))
+            .catch((reason) => setError(errorMessage(reason)))
+    }
+
+    return <div style={{paddingTop: 15, display: 'flex', flexDirection: 'column', gap: 5}}>
+        <Button disabled={currentTournament == null} onClick={() => checkGames()}>Check games</Button>
+        {error ? <Typography.Text type="danger">{error}</Typography.Text> : null}
+        {issues != null && issues.length == 0 ? <Typography.Text type="success">No problems found</Typography.Text> : null}
+        {issues != null && issues.length > 0 ?
+            <List size="small">{issues.map((issue, i) => (
+                <List.Item key={i}>
+                    <Link to={`focus_match/${issue.match_id}`}>
+                        <Typography.Text type={issue.severity == IssueSeverity.Error ? "danger" : "warning"}>
+                            {`${issue.players}: ${issue.description}`}
+                        </Typography.Text>
+                    </Link>
+                </List.Item>
+            ))}</List> : 
+            null
+        }
+    </div>
+}
+
+export default GameIssues;
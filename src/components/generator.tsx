@@ -1,18 +1,323 @@
-import { Button } from "antd";
+import { Button, Checkbox, InputNumber, Progress, Segmented, Select, Typography } from "antd";
 import useTournamentsStore from "../stores/tournament";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { open, save } from "@tauri-apps/plugin-dialog";
+import { useEffect, useState } from "react";
+import { useShallow } from "zustand/shallow";
+import { errorMessage } from "../types/error";
+import { ComparisonSettings, DEFAULT_HEATMAPS, HeadToHeadFormat, Heatmap, HeatmapSettings, Locale, Match, SeasonFilter, TournamentSource, WorkbookSettings } from "../types/tournament";
+
+type GamesExportPaths = {
+    games: string,
+    rejected: string
+}
+
+type TournamentData = {
+    id: string,
+    name: string
+}
+
+type PlayerData = {
+    id: string,
+    nickname: string
+}
+
+type GenerationProgress =
+    | {kind: "Fetching", stage: string}
+    | {kind: "SheetBuilt", name: string, built: number, total: number}
+    | {kind: "PlayerProcessed", nickname: string, processed: number, total: number}
+    | {kind: "Warning", message: string}
+    | {kind: "Saving"}
+
+const HEATMAP_TABLES: {key: keyof HeatmapSettings, label: string}[] = [
+    {key: "race_games", label: "Race games"},
+    {key: "race_winrates", label: "Race winrates"},
+    {key: "match_up_games", label: "Matchup games"},
+    {key: "match_up_winrates", label: "Matchup winrates"},
+    {key: "hero_picks", label: "Hero picks"},
+    {key: "hero_winrates", label: "Hero winrates"},
+    {key: "hero_match_ups", label: "Hero vs hero"}
+]
+
+const HEATMAP_OPTIONS = [
+    {label: "Off", value: Heatmap.Off},
+    {label: "Color scale", value: Heatmap.ColorScale},
+    {label: "Data bars", value: Heatmap.DataBar}
+]
+
+type ProgressState = {
+    percent: number,
+    step: string
+}
 
 function Generator() {
-    
-    const currentTournament = useTournamentsStore((state) => state.selected_id);
+
+    const [currentTournament, tournamentIsActive] = useTournamentsStore(useShallow((state) => [state.selected_id, state.active]));
+    const [locale, setLocale] = useTournamentsStore(useShallow((state) => [state.locale, state.set_locale]));
+    const [savedPath, setSavedPath] = useState<string | null>(null);
+    const [error, setError] = useState<string | null>(null);
+    const [busy, setBusy] = useState<boolean>(false);
+    // id of running command, cancel stops only it
+    const [operationId, setOperationId] = useState<string | null>(null);
+    const [progress, setProgress] = useState<ProgressState | null>(null);
+    const [warnings, setWarnings] = useState<string[]>([]);
+    const [showIntervals, setShowIntervals] = useState<boolean>(true);
+    const [minGames, setMinGames] = useState<number | null>(null);
+    const [heatmaps, setHeatmaps] = useState<HeatmapSettings>(DEFAULT_HEATMAPS);
+    const [tournaments, setTournaments] = useState<TournamentData[]>([]);
+    const [seasonTournaments, setSeasonTournaments] = useState<string[]>([]);
+    const [seasonFilter, setSeasonFilter] = useState<SeasonFilter>({mod_type: "Universe", game_type: "Rmg"});
+    const [previousTournament, setPreviousTournament] = useState<string | null>(null);
+    const [metaPickRate, setMetaPickRate] = useState<number>(5);
+    const [players, setPlayers] = useState<PlayerData[]>([]);
+    const [firstPlayer, setFirstPlayer] = useState<string | null>(null);
+    const [secondPlayer, setSecondPlayer] = useState<string | null>(null);
+
+    const workbookSettings: WorkbookSettings = {locale: locale, winrates: {show_intervals: showIntervals, min_games: minGames}, heatmaps: heatmaps};
+
+    useEffect(() => {
+        invoke<TournamentData[]>("load_tournaments_list")
+            .then((value) => setTournaments(value))
+    }, [])
+
+    // players of selected tournament are taken from its matches
+    useEffect(() => {
+        if (tournamentIsActive) {
+            invoke<Match[]>("load_matches", {tournamentId: currentTournament})
+                .then((matches) => {
+                    const known = new Map<string, string>();
+                    matches.forEach((m) => {
+                        known.set(m.first_user_id, m.first_user_nickname);
+                        known.set(m.second_user_id, m.second_user_nickname);
+                    });
+                    setPlayers(Array.from(known, ([id, nickname]) => ({id: id, nickname: nickname})));
+                })
+        }
+    }, [tournamentIsActive, currentTournament])
+
+    useEffect(() => {
+        const unlisten = listen<GenerationProgress>("generation-progress", (event) => {
+            const payload = event.payload;
+            switch (payload.kind) {
+                case "Fetching":
+                    setProgress({percent: 0, step: `Loading ${payload.stage.toLowerCase()}`});
+                    break;
+                case "SheetBuilt":
+                    setProgress({percent: Math.round(payload.built / payload.total * 100), step: `Built ${payload.name}`});
+                    break;
+                case "PlayerProcessed":
+                    setProgress((current) => ({percent: current?.percent ?? 0, step: `Player ${payload.nickname} (${payload.processed}/${payload.total})`}));
+                    break;
+                case "Warning":
+                    setWarnings((current) => [...current, payload.message]);
+                    break;
+                case "Saving":
+                    setProgress({percent: 100, step: "Saving"});
+                    break;
+            }
+        });
+        return () => {
+            unlisten.then((stop) => stop());
+        }
+    }, [])
+
+    async function runCommand<T>(command: string, args: Record<string, unknown>, describe: (value: T) => string) {
+        const operation = crypto.randomUUID();
+        setSavedPath(null);
+        setError(null);
+        setProgress(null);
+        setWarnings([]);
+        setBusy(true);
+        setOperationId(operation);
+        await invoke<T>(command, {...args, operationId: operation})
+            .then((value) => setSavedPath(describe(value)))
+            .catch((reason) => setError(errorMessage(reason)))
+            .finally(() => {
+                setBusy(false);
+                setOperationId(null);
+                setProgress(null);
+            })
+    }
+
+    async function selectOutput(defaultPath: string, filterName: string, extension: string) {
+        return await save({
+            defaultPath: defaultPath,
+            filters: [{name: filterName, extensions: [extension]}]
+        });
+    }
 
     async function startGeneration() {
-        await invoke("invoke_generation", {tournamentId: currentTournament})
+        const path = await selectOutput("stats.xlsx", "Excel", "xlsx");
+        if (path == null) {
+            return;
+        }
+        // save dialog already asked user about replacing existing file
+        await runCommand<string>("invoke_generation", {tournamentId: currentTournament, output: {path: path, overwrite: true}, settings: workbookSettings}, (value) => value);
+    }
+
+    async function startSeasonGeneration() {
+        const path = await selectOutput("season.xlsx", "Excel", "xlsx");
+        if (path == null) {
+            return;
+        }
+        await runCommand<string>(
+            "invoke_season_generation",
+            {tournamentIds: seasonTournaments, filter: seasonFilter, output: {path: path, overwrite: true}, settings: workbookSettings},
+            (value) => value
+        );
+    }
+
+    // currently selected tournament is compared with the previous one
+    async function startComparison() {
+        const path = await selectOutput("comparison.xlsx", "Excel", "xlsx");
+        if (path == null) {
+            return;
+        }
+        const previous: TournamentSource = {tournament: previousTournament!};
+        const current: TournamentSource = {tournament: currentTournament!};
+        const settings: ComparisonSettings = {meta_pick_ratio: metaPickRate / 100};
+        await runCommand<string>(
+            "invoke_comparison",
+            {previous: previous, current: current, output: {path: path, overwrite: true}, settings: settings, locale: locale},
+            (value) => value
+        );
+    }
+
+    // history of players is collected over season tournaments if any are selected
+    async function startHeadToHeadExport(format: HeadToHeadFormat) {
+        const path = await selectOutput(`head_to_head.${format}`, format.toUpperCase(), format);
+        if (path == null) {
+            return;
+        }
+        const tournamentIds = seasonTournaments.length > 0 ? seasonTournaments : [currentTournament];
+        await runCommand<string>(
+            "invoke_head_to_head_export",
+            {tournamentIds: tournamentIds, firstUser: firstPlayer, secondUser: secondPlayer, format: format, output: {path: path, overwrite: true}, settings: workbookSettings},
+            (value) => value
+        );
+    }
+
+    async function startJsonExport() {
+        const path = await selectOutput("stats.json", "JSON", "json");
+        if (path == null) {
+            return;
+        }
+        await runCommand<string>("invoke_json_export", {tournamentId: currentTournament, output: {path: path, overwrite: true}}, (value) => value);
+    }
+
+    async function startHtmlExport() {
+        const path = await selectOutput("stats.html", "HTML", "html");
+        if (path == null) {
+            return;
+        }
+        await runCommand<string>("invoke_html_export", {tournamentId: currentTournament, output: {path: path, overwrite: true}, settings: workbookSettings}, (value) => value);
+    }
+
+    async function startGamesExport() {
+        const path = await selectOutput("games.csv", "CSV", "csv");
+        if (path == null) {
+            return;
+        }
+        await runCommand<GamesExportPaths>(
+            "invoke_games_export",
+            {tournamentId: currentTournament, output: {path: path, overwrite: true}},
+            (value) => `${value.games}, ${value.rejected}`
+        );
+    }
+
+    async function saveSnapshot() {
+        const path = await selectOutput("snapshot.json", "Snapshot", "json");
+        if (path == null) {
+            return;
+        }
+        await runCommand<string>("invoke_snapshot_save", {tournamentId: currentTournament, output: {path: path, overwrite: true}}, (value) => value);
+    }
+
+    // works without API, so doesn't need selected tournament
+    async function startSnapshotGeneration() {
+        const snapshotPath = await open({
+            multiple: false,
+            filters: [{name: "Snapshot", extensions: ["json"]}]
+        });
+        if (snapshotPath == null) {
+            return;
+        }
+        const path = await selectOutput("stats.xlsx", "Excel", "xlsx");
+        if (path == null) {
+            return;
+        }
+        await runCommand<string>("invoke_snapshot_generation", {snapshotPath: snapshotPath, output: {path: path, overwrite: true}, settings: workbookSettings}, (value) => value);
     }
 
-    return <div style={{paddingTop: 15}}>
-        <Button onClick={() => startGeneration()}>Generate stats</Button>
+    return <div style={{paddingTop: 15, display: 'flex', flexDirection: 'column', gap: 5}}>
+        <Segmented value={locale} onChange={(value) => setLocale(value as Locale)} options={[{label: "Русский", value: Locale.Ru}, {label: "English", value: Locale.En}]}/>
+        <Checkbox checked={showIntervals} onChange={(e) => setShowIntervals(e.target.checked)}>Confidence intervals in winrate notes</Checkbox>
+        <InputNumber min={1} value={minGames} placeholder="Grey out winrates below N games" style={{width: 260}} onChange={(value) => setMinGames(value)}/>
+        {HEATMAP_TABLES.map((table) => (
+            <div key={table.key} style={{display: 'flex', alignItems: 'center', gap: 5}}>
+                <Typography.Text style={{width: 130}}>{table.label}</Typography.Text>
+                <Select value={heatmaps[table.key]} options={HEATMAP_OPTIONS} style={{width: 130}} onChange={(value) => setHeatmaps({...heatmaps, [table.key]: value})}/>
+            </div>
+        ))}
+        <Button disabled={currentTournament == null || busy} onClick={() => startGeneration()}>Generate stats</Button>
+        <Select
+            mode="multiple"
+            placeholder="Season tournaments"
+            value={seasonTournaments}
+            options={tournaments.map((t) => ({label: t.name, value: t.id}))}
+            onChange={(value) => setSeasonTournaments(value)}
+        />
+        <div style={{display: 'flex', gap: 5}}>
+            <Segmented value={seasonFilter.mod_type} onChange={(value) => setSeasonFilter({...seasonFilter, mod_type: value as SeasonFilter["mod_type"]})} options={["Universe", "Hrta"]}/>
+            <Segmented value={seasonFilter.game_type} onChange={(value) => setSeasonFilter({...seasonFilter, game_type: value as SeasonFilter["game_type"]})} options={["Rmg", "Arena"]}/>
+        </div>
+        <Button disabled={seasonTournaments.length == 0 || busy} onClick={() => startSeasonGeneration()}>Generate season stats</Button>
+        <div style={{display: 'flex', gap: 5}}>
+            <Select
+                placeholder="Previous tournament"
+                value={previousTournament}
+                options={tournaments.map((t) => ({label: t.name, value: t.id}))}
+                style={{flexGrow: 1}}
+                onChange={(value) => setPreviousTournament(value)}
+            />
+            <InputNumber min={0} max={100} value={metaPickRate} addonAfter="% meta" style={{width: 140}} onChange={(value) => setMetaPickRate(value ?? 0)}/>
+        </div>
+        <Button disabled={currentTournament == null || previousTournament == null || busy} onClick={() => startComparison()}>Compare with previous</Button>
+        <div style={{display: 'flex', gap: 5}}>
+            {[[firstPlayer, setFirstPlayer] as const, [secondPlayer, setSecondPlayer] as const].map(([value, setValue], i) => (
+                <Select
+                    key={i}
+                    showSearch
+                    optionFilterProp="label"
+                    placeholder={i == 0 ? "First player" : "Second player"}
+                    value={value}
+                    options={players.map((p) => ({label: p.nickname, value: p.id}))}
+                    style={{flexGrow: 1}}
+                    onChange={(player) => setValue(player)}
+                />
+            ))}
+        </div>
+        <div style={{display: 'flex', gap: 5}}>
+            <Button disabled={firstPlayer == null || secondPlayer == null || firstPlayer == secondPlayer || busy} onClick={() => startHeadToHeadExport("xlsx")}>Head to head xlsx</Button>
+            <Button disabled={firstPlayer == null || secondPlayer == null || firstPlayer == secondPlayer || busy} onClick={() => startHeadToHeadExport("html")}>Head to head HTML</Button>
+        </div>
+        <Button disabled={currentTournament == null || busy} onClick={() => startJsonExport()}>Export JSON</Button>
+        <Button disabled={currentTournament == null || busy} onClick={() => startHtmlExport()}>Export HTML</Button>
+        <Button disabled={currentTournament == null || busy} onClick={() => startGamesExport()}>Export games CSV</Button>
+        <Button disabled={currentTournament == null || busy} onClick={() => saveSnapshot()}>Save snapshot</Button>
+        <Button disabled={busy} onClick={() => startSnapshotGeneration()}>Generate from snapshot</Button>
+        {busy && operationId != null ? <Button danger onClick={() => invoke("cancel_operation", {operationId: operationId})}>Cancel</Button> : null}
+        {progress ? <>
+            <Progress percent={progress.percent}/>
+            <Typography.Text type="secondary">{progress.step}</Typography.Text>
+        </> : null}
+        {savedPath ? <Typography.Text type="success">{`Saved to ${savedPath}`}</Typography.Text> : null}
+        {error ? <Typography.Text type="danger">{error}</Typography.Text> : null}
+        {warnings.map((warning, i) => (
+            <Typography.Text key={i} type="warning">{warning}</Typography.Text>
+        ))}
     </div>
 }
 
-export default Generator;
\ No newline at end of file
+export default Generator;
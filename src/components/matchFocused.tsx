@@ -2,7 +2,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { Segmented, Select } from "antd";
 import { useEffect, useState } from "react";
 import { useParams } from "react-router";
-import { BargainsColor, Game, GameOutcome, GameResult, Hero, racesData } from "../types/tournament";
+import { BargainsColor, Game, GameOutcome, GameResult, Hero } from "../types/tournament";
 import useTournamentsStore from "../stores/tournament";
 
 function MatchFocused() {
@@ -109,6 +109,7 @@ function PlayerDataRenderer({race, hero, updateRace, updateHero}: {
     updateHero: (h: number) => void
 }) {
     const [heroesData, setHeroesData] = useState<Hero[]>([]);
+    const races = useTournamentsStore((state) => state.races);
     
     useEffect(() => {
         console.log(race)
@@ -128,8 +129,8 @@ function PlayerDataRenderer({race, hero, updateRace, updateHero}: {
         <Select
             onChange={updateRace}
             value={race}
-        >{Array.from(racesData.entries()).map((v, i) => (
-            <Select.Option key={i} value={v[0]}>{v[1]}</Select.Option>
+        >{races.map((r, i) => (
+            <Select.Option key={i} value={r.id}>{r.name}</Select.Option>
         ))}</Select>
         <Select
             onChange={updateHero}
@@ -0,0 +1,104 @@
+import { Button, Input, InputNumber, Select, Typography } from "antd";
+import { invoke } from "@tauri-apps/api/core";
+import { useEffect, useState } from "react";
+import { errorMessage } from "../types/error";
+import { ENVIRONMENTS, LogLevel, ServiceConfig } from "../types/settings";
+
+function ServiceSettings(props: {onSaved: () => void}) {
+    const [config, setConfig] = useState<ServiceConfig | null>(null);
+    const [error, setError] = useState<string | null>(null);
+    const [saved, setSaved] = useState<boolean>(false);
+    const [logLevel, setLogLevel] = useState<LogLevel>(LogLevel.Info);
+    const [diagnosticsCopied, setDiagnosticsCopied] = useState<boolean>(false);
+
+    useEffect(() => {
+        invoke<ServiceConfig>("load_service_config")
+            .then((value) => setConfig(value))
+        invoke<LogLevel>("load_log_level")
+            .then((value) => setLogLevel(value))
+    }, [])
+
+    async function updateLogLevel(level: LogLevel) {
+        setError(null);
+        await invoke("update_log_level", {level: level})
+            .then(() => setLogLevel(level))
+            .catch((reason) => setError(errorMessage(reason)))
+    }
+
+    async function copyDiagnostics() {
+        setError(null);
+        setDiagnosticsCopied(false);
+        await invoke<string>("get_diagnostics")
+            .then((text) => navigator.clipboard.writeText(text))
+            .then(() => setDiagnosticsCopied(true))
+            .catch((reason) => setError(errorMessage(reason)))
+    }
+
+    function update(changes: Partial<ServiceConfig>) {
+        if (config) {
+            setSaved(false);
+            setConfig({...config, ...changes});
+        }
+    }
+
+    async function saveConfig() {
+        setError(null);
+        await invoke("update_service_config", {config: config})
+            .then(() => {
+                setSaved(true);
+                props.onSaved();
+            })
+            .catch((reason) => setError(errorMessage(reason)))
+    }
+
+    if (config == null) {
+        return null;
+    }
+
+    return <div style={{display: 'flex', flexDirection: 'column', gap: 5, padding: 10, width: '50%'}}>
+        <Typography.Text>Environment</Typography.Text>
+        <Select
+            value={ENVIRONMENTS.find((e) => e.endpoint == config.endpoint)?.endpoint ?? null}
+            placeholder="Custom"
+            onChange={(value) => update({endpoint: value})}
+        >{ENVIRONMENTS.map((e, i) => (
+            <Select.Option key={i} value={e.endpoint}>{e.name}</Select.Option>
+        ))}</Select>
+        <Typography.Text>Endpoint</Typography.Text>
+        <Input value={config.endpoint} onChange={(e) => update({endpoint: e.target.value})}/>
+        <Typography.Text>Timeout, seconds</Typography.Text>
+        <InputNumber min={1} value={config.timeout_secs} onChange={(value) => update({timeout_secs: value ?? config.timeout_secs})}/>
+        <Typography.Text>Connect timeout, seconds</Typography.Text>
+        <InputNumber min={1} value={config.connect_timeout_secs} onChange={(value) => update({connect_timeout_secs: value ?? config.connect_timeout_secs})}/>
+        <Typography.Text>Attempts</Typography.Text>
+        <InputNumber min={1} value={config.retry.max_attempts} onChange={(value) => update({retry: {...config.retry, max_attempts: value ?? 1}})}/>
+        <Typography.Text>Initial backoff, ms</Typography.Text>
+        <InputNumber min={0} value={config.retry.initial_backoff_ms} onChange={(value) => update({retry: {...config.retry, initial_backoff_ms: value ?? 0}})}/>
+        <Typography.Text>Max backoff, ms</Typography.Text>
+        <InputNumber min={0} value={config.retry.max_backoff_ms} onChange={(value) => update({retry: {...config.retry, max_backoff_ms: value ?? 0}})}/>
+        <Typography.Text>Auth header</Typography.Text>
+        <div style={{display: 'flex', flexDirection: 'row', gap: 5}}>
+            <Input
+                placeholder="Authorization"
+                value={config.auth_header?.name ?? ""}
+                onChange={(e) => update({auth_header: {name: e.target.value, value: config.auth_header?.value ?? ""}})}
+            />
+            <Input.Password
+                value={config.auth_header?.value ?? ""}
+                onChange={(e) => update({auth_header: {name: config.auth_header?.name || "Authorization", value: e.target.value}})}
+            />
+            <Button onClick={() => update({auth_header: null})}>Clear</Button>
+        </div>
+        <Button type="primary" onClick={() => saveConfig()}>Save</Button>
+        {saved ? <Typography.Text type="success">Settings applied</Typography.Text> : null}
+        <Typography.Text>Log level</Typography.Text>
+        <Select value={logLevel} onChange={(value) => updateLogLevel(value)}>{Object.values(LogLevel).map((level) => (
+            <Select.Option key={level} value={level}>{level}</Select.Option>
+        ))}</Select>
+        <Button onClick={() => copyDiagnostics()}>Copy diagnostics</Button>
+        {diagnosticsCopied ? <Typography.Text type="success">Diagnostics copied to clipboard</Typography.Text> : null}
+        {error ? <Typography.Text type="danger">{error}</Typography.Text> : null}
+    </div>
+}
+
+export default ServiceSettings;
@@ -1,21 +1,28 @@
 import { create } from "zustand"
+import { Locale, Race } from "../types/tournament"
 
 type State = {
     selected_id: string | null,
     active: boolean,
-    focused_match: string | null
+    focused_match: string | null,
+    races: Race[],
+    locale: Locale
 }
 
 type Action = {
     set_id: (id: string) => void,
     set_active: (is_active: boolean) => void,
-    set_focused: (focused: string) => void
+    set_focused: (focused: string) => void,
+    set_races: (races: Race[]) => void,
+    set_locale: (locale: Locale) => void
 }
 
 const useTournamentsStore = create<State & Action>((set) => ({
     selected_id: null,
     active: false,
     focused_match: null,
+    races: [],
+    locale: Locale.Ru,
     set_id(id) {
         set({selected_id: id})
     },
@@ -25,6 +32,12 @@ const useTournamentsStore = create<State & Action>((set) => ({
     set_focused(focused) {
         set({focused_match: focused})
     },
+    set_races(races) {
+        set({races: races})
+    },
+    set_locale(locale) {
+        set({locale: locale})
+    },
 }))
 
 export default useTournamentsStore;
\ No newline at end of file
@@ -0,0 +1,14 @@
+// Error returned by commands, `kind` is name of backend error variant, e.g. `OutputExists` or `Cancelled`.
+export type CommandError = {
+    kind: string,
+    message: string
+}
+
+export function isCommandError(reason: unknown): reason is CommandError {
+    return typeof reason == "object" && reason != null && "kind" in reason && "message" in reason;
+}
+
+// Text of rejected command to show to user.
+export function errorMessage(reason: unknown): string {
+    return isCommandError(reason) ? reason.message : `${reason}`;
+}
@@ -0,0 +1,31 @@
+export type RetryPolicy = {
+    max_attempts: number,
+    initial_backoff_ms: number,
+    max_backoff_ms: number
+}
+
+export type AuthHeader = {
+    name: string,
+    value: string
+}
+
+export type ServiceConfig = {
+    endpoint: string,
+    timeout_secs: number,
+    connect_timeout_secs: number,
+    retry: RetryPolicy,
+    auth_header: AuthHeader | null
+}
+
+export enum LogLevel {
+    Error = "error",
+    Warn = "warn",
+    Info = "info",
+    Debug = "debug",
+    Trace = "trace"
+}
+
+export const ENVIRONMENTS: {name: string, endpoint: string}[] = [
+    {name: "Production", endpoint: "https://h5-tournaments-api-5epg.shuttle.app/"},
+    {name: "Local", endpoint: "http://localhost:8000/"}
+]
@@ -61,13 +61,79 @@ export type Hero = {
     name: number
 }
 
-export const racesData = new Map<number, string>([
-    [1, "Орден порядка"],
-    [2, "Инферно"],
-    [3, "Некрополис"],
-    [4, "Лесной союз"],
-    [5, "Лига теней"],
-    [6, "Академия волшебства"],
-    [7, "Северные кланы"],
-    [8, "Великая орда"]
-])
\ No newline at end of file
+export enum Locale {
+    Ru = "ru",
+    En = "en"
+}
+
+export type WinrateSettings = {
+    show_intervals: boolean,
+    min_games: number | null
+}
+
+export enum Heatmap {
+    Off = "off",
+    ColorScale = "color_scale",
+    DataBar = "data_bar"
+}
+
+export type HeatmapSettings = {
+    race_games: Heatmap,
+    race_winrates: Heatmap,
+    match_up_games: Heatmap,
+    match_up_winrates: Heatmap,
+    hero_picks: Heatmap,
+    hero_winrates: Heatmap,
+    hero_match_ups: Heatmap
+}
+
+export const DEFAULT_HEATMAPS: HeatmapSettings = {
+    race_games: Heatmap.DataBar,
+    race_winrates: Heatmap.ColorScale,
+    match_up_games: Heatmap.ColorScale,
+    match_up_winrates: Heatmap.ColorScale,
+    hero_picks: Heatmap.DataBar,
+    hero_winrates: Heatmap.ColorScale,
+    hero_match_ups: Heatmap.ColorScale
+}
+
+export type WorkbookSettings = {
+    locale: Locale,
+    winrates: WinrateSettings,
+    heatmaps: HeatmapSettings
+}
+
+/// Enum names as backend serializes them, season generation skips tournaments of other types.
+export type SeasonFilter = {
+    mod_type: "Universe" | "Hrta",
+    game_type: "Rmg" | "Arena"
+}
+
+/// Tournament is taken either from API by id or from snapshot file.
+export type TournamentSource =
+    | {tournament: string}
+    | {snapshot: string}
+
+export type ComparisonSettings = {
+    meta_pick_ratio: number
+}
+
+export type HeadToHeadFormat = "xlsx" | "html" | "json"
+
+export type Race = {
+    id: number,
+    name: string
+}
+
+export enum IssueSeverity {
+    Error = "Error",
+    Warning = "Warning"
+}
+
+export type GameIssue = {
+    game_id: string,
+    match_id: string,
+    players: string,
+    severity: IssueSeverity,
+    description: string
+}